@@ -0,0 +1,485 @@
+//! The arguments of `galois_lfsr!`, `fibonacci_lfsr!` and the other generator macros after the name,
+//! parsed the same way by the macros and by `lfsr-hdl`
+//!
+//! The width comes first, then the taps, either as numbers or in one named notation, optionally after
+//! the sequence length:
+//!
+//! ```text
+//! 8, 8, 6, 5, 4
+//! 8, 255, 4, 5, 6, 8
+//! 8, taps = [8, 6, 5, 4]
+//! 8, polynomial = x^8 + x^6 + x^5 + x^4 + 1
+//! 8, koopman = 0xB8
+//! 8, normal = 0x71
+//! 8, reversed = 0x8E
+//! 8, 255, reciprocal = 0x1D
+//! ```
+//!
+//! Without a sequence length the taps start with `width`, so the first number after the width
+//! is the sequence length if it isn't `width`, or if `width` comes again among the taps.
+//! Numbers are decimal, or hexadecimal, octal or binary with `0x`, `0o` or `0b`, with optional `_`
+//! separators and an integer type suffix. Widths and taps have to fit into `u32`, the taps given
+//! as numbers are kept in their order and the ones of a named notation are sorted from `width` down.
+//!
+//! ```rust
+//! use lfsr_base::arguments::Arguments;
+//!
+//! let arguments = Arguments::parse(&["8", "255", "reciprocal = 0x1D"]).unwrap();
+//! assert_eq!(arguments.width, 8);
+//! assert_eq!(arguments.sequence_length, Some(255));
+//! assert_eq!(arguments.taps(), &[8, 6, 5, 4]);
+//! ```
+//!
+//! Whether the taps describe an LFSR of that width is up to the caller.
+//!
+
+use core::convert::TryFrom;
+use core::fmt;
+
+use crate::gf2::{Notation, ParsePolyError, Poly};
+
+/// Most taps the arguments can give, one for each bit of the widest LFSR
+pub const MAX_TAPS: usize = 128;
+
+/// Why an argument doesn't parse
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArgumentErrorKind {
+    /// There are no taps after the width
+    NoTaps,
+    /// Not a number
+    Number,
+    /// A width or a tap doesn't fit into `u32`
+    TooLarge,
+    /// More than [`MAX_TAPS`] taps
+    TooManyTaps,
+    /// Taps are given by name more than once
+    DuplicateNotation,
+    /// Taps are given by name and there are numbers besides the sequence length
+    TapsAlreadyNamed,
+    /// A named argument isn't one of the notations
+    UnknownNotation,
+    /// `taps = ` isn't followed by `[...]`
+    TapList,
+    /// A notation needs a width in `1..=128`
+    Width,
+    /// A mask has bits beyond the width
+    MaskTooWide,
+    /// A polynomial doesn't parse
+    Polynomial(ParsePolyError),
+    /// A notation doesn't give a polynomial of degree `width` with a `+ 1`
+    NotFeedbackPolynomial(Poly),
+}
+
+/// An argument that doesn't parse, `argument` counts from the width on
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ArgumentError {
+    pub argument: usize,
+    pub kind: ArgumentErrorKind,
+}
+
+impl fmt::Display for ArgumentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ArgumentErrorKind::NoTaps => write!(f, "expected taps after the width"),
+            ArgumentErrorKind::Number => write!(f, "expected a number"),
+            ArgumentErrorKind::TooLarge => write!(f, "number doesn't fit into `u32`"),
+            ArgumentErrorKind::TooManyTaps => write!(f, "more than {} taps", MAX_TAPS),
+            ArgumentErrorKind::DuplicateNotation => write!(f, "taps are given more than once"),
+            ArgumentErrorKind::TapsAlreadyNamed => write!(f, "taps are already given by name"),
+            ArgumentErrorKind::UnknownNotation => write!(
+                f,
+                "expected one of taps, polynomial, koopman, normal, reversed or reciprocal"
+            ),
+            ArgumentErrorKind::TapList => write!(f, "expected `taps = [...]`"),
+            ArgumentErrorKind::Width => write!(f, "width has to be between 1 and 128 bits"),
+            ArgumentErrorKind::MaskTooWide => write!(f, "mask doesn't fit into the width"),
+            ArgumentErrorKind::Polynomial(err) => write!(f, "{}", err),
+            ArgumentErrorKind::NotFeedbackPolynomial(polynomial) => write!(
+                f,
+                "`{}` isn't a feedback polynomial of this width, of degree `width` with a `+ 1`",
+                polynomial
+            ),
+        }
+    }
+}
+
+/// Width, sequence length and taps given to a generator macro
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Arguments {
+    pub width: u32,
+    pub sequence_length: Option<u128>,
+    /// Argument the sequence length comes from
+    pub sequence_length_argument: Option<usize>,
+    /// Argument of the named notation, if the taps are given by name
+    pub named_argument: Option<usize>,
+    taps: [u32; MAX_TAPS],
+    taps_len: usize,
+    first_tap_argument: usize,
+}
+
+impl Arguments {
+    /// Parses the arguments after the name, each of them trimmed of its commas
+    pub fn parse(arguments: &[&str]) -> Result<Self, ArgumentError> {
+        let error = |argument, kind| ArgumentError { argument, kind };
+        let width_argument = arguments
+            .first()
+            .ok_or(error(0, ArgumentErrorKind::Number))?;
+        let width = parse_u32(width_argument).map_err(|kind| error(0, kind))?;
+        let mut ret = Self {
+            width,
+            sequence_length: None,
+            sequence_length_argument: None,
+            named_argument: None,
+            taps: [0; MAX_TAPS],
+            taps_len: 0,
+            first_tap_argument: 1,
+        };
+
+        for (ix, argument) in arguments.iter().enumerate().skip(1) {
+            if let Some((key, value)) = argument.split_once('=') {
+                if ret.named_argument.is_some() {
+                    return Err(error(ix, ArgumentErrorKind::DuplicateNotation));
+                }
+                ret.named_argument = Some(ix);
+                ret.named_taps(key.trim(), value.trim())
+                    .map_err(|kind| error(ix, kind))?;
+            }
+        }
+
+        // numbers are the taps, or the sequence length first
+        let named_argument = ret.named_argument;
+        let numbers = (1..arguments.len()).filter(|ix| Some(*ix) != named_argument);
+        let count = numbers.clone().count();
+        let mut numbers = numbers.peekable();
+        let width_again = numbers
+            .clone()
+            .skip(1)
+            .any(|ix| parse_number(arguments[ix]) == Some(u128::from(width)));
+        if let Some(first) = numbers.peek().copied() {
+            let first_value =
+                parse_number(arguments[first]).ok_or(error(first, ArgumentErrorKind::Number))?;
+            let is_length = match named_argument {
+                Some(_) => true,
+                None => count > 1 && (first_value != u128::from(width) || width_again),
+            };
+            if is_length {
+                ret.sequence_length = Some(first_value);
+                ret.sequence_length_argument = Some(first);
+                numbers.next();
+            }
+        }
+        if let Some(named) = named_argument {
+            if let Some(ix) = numbers.next() {
+                return Err(error(ix, ArgumentErrorKind::TapsAlreadyNamed));
+            }
+            ret.first_tap_argument = named;
+        } else {
+            ret.first_tap_argument = numbers.peek().copied().unwrap_or(arguments.len());
+            for ix in numbers {
+                let tap = parse_u32(arguments[ix]).map_err(|kind| error(ix, kind))?;
+                ret.push(tap).map_err(|kind| error(ix, kind))?;
+            }
+        }
+        if ret.taps_len == 0 {
+            return Err(error(arguments.len() - 1, ArgumentErrorKind::NoTaps));
+        }
+        Ok(ret)
+    }
+
+    /// The taps in the order they're given
+    pub fn taps(&self) -> &[u32] {
+        &self.taps[..self.taps_len]
+    }
+
+    /// Argument tap `ix` comes from, the named notation gives all of them
+    pub fn tap_argument(&self, ix: usize) -> usize {
+        match self.named_argument {
+            Some(named) => named,
+            None => self.first_tap_argument + ix,
+        }
+    }
+
+    fn push(&mut self, tap: u32) -> Result<(), ArgumentErrorKind> {
+        if self.taps_len == MAX_TAPS {
+            return Err(ArgumentErrorKind::TooManyTaps);
+        }
+        self.taps[self.taps_len] = tap;
+        self.taps_len += 1;
+        Ok(())
+    }
+
+    fn named_taps(&mut self, key: &str, value: &str) -> Result<(), ArgumentErrorKind> {
+        let notation = match key {
+            "taps" => {
+                let list = value
+                    .strip_prefix('[')
+                    .and_then(|value| value.strip_suffix(']'))
+                    .ok_or(ArgumentErrorKind::TapList)?;
+                for tap in split(list) {
+                    self.push(parse_u32(tap)?)?;
+                }
+                return Ok(());
+            }
+            "polynomial" => None,
+            "koopman" => Some(Notation::Koopman),
+            "normal" => Some(Notation::Normal),
+            "reversed" => Some(Notation::Reversed),
+            "reciprocal" => Some(Notation::Reciprocal),
+            _ => return Err(ArgumentErrorKind::UnknownNotation),
+        };
+        if !(1..=128).contains(&self.width) {
+            return Err(ArgumentErrorKind::Width);
+        }
+        let polynomial = match notation {
+            None => value
+                .parse::<Poly>()
+                .map_err(ArgumentErrorKind::Polynomial)?,
+            Some(notation) => {
+                let mask = parse_number(value).ok_or(ArgumentErrorKind::Number)?;
+                Poly::from_notation(notation, mask, self.width)
+                    .ok_or(ArgumentErrorKind::MaskTooWide)?
+            }
+        };
+        if polynomial.degree() != Some(self.width) || !polynomial.coefficient(0) {
+            return Err(ArgumentErrorKind::NotFeedbackPolynomial(polynomial));
+        }
+        for tap in polynomial.taps() {
+            self.push(tap)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits `s` on the commas outside of `[...]` into trimmed arguments, an empty last one is dropped
+pub fn split(s: &str) -> Split<'_> {
+    Split { rest: Some(s) }
+}
+
+/// Iterator returned by [`split`]
+#[derive(Clone, Debug)]
+pub struct Split<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Iterator for Split<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest?;
+        let mut depth = 0;
+        for (ix, c) in rest.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    self.rest = Some(&rest[ix + 1..]);
+                    return Some(rest[..ix].trim());
+                }
+                _ => {}
+            }
+        }
+        self.rest = None;
+        Some(rest.trim()).filter(|last| !last.is_empty())
+    }
+}
+
+/// A number that fits into `u32`
+fn parse_u32(s: &str) -> Result<u32, ArgumentErrorKind> {
+    let number = parse_number(s).ok_or(ArgumentErrorKind::Number)?;
+    u32::try_from(number).map_err(|_| ArgumentErrorKind::TooLarge)
+}
+
+/// A decimal, `0x` hexadecimal, `0o` octal or `0b` binary number with optional `_` separators
+/// and integer type suffix, `None` if it isn't one or doesn't fit into `u128`
+pub fn parse_number(s: &str) -> Option<u128> {
+    let s = s.trim();
+    let (radix, digits) = match s.get(..2) {
+        Some("0x") | Some("0X") => (16, &s[2..]),
+        Some("0o") => (8, &s[2..]),
+        Some("0b") => (2, &s[2..]),
+        _ => (10, s),
+    };
+    // neither `u` nor `i` is a hexadecimal digit
+    let digits = match digits.find(['u', 'i']) {
+        Some(suffix) => {
+            let suffix = &digits[suffix..];
+            let known = [
+                "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128",
+                "isize",
+            ];
+            if !known.contains(&suffix) {
+                return None;
+            }
+            &digits[..digits.len() - suffix.len()]
+        }
+        None => digits,
+    };
+    let mut ret = None;
+    for c in digits.chars() {
+        if c == '_' {
+            continue;
+        }
+        let digit = c.to_digit(radix)?;
+        ret = Some(
+            ret.unwrap_or(0u128)
+                .checked_mul(u128::from(radix))?
+                .checked_add(u128::from(digit))?,
+        );
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    fn taps(arguments: &[&str]) -> Result<(Option<u128>, Vec<u32>), ArgumentErrorKind> {
+        let parsed = Arguments::parse(arguments).map_err(|err| err.kind)?;
+        Ok((parsed.sequence_length, parsed.taps().to_vec()))
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_number("255"), Some(255));
+        assert_eq!(parse_number(" 0xB8 "), Some(0xb8));
+        assert_eq!(parse_number("0b1011_1000"), Some(0xb8));
+        assert_eq!(parse_number("0o270"), Some(0xb8));
+        assert_eq!(parse_number("24u64"), Some(24));
+        assert_eq!(parse_number("0xffu8"), Some(0xff));
+        assert_eq!(parse_number("1_000_000"), Some(1_000_000));
+        assert_eq!(parse_number(&u128::MAX.to_string()), Some(u128::MAX));
+        assert_eq!(
+            parse_number("340282366920938463463374607431768211456"),
+            None
+        );
+        assert_eq!(parse_number("24u7"), None);
+        assert_eq!(parse_number("0x"), None);
+        assert_eq!(parse_number("x"), None);
+        assert_eq!(parse_number(""), None);
+    }
+
+    #[test]
+    fn splits() {
+        let arguments: Vec<&str> = split(" A, 8 , taps = [8, 4, 3, 2], ").collect();
+        assert_eq!(arguments, ["A", "8", "taps = [8, 4, 3, 2]"]);
+        assert_eq!(split("A,,8").collect::<Vec<_>>(), ["A", "", "8"]);
+        assert_eq!(split("").count(), 0);
+    }
+
+    #[test]
+    fn sequence_length() {
+        assert_eq!(
+            taps(&["8", "8", "6", "5", "4"]),
+            Ok((None, std::vec![8, 6, 5, 4]))
+        );
+        assert_eq!(
+            taps(&["8", "255", "4", "5", "6", "8"]),
+            Ok((Some(255), std::vec![4, 5, 6, 8]))
+        );
+        // not the width, so it's a sequence length rather than a tap
+        assert_eq!(
+            taps(&["8", "4", "5", "6", "8"]),
+            Ok((Some(4), std::vec![5, 6, 8]))
+        );
+        assert_eq!(taps(&["4", "4", "4"]), Ok((Some(4), std::vec![4])));
+        assert_eq!(taps(&["8", "8"]), Ok((None, std::vec![8])));
+        assert_eq!(
+            taps(&["8", "255", "koopman = 0xB8"]),
+            Ok((Some(255), std::vec![8, 6, 5, 4]))
+        );
+    }
+
+    #[test]
+    fn notations() {
+        let expected = Ok((None, std::vec![8, 6, 5, 4]));
+        assert_eq!(taps(&["8", "taps = [8, 6, 5, 4]"]), expected);
+        assert_eq!(taps(&["8", "taps = [8 , 6 , 5 , 4 ,]"]), expected);
+        assert_eq!(
+            taps(&["8", "polynomial = x^8 + x^6 + x^5 + x^4 + 1"]),
+            expected
+        );
+        assert_eq!(
+            taps(&["8", "polynomial = x ^ 8 + x ^ 6 + x ^ 5 + x ^ 4 + 1"]),
+            expected
+        );
+        assert_eq!(taps(&["8", "koopman = 0xB8"]), expected);
+        assert_eq!(taps(&["8", "normal = 0x71"]), expected);
+        assert_eq!(taps(&["8", "reversed = 0x8E"]), expected);
+        assert_eq!(taps(&["8", "reciprocal = 0x1D"]), expected);
+        assert_eq!(
+            taps(&["24u64", "koopman=0xE10000"]),
+            Ok((None, std::vec![24, 23, 22, 17]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |arguments: &[&str]| Arguments::parse(arguments).unwrap_err();
+        let at = |argument, kind| ArgumentError { argument, kind };
+        assert_eq!(
+            error(&["4294967296", "1"]),
+            at(0, ArgumentErrorKind::TooLarge)
+        );
+        assert_eq!(
+            error(&["8", "8", "4294967300"]),
+            at(2, ArgumentErrorKind::TooLarge)
+        );
+        assert_eq!(
+            error(&["8", "taps = [8, 4294967300]"]),
+            at(1, ArgumentErrorKind::TooLarge)
+        );
+        assert_eq!(error(&["8", "8", "y"]), at(2, ArgumentErrorKind::Number));
+        assert_eq!(error(&["8"]), at(0, ArgumentErrorKind::NoTaps));
+        assert_eq!(
+            error(&["8", "koopman = 0xB8", "taps = [8]"]),
+            at(2, ArgumentErrorKind::DuplicateNotation)
+        );
+        assert_eq!(
+            error(&["8", "255", "8", "koopman = 0xB8"]),
+            at(2, ArgumentErrorKind::TapsAlreadyNamed)
+        );
+        assert_eq!(
+            error(&["8", "poly = 0xB8"]),
+            at(1, ArgumentErrorKind::UnknownNotation)
+        );
+        assert_eq!(error(&["8", "taps = 8"]), at(1, ArgumentErrorKind::TapList));
+        assert_eq!(
+            error(&["200", "koopman = 1"]),
+            at(1, ArgumentErrorKind::Width)
+        );
+        assert_eq!(
+            error(&["8", "koopman = 0x1B8"]),
+            at(1, ArgumentErrorKind::MaskTooWide)
+        );
+        assert_eq!(
+            error(&["8", "polynomial = x^8 + y"]),
+            at(1, ArgumentErrorKind::Polynomial(ParsePolyError::Term))
+        );
+        assert_eq!(
+            error(&["8", "polynomial = x^8 + x^4"]).kind,
+            ArgumentErrorKind::NotFeedbackPolynomial(Poly::from_taps(&[8, 4]) ^ Poly::ONE)
+        );
+        let many: Vec<String> = (0..=MAX_TAPS).map(|tap| (tap + 1).to_string()).collect();
+        let mut arguments = std::vec!["129"];
+        arguments.extend(many.iter().rev().map(|tap| tap.as_str()));
+        assert_eq!(
+            error(&arguments),
+            at(MAX_TAPS + 1, ArgumentErrorKind::TooManyTaps)
+        );
+    }
+
+    #[test]
+    fn tap_arguments() {
+        let positional = Arguments::parse(&["8", "255", "8", "6", "5", "4"]).unwrap();
+        assert_eq!(positional.sequence_length_argument, Some(1));
+        assert_eq!(positional.tap_argument(0), 2);
+        assert_eq!(positional.tap_argument(3), 5);
+        let named = Arguments::parse(&["8", "koopman = 0xB8", "255"]).unwrap();
+        assert_eq!(named.sequence_length_argument, Some(2));
+        assert_eq!(named.tap_argument(3), 1);
+    }
+}
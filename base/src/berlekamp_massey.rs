@@ -0,0 +1,144 @@
+//! [Berlekamp–Massey algorithm](https://en.wikipedia.org/wiki/Berlekamp%E2%80%93Massey_algorithm):
+//! the shortest LFSR that generates a sequence of bits
+//!
+//! The shortest LFSR is unique once at least twice its length bits are observed.
+
+use crate::gf2::Poly;
+use crate::runtime::FibonacciLfsr;
+
+/// The shortest LFSR that generates a sequence of bits
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Synthesis {
+    /// Length of the shortest LFSR that generates the sequence
+    pub linear_complexity: u32,
+    /// Connection polynomial `1 + c_1 x + ... + c_L x^L` without the constant term, bit `i - 1` is `c_i`,
+    /// so the set bits are 1-based taps
+    pub mask: u128,
+    /// The first `linear_complexity` bits of the sequence, the first one is bit `0`
+    pub initial_state: u128,
+}
+
+impl Synthesis {
+    /// `1 + c_1 x + ... + c_L x^L`, the same as the feedback polynomial of `galois_lfsr!` and `fibonacci_lfsr!`
+    pub fn connection_polynomial(&self) -> Poly {
+        (Poly::from(self.mask) * Poly::X) ^ Poly::ONE
+    }
+
+    /// Taps in decreasing order, as passed to `galois_lfsr!` or `fibonacci_lfsr!` with `linear_complexity`
+    /// as the width
+    pub fn taps(&self) -> impl Iterator<Item = u32> {
+        let mask = self.mask;
+        (1..=self.linear_complexity)
+            .rev()
+            .filter(move |tap| (mask >> (tap - 1)) & 1 == 1)
+    }
+
+    /// A Fibonacci LFSR whose [`OutputBit::Lsb`](crate::OutputBit::Lsb) bits are the sequence
+    ///
+    /// `None` if `c_L` is `0`, then the sequence starts with bits that never repeat and no LFSR
+    /// that can count down generates it, or if all the bits are `0`.
+    pub fn lfsr(&self) -> Option<FibonacciLfsr> {
+        if self.linear_complexity == 0 || (self.mask >> (self.linear_complexity - 1)) & 1 == 0 {
+            return None;
+        }
+        Some(FibonacciLfsr {
+            width: self.linear_complexity,
+            mask: self.mask,
+            state: self.initial_state,
+        })
+    }
+}
+
+/// Finds the shortest LFSR that generates `bits`, `None` if it's longer than 128 bits
+pub fn berlekamp_massey<I: IntoIterator<Item = bool>>(bits: I) -> Option<Synthesis> {
+    // connection polynomial and the one before the last length change, without the constant terms
+    let mut connection = 0u128;
+    let mut previous = 0u128;
+    let mut linear_complexity = 0u32;
+    // steps since the last length change
+    let mut shift = 1u32;
+    // the last 128 bits, the latest one is bit `0`
+    let mut history = 0u128;
+    let mut first_bits = 0u128;
+
+    for (n, bit) in bits.into_iter().enumerate() {
+        if n < 128 {
+            first_bits |= (bit as u128) << n;
+        }
+
+        let discrepancy = bit ^ ((connection & history).count_ones() & 1 == 1);
+        if discrepancy {
+            let length = if 2 * linear_complexity <= n as u32 {
+                n as u32 + 1 - linear_complexity
+            } else {
+                linear_complexity
+            };
+            if length > 128 {
+                return None;
+            }
+            // `connection + x^shift * previous`, its degree is at most `length`
+            let updated =
+                connection ^ (1 << (shift - 1)) ^ previous.checked_shl(shift).unwrap_or(0);
+            if length != linear_complexity {
+                previous = connection;
+                linear_complexity = length;
+                shift = 1;
+            } else {
+                shift += 1;
+            }
+            connection = updated;
+        } else {
+            shift = shift.saturating_add(1);
+        }
+
+        history = history << 1 | bit as u128;
+    }
+
+    let state_mask = u128::MAX
+        .checked_shr(128 - linear_complexity)
+        .unwrap_or(0);
+    Some(Synthesis {
+        linear_complexity,
+        mask: connection,
+        initial_state: first_bits & state_mask,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OutputBit, LFSR};
+
+    #[test]
+    fn recovers_fibonacci_lfsr() {
+        let lfsr = FibonacciLfsr::new(16, &[16, 14, 13, 11], 0xACE1).unwrap();
+        let synthesis = berlekamp_massey(lfsr.bits(OutputBit::Lsb).take(32)).unwrap();
+        assert_eq!(synthesis.linear_complexity, 16);
+        assert!(synthesis.taps().eq([16, 14, 13, 11].iter().copied()));
+        assert_eq!(synthesis.lfsr(), Some(lfsr));
+    }
+
+    #[test]
+    fn degenerate_sequences() {
+        let zeros = berlekamp_massey([false; 10].iter().copied()).unwrap();
+        assert_eq!(zeros.linear_complexity, 0);
+        assert_eq!(zeros.lfsr(), None);
+
+        let ones = berlekamp_massey([true; 10].iter().copied()).unwrap();
+        assert_eq!(ones.linear_complexity, 1);
+        assert!(ones.lfsr().unwrap().bits(OutputBit::Lsb).take(20).all(|bit| bit));
+
+        // `1000` needs 1 bit of state, but it isn't fed back
+        let transient = berlekamp_massey([true, false, false, false].iter().copied()).unwrap();
+        assert_eq!(transient.linear_complexity, 1);
+        assert_eq!(transient.mask, 0);
+        assert_eq!(transient.lfsr(), None);
+
+        let periodic = berlekamp_massey([false, false, false, true].iter().copied()).unwrap();
+        assert_eq!(periodic.linear_complexity, 4);
+        assert!(periodic.taps().eq([4].iter().copied()));
+
+        let too_long = (0..300).map(|n| n == 299);
+        assert_eq!(berlekamp_massey(too_long), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Conversions between Galois and Fibonacci LFSRs that output the same bit stream
+//!
+//! The output of both is the least significant bit of the state. A Galois LFSR divides its state
+//! by `x` modulo the feedback polynomial, a Fibonacci one holds the next `width` output bits and
+//! computes the following one from its taps, and with the same taps both streams obey the same
+//! linear recurrence. So the equivalent Fibonacci polynomial of a Galois LFSR is its own feedback
+//! polynomial, taps and all, and only the states differ: the Fibonacci state is the next `width`
+//! output bits of the Galois LFSR, and those bits determine the Galois state.
+//!
+//! ```rust
+//! use lfsr_base::{GaloisLfsr, LFSR};
+//!
+//! let galois = GaloisLfsr::new(16, &[16, 15, 13, 4], 0xACE1).unwrap();
+//! let fibonacci = galois.to_fibonacci();
+//! assert_eq!(fibonacci.mask, galois.mask);
+//! assert_eq!(fibonacci.to_galois(), galois);
+//!
+//! let galois_bits = galois.states().take(1000).map(|state| state & 1);
+//! let fibonacci_bits = fibonacci.states().take(1000).map(|state| state & 1);
+//! assert!(galois_bits.eq(fibonacci_bits));
+//! ```
+//!
+//! A reference register with the same taps but another state outputs the same stream shifted,
+//! [`alignment`] finds by how much.
+//!
+
+use crate::{FibonacciLfsr, GaloisLfsr};
+
+impl GaloisLfsr {
+    /// The Fibonacci LFSR with the same taps and the state that outputs the same bit stream
+    pub fn to_fibonacci(&self) -> FibonacciLfsr {
+        FibonacciLfsr {
+            width: self.width,
+            mask: self.mask,
+            state: self.fibonacci_state(self.state),
+        }
+    }
+
+    /// State of the Fibonacci LFSR with the same taps that outputs the same bit stream as `state`:
+    /// the next `width` output bits, the first one in the least significant bit
+    pub fn fibonacci_state(&self, state: u128) -> u128 {
+        let mut fibonacci_state = 0;
+        let mut state = state;
+        for i in 0..self.width {
+            fibonacci_state |= (state & 1) << i;
+            state = self.up(state);
+        }
+        fibonacci_state
+    }
+
+    /// The inverse of [`GaloisLfsr::fibonacci_state`], the state that outputs the bits
+    /// of `fibonacci_state` first
+    pub fn galois_state(&self, fibonacci_state: u128) -> u128 {
+        // after `i` steps the state is the initial one shifted down by `i` bits, XORed with
+        // the mask shifted down for every output bit so far, so bit `i` of the initial state
+        // is output bit `i` corrected by the masks
+        let mut state = 0;
+        let mut correction = 0;
+        for i in 0..self.width {
+            let output = (fibonacci_state >> i) & 1;
+            state |= (output ^ (correction & 1)) << i;
+            correction = (correction >> 1) ^ (output.wrapping_neg() & self.mask);
+        }
+        state
+    }
+}
+
+impl FibonacciLfsr {
+    /// The Galois LFSR with the same taps and the state that outputs the same bit stream
+    pub fn to_galois(&self) -> GaloisLfsr {
+        let galois = GaloisLfsr {
+            width: self.width,
+            mask: self.mask,
+            state: 0,
+        };
+        GaloisLfsr {
+            state: galois.galois_state(self.state),
+            ..galois
+        }
+    }
+}
+
+/// Number of steps `fibonacci` has to make to output the same bit stream as `galois` from now on,
+/// found by stepping it up to `limit` times
+///
+/// `None` if the taps differ or the streams don't line up within `limit` steps. The period minus
+/// the alignment is the number of steps `galois` has to make instead. For maximum length LFSRs
+/// positions from a `discrete_log_lfsr_lookup!` of both Galois states give it without stepping,
+/// with [`FibonacciLfsr::to_galois`] for the Fibonacci one.
+pub fn alignment(galois: &GaloisLfsr, fibonacci: &FibonacciLfsr, limit: u128) -> Option<u128> {
+    if galois.width != fibonacci.width || galois.mask != fibonacci.mask {
+        return None;
+    }
+    let target = galois.fibonacci_state(galois.state);
+    let mut state = fibonacci.state;
+    let mut steps = 0;
+    while state != target {
+        if steps == limit {
+            return None;
+        }
+        state = fibonacci.up(state);
+        steps += 1;
+    }
+    Some(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LFSR;
+
+    #[test]
+    fn state_mapping() {
+        for taps in [&[8, 6, 5, 4][..], &[4, 2], &[7, 1], &[1]].iter() {
+            let width = taps[0];
+            let galois = GaloisLfsr::new(width, taps, 0).unwrap();
+            for state in 0..1 << width {
+                let fibonacci_state = galois.fibonacci_state(state);
+                assert_eq!(galois.galois_state(fibonacci_state), state);
+
+                let mut galois = GaloisLfsr { state, ..galois };
+                let mut fibonacci = galois.to_fibonacci();
+                assert_eq!(fibonacci.state, fibonacci_state);
+                for _ in 0..300 {
+                    assert_eq!(galois.state & 1, fibonacci.state & 1);
+                    galois.inc();
+                    fibonacci.inc();
+                    assert_eq!(fibonacci.to_galois(), galois);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wide_registers() {
+        let galois = GaloisLfsr::new(128, &[128, 126, 101, 99], 0x0123_4567_89ab_cdef).unwrap();
+        let fibonacci = galois.to_fibonacci();
+        assert_eq!(fibonacci.to_galois(), galois);
+        let galois_bits = galois.states().take(500).map(|state| state & 1);
+        let fibonacci_bits = fibonacci.states().take(500).map(|state| state & 1);
+        assert!(galois_bits.eq(fibonacci_bits));
+    }
+
+    #[test]
+    fn alignments() {
+        let galois = GaloisLfsr::new(16, &[16, 15, 13, 4], 1).unwrap();
+        let mut fibonacci = FibonacciLfsr::new(16, &[16, 15, 13, 4], 1).unwrap();
+        let steps = alignment(&galois, &fibonacci, u128::MAX).unwrap();
+        fibonacci.advance(steps);
+        assert_eq!(fibonacci, galois.to_fibonacci());
+        assert_eq!(alignment(&galois, &fibonacci, 0), Some(0));
+
+        // one step ahead, a whole period but one behind
+        fibonacci.inc();
+        assert_eq!(alignment(&galois, &fibonacci, 65533), None);
+        assert_eq!(alignment(&galois, &fibonacci, 65534), Some(65534));
+
+        let other = FibonacciLfsr::new(16, &[16, 14, 13, 11], 1).unwrap();
+        assert_eq!(alignment(&galois, &other, u128::MAX), None);
+    }
+}
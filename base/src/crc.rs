@@ -0,0 +1,1245 @@
+//! Cyclic redundancy checks in the Rocksoft^tm model of Ross Williams'
+//! [A Painless Guide to CRC Error Detection Algorithms](http://www.ross.net/crc/download/crc_v3.txt)
+//!
+//! A CRC register is a Galois LFSR: without reflection it shifts towards the most significant bit,
+//! which is [`GaloisLfsr::down`] for the generator polynomial itself, and with reflected input it
+//! shifts towards the least significant bit, which is [`GaloisLfsr::up`] for the reciprocal
+//! polynomial. Data bits are XORed into the bit that's about to be shifted out.
+//!
+//! [`CrcParams`] holds the parameters of the model: `width`, `poly` in the normal notation, the
+//! `init` value of the register, whether input bytes and the output are reflected, the `xorout`
+//! value applied to the output and the `check` value, the CRC of the ASCII string `"123456789"`.
+//! The [`CATALOGUE`] lists the CRC-8, CRC-16, CRC-32 and CRC-64 algorithms of Greg Cook's
+//! [catalogue](https://reveng.sourceforge.io/crc-catalogue/all.htm) under the same names.
+//!
+//! [`Crc`] goes bit by bit through the LFSR, [`CrcTable`] a byte at a time through a table of 256 entries.
+//! Both implement [`CrcEngine`] and compute the same checksums, in one go or piece by piece:
+//!
+//! ```rust
+//! use lfsr_base::crc::{Crc, CrcEngine, CrcTable, CRC_32_ISO_HDLC};
+//!
+//! let crc = CrcTable::new(CRC_32_ISO_HDLC).unwrap();
+//! assert_eq!(crc.checksum(b"123456789"), 0xCBF43926);
+//!
+//! let bitwise = Crc::new(CRC_32_ISO_HDLC).unwrap();
+//! let mut digest = bitwise.digest();
+//! digest.update(b"1234");
+//! digest.update(b"56789");
+//! assert_eq!(digest.finalize(), 0xCBF43926);
+//! ```
+//!
+
+use core::fmt;
+
+use crate::gf2::{Notation, Poly};
+use crate::GaloisLfsr;
+
+/// Why CRC parameters don't describe a CRC
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CrcError {
+    /// Width isn't in `1..=128`
+    Width(u32),
+    /// The named parameter has bits beyond the width
+    TooWide(&'static str),
+    /// The polynomial has no `+ 1`, so it's not the feedback polynomial of an LFSR
+    NoConstantTerm,
+    /// The CRC of `"123456789"` isn't the stated `check` value
+    Check { stated: u128, actual: u128 },
+}
+
+impl fmt::Display for CrcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrcError::Width(width) => write!(f, "width {} is not in 1..=128", width),
+            CrcError::TooWide(parameter) => write!(f, "{} doesn't fit into the width", parameter),
+            CrcError::NoConstantTerm => write!(f, "the polynomial has no `+ 1`"),
+            CrcError::Check { stated, actual } => write!(
+                f,
+                "the check value is {:#x}, not {:#x} as stated",
+                actual, stated
+            ),
+        }
+    }
+}
+
+/// Parameters of a CRC in the Rocksoft^tm model
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CrcParams {
+    /// Name in the catalogue, as in `CRC-32/ISO-HDLC`
+    pub name: &'static str,
+    /// Other names the CRC is known by
+    pub aliases: &'static [&'static str],
+    /// Number of bits in the register and in the CRC, `1..=128`
+    pub width: u32,
+    /// Generator polynomial in the normal notation, bit `i` is `x^i` and `x^width` is implicit
+    pub poly: u128,
+    /// Register before the first byte, unreflected
+    pub init: u128,
+    /// Bytes go in least significant bit first
+    pub refin: bool,
+    /// The register is reflected before `xorout`
+    pub refout: bool,
+    /// XORed into the CRC at the end
+    pub xorout: u128,
+    /// CRC of the ASCII string `"123456789"`
+    pub check: u128,
+}
+
+impl CrcParams {
+    /// Parameters not in the catalogue, their `check` is computed
+    pub fn new(
+        width: u32,
+        poly: u128,
+        init: u128,
+        refin: bool,
+        refout: bool,
+        xorout: u128,
+    ) -> Result<Self, CrcError> {
+        let mut params = preset("", &[], width, poly, init, refin, refout, xorout, 0);
+        params.check = Crc::new(params)?.checksum(CHECK_INPUT);
+        Ok(params)
+    }
+
+    /// Looks up a CRC in the [`CATALOGUE`] by its name or an alias, ignoring case
+    pub fn find(name: &str) -> Option<&'static CrcParams> {
+        CATALOGUE.iter().find(|params| {
+            params.name.eq_ignore_ascii_case(name)
+                || params
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Generator polynomial with the `x^width` term
+    pub fn polynomial(&self) -> Poly {
+        Poly::from(self.poly) ^ Poly::monomial(self.width)
+    }
+
+    /// Checks that the parameters fit the width and the polynomial has a `+ 1`
+    pub fn validate(&self) -> Result<(), CrcError> {
+        if !(1..=128).contains(&self.width) {
+            return Err(CrcError::Width(self.width));
+        }
+        let fits = |value: u128| value & !state_mask(self.width) == 0;
+        for (parameter, value) in [
+            ("poly", self.poly),
+            ("init", self.init),
+            ("xorout", self.xorout),
+            ("check", self.check),
+        ]
+        .iter()
+        {
+            if !fits(*value) {
+                return Err(CrcError::TooWide(parameter));
+            }
+        }
+        if self.poly & 1 == 0 {
+            return Err(CrcError::NoConstantTerm);
+        }
+        Ok(())
+    }
+
+    /// Validates the parameters and computes the CRC of `"123456789"` to compare with `check`
+    pub fn verify(&self) -> Result<(), CrcError> {
+        let actual = Crc::new(*self)?.checksum(CHECK_INPUT);
+        if actual != self.check {
+            return Err(CrcError::Check {
+                stated: self.check,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// The Galois LFSR the register steps like, see the [module documentation](self)
+    pub fn lfsr(&self) -> Result<GaloisLfsr, CrcError> {
+        self.validate()?;
+        let polynomial = if self.refin {
+            self.polynomial().reciprocal()
+        } else {
+            self.polynomial()
+        };
+        Ok(GaloisLfsr {
+            width: self.width,
+            mask: polynomial
+                .to_notation(Notation::Koopman)
+                .expect("the degree is the width"),
+            state: 0,
+        })
+    }
+}
+
+/// The input of the `check` value
+pub const CHECK_INPUT: &[u8] = b"123456789";
+
+fn state_mask(width: u32) -> u128 {
+    u128::MAX >> (128 - width)
+}
+
+fn reflect(value: u128, width: u32) -> u128 {
+    value.reverse_bits() >> (128 - width)
+}
+
+/// The register of a reflected CRC holds the CRC reflected, the unreflected register
+/// of a [`CrcTable`] is shifted up by `align` bits to be at least a byte wide
+fn initial_register(params: &CrcParams, align: u32) -> u128 {
+    if params.refin {
+        reflect(params.init, params.width)
+    } else {
+        params.init << align
+    }
+}
+
+fn final_crc(params: &CrcParams, align: u32, register: u128) -> u128 {
+    let crc = if params.refin {
+        register
+    } else {
+        register >> align
+    };
+    let crc = if params.refin != params.refout {
+        reflect(crc, params.width)
+    } else {
+        crc
+    };
+    crc ^ params.xorout
+}
+
+/// A way to compute CRCs, both engines give the same results
+pub trait CrcEngine {
+    fn params(&self) -> &CrcParams;
+    /// The register before any data
+    fn initial_register(&self) -> u128;
+    /// The register after `data`
+    fn update_register(&self, register: u128, data: &[u8]) -> u128;
+    /// The CRC of the data that made up `register`
+    fn final_crc(&self, register: u128) -> u128;
+
+    fn checksum(&self, data: &[u8]) -> u128 {
+        self.final_crc(self.update_register(self.initial_register(), data))
+    }
+
+    /// Starts computing a CRC of data that comes in pieces
+    fn digest(&self) -> Digest<'_, Self> {
+        Digest {
+            engine: self,
+            register: self.initial_register(),
+        }
+    }
+}
+
+/// CRC of data fed so far
+#[derive(Clone, Debug)]
+pub struct Digest<'a, E: ?Sized> {
+    engine: &'a E,
+    register: u128,
+}
+
+impl<'a, E: CrcEngine + ?Sized> Digest<'a, E> {
+    pub fn update(&mut self, data: &[u8]) {
+        self.register = self.engine.update_register(self.register, data);
+    }
+
+    /// The CRC of everything fed so far, more data can be fed afterwards
+    pub fn finalize(&self) -> u128 {
+        self.engine.final_crc(self.register)
+    }
+}
+
+/// Bitwise CRC engine, a bit per step of the LFSR
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Crc {
+    params: CrcParams,
+    lfsr: GaloisLfsr,
+}
+
+impl Crc {
+    pub fn new(params: CrcParams) -> Result<Self, CrcError> {
+        Ok(Self {
+            lfsr: params.lfsr()?,
+            params,
+        })
+    }
+}
+
+impl CrcEngine for Crc {
+    fn params(&self) -> &CrcParams {
+        &self.params
+    }
+
+    fn initial_register(&self) -> u128 {
+        initial_register(&self.params, 0)
+    }
+
+    fn update_register(&self, mut register: u128, data: &[u8]) -> u128 {
+        let width = self.params.width;
+        for byte in data {
+            for i in 0..8 {
+                register = if self.params.refin {
+                    self.lfsr.up(register ^ u128::from((byte >> i) & 1))
+                } else {
+                    self.lfsr
+                        .down(register ^ (u128::from((byte >> (7 - i)) & 1) << (width - 1)))
+                };
+            }
+        }
+        register
+    }
+
+    fn final_crc(&self, register: u128) -> u128 {
+        final_crc(&self.params, 0, register)
+    }
+}
+
+/// Table-driven CRC engine, a byte per table lookup
+#[derive(Clone, Debug)]
+pub struct CrcTable {
+    params: CrcParams,
+    /// The unreflected register is shifted up by this many bits to be at least a byte wide
+    align: u32,
+    table: [u128; 256],
+}
+
+impl CrcTable {
+    pub fn new(params: CrcParams) -> Result<Self, CrcError> {
+        params.validate()?;
+        let width = params.width.max(8);
+        let align = width - params.width;
+        let mut table = [0u128; 256];
+        for (byte, entry) in table.iter_mut().enumerate() {
+            let mut register = byte as u128;
+            if params.refin {
+                let poly = reflect(params.poly, params.width);
+                for _ in 0..8 {
+                    register = (register >> 1) ^ ((register & 1).wrapping_neg() & poly);
+                }
+            } else {
+                let poly = params.poly << align;
+                register <<= width - 8;
+                for _ in 0..8 {
+                    let msb = (register >> (width - 1)) & 1;
+                    register = ((register << 1) & state_mask(width)) ^ (msb.wrapping_neg() & poly);
+                }
+            }
+            *entry = register;
+        }
+        Ok(Self {
+            params,
+            align,
+            table,
+        })
+    }
+
+    /// Entry `i` is the register after byte `i` fed into a zero one, unreflected entries are
+    /// shifted up as the register
+    pub fn table(&self) -> &[u128; 256] {
+        &self.table
+    }
+}
+
+impl CrcEngine for CrcTable {
+    fn params(&self) -> &CrcParams {
+        &self.params
+    }
+
+    fn initial_register(&self) -> u128 {
+        initial_register(&self.params, self.align)
+    }
+
+    fn update_register(&self, mut register: u128, data: &[u8]) -> u128 {
+        let width = self.params.width + self.align;
+        for byte in data {
+            register = if self.params.refin {
+                let index = (register as u8 ^ byte) as usize;
+                (register >> 8) ^ self.table[index]
+            } else {
+                let index = ((register >> (width - 8)) as u8 ^ byte) as usize;
+                ((register << 8) & state_mask(width)) ^ self.table[index]
+            };
+        }
+        register
+    }
+
+    fn final_crc(&self, register: u128) -> u128 {
+        final_crc(&self.params, self.align, register)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn preset(
+    name: &'static str,
+    aliases: &'static [&'static str],
+    width: u32,
+    poly: u128,
+    init: u128,
+    refin: bool,
+    refout: bool,
+    xorout: u128,
+    check: u128,
+) -> CrcParams {
+    CrcParams {
+        name,
+        aliases,
+        width,
+        poly,
+        init,
+        refin,
+        refout,
+        xorout,
+        check,
+    }
+}
+
+pub const CRC_8_AUTOSAR: CrcParams = preset(
+    "CRC-8/AUTOSAR",
+    &[],
+    8,
+    0x2f,
+    0xff,
+    false,
+    false,
+    0xff,
+    0xdf,
+);
+pub const CRC_8_BLUETOOTH: CrcParams = preset(
+    "CRC-8/BLUETOOTH",
+    &[],
+    8,
+    0xa7,
+    0x00,
+    true,
+    true,
+    0x00,
+    0x26,
+);
+pub const CRC_8_CDMA2000: CrcParams = preset(
+    "CRC-8/CDMA2000",
+    &[],
+    8,
+    0x9b,
+    0xff,
+    false,
+    false,
+    0x00,
+    0xda,
+);
+pub const CRC_8_DARC: CrcParams = preset("CRC-8/DARC", &[], 8, 0x39, 0x00, true, true, 0x00, 0x15);
+pub const CRC_8_DVB_S2: CrcParams =
+    preset("CRC-8/DVB-S2", &[], 8, 0xd5, 0x00, false, false, 0x00, 0xbc);
+pub const CRC_8_GSM_A: CrcParams =
+    preset("CRC-8/GSM-A", &[], 8, 0x1d, 0x00, false, false, 0x00, 0x37);
+pub const CRC_8_GSM_B: CrcParams =
+    preset("CRC-8/GSM-B", &[], 8, 0x49, 0x00, false, false, 0xff, 0x94);
+pub const CRC_8_HITAG: CrcParams =
+    preset("CRC-8/HITAG", &[], 8, 0x1d, 0xff, false, false, 0x00, 0xb4);
+pub const CRC_8_I_432_1: CrcParams = preset(
+    "CRC-8/I-432-1",
+    &["CRC-8/ITU"],
+    8,
+    0x07,
+    0x00,
+    false,
+    false,
+    0x55,
+    0xa1,
+);
+pub const CRC_8_I_CODE: CrcParams =
+    preset("CRC-8/I-CODE", &[], 8, 0x1d, 0xfd, false, false, 0x00, 0x7e);
+pub const CRC_8_LTE: CrcParams = preset("CRC-8/LTE", &[], 8, 0x9b, 0x00, false, false, 0x00, 0xea);
+pub const CRC_8_MAXIM_DOW: CrcParams = preset(
+    "CRC-8/MAXIM-DOW",
+    &["CRC-8/MAXIM", "DOW-CRC"],
+    8,
+    0x31,
+    0x00,
+    true,
+    true,
+    0x00,
+    0xa1,
+);
+pub const CRC_8_MIFARE_MAD: CrcParams = preset(
+    "CRC-8/MIFARE-MAD",
+    &[],
+    8,
+    0x1d,
+    0xc7,
+    false,
+    false,
+    0x00,
+    0x99,
+);
+pub const CRC_8_NRSC_5: CrcParams =
+    preset("CRC-8/NRSC-5", &[], 8, 0x31, 0xff, false, false, 0x00, 0xf7);
+pub const CRC_8_OPENSAFETY: CrcParams = preset(
+    "CRC-8/OPENSAFETY",
+    &[],
+    8,
+    0x2f,
+    0x00,
+    false,
+    false,
+    0x00,
+    0x3e,
+);
+pub const CRC_8_ROHC: CrcParams = preset("CRC-8/ROHC", &[], 8, 0x07, 0xff, true, true, 0x00, 0xd0);
+pub const CRC_8_SAE_J1850: CrcParams = preset(
+    "CRC-8/SAE-J1850",
+    &[],
+    8,
+    0x1d,
+    0xff,
+    false,
+    false,
+    0xff,
+    0x4b,
+);
+pub const CRC_8_SMBUS: CrcParams = preset(
+    "CRC-8/SMBUS",
+    &["CRC-8"],
+    8,
+    0x07,
+    0x00,
+    false,
+    false,
+    0x00,
+    0xf4,
+);
+pub const CRC_8_TECH_3250: CrcParams = preset(
+    "CRC-8/TECH-3250",
+    &["CRC-8/AES", "CRC-8/EBU"],
+    8,
+    0x1d,
+    0xff,
+    true,
+    true,
+    0x00,
+    0x97,
+);
+pub const CRC_8_WCDMA: CrcParams =
+    preset("CRC-8/WCDMA", &[], 8, 0x9b, 0x00, true, true, 0x00, 0x25);
+
+pub const CRC_16_ARC: CrcParams = preset(
+    "CRC-16/ARC",
+    &["ARC", "CRC-16", "CRC-16/LHA", "CRC-IBM"],
+    16,
+    0x8005,
+    0x0000,
+    true,
+    true,
+    0x0000,
+    0xbb3d,
+);
+pub const CRC_16_CDMA2000: CrcParams = preset(
+    "CRC-16/CDMA2000",
+    &[],
+    16,
+    0xc867,
+    0xffff,
+    false,
+    false,
+    0x0000,
+    0x4c06,
+);
+pub const CRC_16_CMS: CrcParams = preset(
+    "CRC-16/CMS",
+    &[],
+    16,
+    0x8005,
+    0xffff,
+    false,
+    false,
+    0x0000,
+    0xaee7,
+);
+pub const CRC_16_DDS_110: CrcParams = preset(
+    "CRC-16/DDS-110",
+    &[],
+    16,
+    0x8005,
+    0x800d,
+    false,
+    false,
+    0x0000,
+    0x9ecf,
+);
+pub const CRC_16_DECT_R: CrcParams = preset(
+    "CRC-16/DECT-R",
+    &["R-CRC-16"],
+    16,
+    0x0589,
+    0x0000,
+    false,
+    false,
+    0x0001,
+    0x007e,
+);
+pub const CRC_16_DECT_X: CrcParams = preset(
+    "CRC-16/DECT-X",
+    &["X-CRC-16"],
+    16,
+    0x0589,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0x007f,
+);
+pub const CRC_16_DNP: CrcParams = preset(
+    "CRC-16/DNP",
+    &[],
+    16,
+    0x3d65,
+    0x0000,
+    true,
+    true,
+    0xffff,
+    0xea82,
+);
+pub const CRC_16_EN_13757: CrcParams = preset(
+    "CRC-16/EN-13757",
+    &[],
+    16,
+    0x3d65,
+    0x0000,
+    false,
+    false,
+    0xffff,
+    0xc2b7,
+);
+pub const CRC_16_GENIBUS: CrcParams = preset(
+    "CRC-16/GENIBUS",
+    &["CRC-16/DARC", "CRC-16/EPC", "CRC-16/I-CODE"],
+    16,
+    0x1021,
+    0xffff,
+    false,
+    false,
+    0xffff,
+    0xd64e,
+);
+pub const CRC_16_GSM: CrcParams = preset(
+    "CRC-16/GSM",
+    &[],
+    16,
+    0x1021,
+    0x0000,
+    false,
+    false,
+    0xffff,
+    0xce3c,
+);
+pub const CRC_16_IBM_3740: CrcParams = preset(
+    "CRC-16/IBM-3740",
+    &["CRC-16/AUTOSAR", "CRC-16/CCITT-FALSE"],
+    16,
+    0x1021,
+    0xffff,
+    false,
+    false,
+    0x0000,
+    0x29b1,
+);
+pub const CRC_16_IBM_SDLC: CrcParams = preset(
+    "CRC-16/IBM-SDLC",
+    &["CRC-16/ISO-HDLC", "CRC-16/X-25", "CRC-B", "X-25"],
+    16,
+    0x1021,
+    0xffff,
+    true,
+    true,
+    0xffff,
+    0x906e,
+);
+pub const CRC_16_ISO_IEC_14443_3_A: CrcParams = preset(
+    "CRC-16/ISO-IEC-14443-3-A",
+    &["CRC-A"],
+    16,
+    0x1021,
+    0xc6c6,
+    true,
+    true,
+    0x0000,
+    0xbf05,
+);
+pub const CRC_16_KERMIT: CrcParams = preset(
+    "CRC-16/KERMIT",
+    &["CRC-16/CCITT", "CRC-16/CCITT-TRUE", "CRC-CCITT", "KERMIT"],
+    16,
+    0x1021,
+    0x0000,
+    true,
+    true,
+    0x0000,
+    0x2189,
+);
+pub const CRC_16_LJ1200: CrcParams = preset(
+    "CRC-16/LJ1200",
+    &[],
+    16,
+    0x6f63,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0xbdf4,
+);
+pub const CRC_16_M17: CrcParams = preset(
+    "CRC-16/M17",
+    &[],
+    16,
+    0x5935,
+    0xffff,
+    false,
+    false,
+    0x0000,
+    0x772b,
+);
+pub const CRC_16_MAXIM_DOW: CrcParams = preset(
+    "CRC-16/MAXIM-DOW",
+    &["CRC-16/MAXIM"],
+    16,
+    0x8005,
+    0x0000,
+    true,
+    true,
+    0xffff,
+    0x44c2,
+);
+pub const CRC_16_MCRF4XX: CrcParams = preset(
+    "CRC-16/MCRF4XX",
+    &[],
+    16,
+    0x1021,
+    0xffff,
+    true,
+    true,
+    0x0000,
+    0x6f91,
+);
+pub const CRC_16_MODBUS: CrcParams = preset(
+    "CRC-16/MODBUS",
+    &["MODBUS"],
+    16,
+    0x8005,
+    0xffff,
+    true,
+    true,
+    0x0000,
+    0x4b37,
+);
+pub const CRC_16_NRSC_5: CrcParams = preset(
+    "CRC-16/NRSC-5",
+    &[],
+    16,
+    0x080b,
+    0xffff,
+    true,
+    true,
+    0x0000,
+    0xa066,
+);
+pub const CRC_16_OPENSAFETY_A: CrcParams = preset(
+    "CRC-16/OPENSAFETY-A",
+    &[],
+    16,
+    0x5935,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0x5d38,
+);
+pub const CRC_16_OPENSAFETY_B: CrcParams = preset(
+    "CRC-16/OPENSAFETY-B",
+    &[],
+    16,
+    0x755b,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0x20fe,
+);
+pub const CRC_16_PROFIBUS: CrcParams = preset(
+    "CRC-16/PROFIBUS",
+    &["CRC-16/IEC-61158-2"],
+    16,
+    0x1dcf,
+    0xffff,
+    false,
+    false,
+    0xffff,
+    0xa819,
+);
+pub const CRC_16_RIELLO: CrcParams = preset(
+    "CRC-16/RIELLO",
+    &[],
+    16,
+    0x1021,
+    0xb2aa,
+    true,
+    true,
+    0x0000,
+    0x63d0,
+);
+pub const CRC_16_SPI_FUJITSU: CrcParams = preset(
+    "CRC-16/SPI-FUJITSU",
+    &["CRC-16/AUG-CCITT"],
+    16,
+    0x1021,
+    0x1d0f,
+    false,
+    false,
+    0x0000,
+    0xe5cc,
+);
+pub const CRC_16_T10_DIF: CrcParams = preset(
+    "CRC-16/T10-DIF",
+    &[],
+    16,
+    0x8bb7,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0xd0db,
+);
+pub const CRC_16_TELEDISK: CrcParams = preset(
+    "CRC-16/TELEDISK",
+    &[],
+    16,
+    0xa097,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0x0fb3,
+);
+pub const CRC_16_TMS37157: CrcParams = preset(
+    "CRC-16/TMS37157",
+    &[],
+    16,
+    0x1021,
+    0x89ec,
+    true,
+    true,
+    0x0000,
+    0x26b1,
+);
+pub const CRC_16_UMTS: CrcParams = preset(
+    "CRC-16/UMTS",
+    &["CRC-16/BUYPASS", "CRC-16/VERIFONE"],
+    16,
+    0x8005,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0xfee8,
+);
+pub const CRC_16_USB: CrcParams = preset(
+    "CRC-16/USB",
+    &[],
+    16,
+    0x8005,
+    0xffff,
+    true,
+    true,
+    0xffff,
+    0xb4c8,
+);
+pub const CRC_16_XMODEM: CrcParams = preset(
+    "CRC-16/XMODEM",
+    &[
+        "CRC-16/ACORN",
+        "CRC-16/LTE",
+        "CRC-16/V-41-MSB",
+        "XMODEM",
+        "ZMODEM",
+    ],
+    16,
+    0x1021,
+    0x0000,
+    false,
+    false,
+    0x0000,
+    0x31c3,
+);
+
+pub const CRC_32_AIXM: CrcParams = preset(
+    "CRC-32/AIXM",
+    &["CRC-32Q"],
+    32,
+    0x814141ab,
+    0x00000000,
+    false,
+    false,
+    0x00000000,
+    0x3010bf7f,
+);
+pub const CRC_32_AUTOSAR: CrcParams = preset(
+    "CRC-32/AUTOSAR",
+    &[],
+    32,
+    0xf4acfb13,
+    0xffffffff,
+    true,
+    true,
+    0xffffffff,
+    0x1697d06a,
+);
+pub const CRC_32_BASE91_D: CrcParams = preset(
+    "CRC-32/BASE91-D",
+    &["CRC-32D"],
+    32,
+    0xa833982b,
+    0xffffffff,
+    true,
+    true,
+    0xffffffff,
+    0x87315576,
+);
+pub const CRC_32_BZIP2: CrcParams = preset(
+    "CRC-32/BZIP2",
+    &["CRC-32/AAL5", "CRC-32/DECT-B", "B-CRC-32"],
+    32,
+    0x04c11db7,
+    0xffffffff,
+    false,
+    false,
+    0xffffffff,
+    0xfc891918,
+);
+pub const CRC_32_CD_ROM_EDC: CrcParams = preset(
+    "CRC-32/CD-ROM-EDC",
+    &[],
+    32,
+    0x8001801b,
+    0x00000000,
+    true,
+    true,
+    0x00000000,
+    0x6ec2edc4,
+);
+pub const CRC_32_CKSUM: CrcParams = preset(
+    "CRC-32/CKSUM",
+    &["CKSUM", "CRC-32/POSIX"],
+    32,
+    0x04c11db7,
+    0x00000000,
+    false,
+    false,
+    0xffffffff,
+    0x765e7680,
+);
+pub const CRC_32_ISCSI: CrcParams = preset(
+    "CRC-32/ISCSI",
+    &[
+        "CRC-32/BASE91-C",
+        "CRC-32/CASTAGNOLI",
+        "CRC-32/INTERLAKEN",
+        "CRC-32C",
+    ],
+    32,
+    0x1edc6f41,
+    0xffffffff,
+    true,
+    true,
+    0xffffffff,
+    0xe3069283,
+);
+pub const CRC_32_ISO_HDLC: CrcParams = preset(
+    "CRC-32/ISO-HDLC",
+    &[
+        "CRC-32",
+        "CRC-32/ADCCP",
+        "CRC-32/V-42",
+        "CRC-32/XZ",
+        "PKZIP",
+    ],
+    32,
+    0x04c11db7,
+    0xffffffff,
+    true,
+    true,
+    0xffffffff,
+    0xcbf43926,
+);
+pub const CRC_32_JAMCRC: CrcParams = preset(
+    "CRC-32/JAMCRC",
+    &["JAMCRC"],
+    32,
+    0x04c11db7,
+    0xffffffff,
+    true,
+    true,
+    0x00000000,
+    0x340bc6d9,
+);
+pub const CRC_32_MEF: CrcParams = preset(
+    "CRC-32/MEF",
+    &[],
+    32,
+    0x741b8cd7,
+    0xffffffff,
+    true,
+    true,
+    0x00000000,
+    0xd2c22f51,
+);
+pub const CRC_32_MPEG_2: CrcParams = preset(
+    "CRC-32/MPEG-2",
+    &[],
+    32,
+    0x04c11db7,
+    0xffffffff,
+    false,
+    false,
+    0x00000000,
+    0x0376e6e7,
+);
+pub const CRC_32_XFER: CrcParams = preset(
+    "CRC-32/XFER",
+    &["XFER"],
+    32,
+    0x000000af,
+    0x00000000,
+    false,
+    false,
+    0x00000000,
+    0xbd0be338,
+);
+
+pub const CRC_64_ECMA_182: CrcParams = preset(
+    "CRC-64/ECMA-182",
+    &["CRC-64"],
+    64,
+    0x42f0e1eba9ea3693,
+    0x0000000000000000,
+    false,
+    false,
+    0x0000000000000000,
+    0x6c40df5f0b497347,
+);
+pub const CRC_64_GO_ISO: CrcParams = preset(
+    "CRC-64/GO-ISO",
+    &[],
+    64,
+    0x000000000000001b,
+    0xffffffffffffffff,
+    true,
+    true,
+    0xffffffffffffffff,
+    0xb90956c775a41001,
+);
+pub const CRC_64_MS: CrcParams = preset(
+    "CRC-64/MS",
+    &[],
+    64,
+    0x259c84cba6426349,
+    0xffffffffffffffff,
+    true,
+    true,
+    0x0000000000000000,
+    0x75d4b74f024eceea,
+);
+pub const CRC_64_NVME: CrcParams = preset(
+    "CRC-64/NVME",
+    &[],
+    64,
+    0xad93d23594c93659,
+    0xffffffffffffffff,
+    true,
+    true,
+    0xffffffffffffffff,
+    0xae8b14860a799888,
+);
+pub const CRC_64_REDIS: CrcParams = preset(
+    "CRC-64/REDIS",
+    &[],
+    64,
+    0xad93d23594c935a9,
+    0x0000000000000000,
+    true,
+    true,
+    0x0000000000000000,
+    0xe9c6d914c4b8d9ca,
+);
+pub const CRC_64_WE: CrcParams = preset(
+    "CRC-64/WE",
+    &[],
+    64,
+    0x42f0e1eba9ea3693,
+    0xffffffffffffffff,
+    false,
+    false,
+    0xffffffffffffffff,
+    0x62ec59e3f1a4f00a,
+);
+pub const CRC_64_XZ: CrcParams = preset(
+    "CRC-64/XZ",
+    &["CRC-64/GO-ECMA"],
+    64,
+    0x42f0e1eba9ea3693,
+    0xffffffffffffffff,
+    true,
+    true,
+    0xffffffffffffffff,
+    0x995dc9bbdf1939fa,
+);
+
+/// All the presets, in the order of the catalogue
+pub const CATALOGUE: &[CrcParams] = &[
+    CRC_8_AUTOSAR,
+    CRC_8_BLUETOOTH,
+    CRC_8_CDMA2000,
+    CRC_8_DARC,
+    CRC_8_DVB_S2,
+    CRC_8_GSM_A,
+    CRC_8_GSM_B,
+    CRC_8_HITAG,
+    CRC_8_I_432_1,
+    CRC_8_I_CODE,
+    CRC_8_LTE,
+    CRC_8_MAXIM_DOW,
+    CRC_8_MIFARE_MAD,
+    CRC_8_NRSC_5,
+    CRC_8_OPENSAFETY,
+    CRC_8_ROHC,
+    CRC_8_SAE_J1850,
+    CRC_8_SMBUS,
+    CRC_8_TECH_3250,
+    CRC_8_WCDMA,
+    CRC_16_ARC,
+    CRC_16_CDMA2000,
+    CRC_16_CMS,
+    CRC_16_DDS_110,
+    CRC_16_DECT_R,
+    CRC_16_DECT_X,
+    CRC_16_DNP,
+    CRC_16_EN_13757,
+    CRC_16_GENIBUS,
+    CRC_16_GSM,
+    CRC_16_IBM_3740,
+    CRC_16_IBM_SDLC,
+    CRC_16_ISO_IEC_14443_3_A,
+    CRC_16_KERMIT,
+    CRC_16_LJ1200,
+    CRC_16_M17,
+    CRC_16_MAXIM_DOW,
+    CRC_16_MCRF4XX,
+    CRC_16_MODBUS,
+    CRC_16_NRSC_5,
+    CRC_16_OPENSAFETY_A,
+    CRC_16_OPENSAFETY_B,
+    CRC_16_PROFIBUS,
+    CRC_16_RIELLO,
+    CRC_16_SPI_FUJITSU,
+    CRC_16_T10_DIF,
+    CRC_16_TELEDISK,
+    CRC_16_TMS37157,
+    CRC_16_UMTS,
+    CRC_16_USB,
+    CRC_16_XMODEM,
+    CRC_32_AIXM,
+    CRC_32_AUTOSAR,
+    CRC_32_BASE91_D,
+    CRC_32_BZIP2,
+    CRC_32_CD_ROM_EDC,
+    CRC_32_CKSUM,
+    CRC_32_ISCSI,
+    CRC_32_ISO_HDLC,
+    CRC_32_JAMCRC,
+    CRC_32_MEF,
+    CRC_32_MPEG_2,
+    CRC_32_XFER,
+    CRC_64_ECMA_182,
+    CRC_64_GO_ISO,
+    CRC_64_MS,
+    CRC_64_NVME,
+    CRC_64_REDIS,
+    CRC_64_WE,
+    CRC_64_XZ,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogue_check_values() {
+        for params in CATALOGUE {
+            assert_eq!(params.verify(), Ok(()), "{}", params.name);
+            let table = CrcTable::new(*params).unwrap();
+            assert_eq!(table.checksum(CHECK_INPUT), params.check, "{}", params.name);
+        }
+    }
+
+    #[test]
+    fn other_widths() {
+        // refin and refout differ
+        let umts12 = CrcParams::new(12, 0x80f, 0, false, true, 0).unwrap();
+        assert_eq!(umts12.check, 0xdaf);
+        let gsm3 = CrcParams::new(3, 0x3, 0, false, false, 0x7).unwrap();
+        assert_eq!(gsm3.check, 0x4);
+        let usb5 = CrcParams::new(5, 0x05, 0x1f, true, true, 0x1f).unwrap();
+        assert_eq!(usb5.check, 0x19);
+        let darc82 = CrcParams::new(82, 0x0308c0111011401440411, 0, true, true, 0).unwrap();
+        assert_eq!(darc82.check, 0x09ea83f625023801fd612);
+
+        for params in [umts12, gsm3, usb5, darc82].iter() {
+            let table = CrcTable::new(*params).unwrap();
+            assert_eq!(table.checksum(CHECK_INPUT), params.check);
+        }
+    }
+
+    #[test]
+    fn engines_agree_piece_by_piece() {
+        let data: [u8; 100] = core::array::from_fn(|i| (i * 37 + 11) as u8);
+        for params in [CRC_8_ROHC, CRC_16_XMODEM, CRC_32_ISCSI, CRC_64_WE].iter() {
+            let bitwise = Crc::new(*params).unwrap();
+            let table = CrcTable::new(*params).unwrap();
+            let mut digest = table.digest();
+            for chunk in data.chunks(7) {
+                digest.update(chunk);
+            }
+            assert_eq!(digest.finalize(), bitwise.checksum(&data));
+        }
+    }
+
+    #[test]
+    fn lookup_and_errors() {
+        assert_eq!(CrcParams::find("crc-32"), Some(&CRC_32_ISO_HDLC));
+        assert_eq!(
+            CrcParams::find("CRC-16/CCITT-FALSE"),
+            Some(&CRC_16_IBM_3740)
+        );
+        assert_eq!(CrcParams::find("CRC-8/NOPE"), None);
+
+        assert_eq!(
+            CrcParams::new(0, 1, 0, false, false, 0),
+            Err(CrcError::Width(0))
+        );
+        assert_eq!(
+            CrcParams::new(8, 0x107, 0, false, false, 0),
+            Err(CrcError::TooWide("poly"))
+        );
+        assert_eq!(
+            CrcParams::new(8, 0x06, 0, false, false, 0),
+            Err(CrcError::NoConstantTerm)
+        );
+        let mut wrong = CRC_16_ARC;
+        wrong.check = 0x1234;
+        assert_eq!(
+            wrong.verify(),
+            Err(CrcError::Check {
+                stated: 0x1234,
+                actual: 0xbb3d
+            })
+        );
+
+        // a reflected register is the LFSR of the reciprocal polynomial
+        assert_eq!(CRC_8_ROHC.lfsr().unwrap().mask, 0xe0);
+        assert_eq!(CRC_8_SMBUS.lfsr().unwrap().mask, 0x83);
+    }
+}
@@ -0,0 +1,134 @@
+//! Discrete logarithms in GF(2^n), to find where a state is in the sequence of
+//! a maximum length Galois LFSR without a table of all of its states
+//!
+//! Uses Pohlig–Hellman over the prime factors of `2^n - 1` and baby-step giant-step
+//! within every subgroup of prime order `q`: a logarithm takes `q / baby_steps.len()`
+//! multiplications for each factor, so the table size trades memory for speed.
+
+use crate::gf2::{mul_mod, pow_mod, x_pow_mod};
+use crate::LFSRState;
+
+/// A subgroup of prime order `prime` of the multiplicative group of GF(2^n)
+pub struct Subgroup<T: 'static> {
+    /// A prime factor of `2^n - 1`
+    pub prime: u128,
+    /// How many times `prime` divides `2^n - 1`
+    pub multiplicity: u32,
+    /// `x^((2^n - 1) / prime)^-baby_steps.len()`
+    pub giant_step: T,
+    /// `(x^((2^n - 1) / prime)^i, i)` for `i` in `0..baby_steps.len()`, sorted by the element
+    pub baby_steps: &'static [(T, u32)],
+}
+
+/// GF(2^n) as polynomials modulo a primitive one, with tables to take logarithms
+pub struct Field<T: 'static> {
+    /// `n`
+    pub degree: u32,
+    /// The primitive polynomial without `x^n`
+    pub low: u128,
+    /// Every prime factor of `2^n - 1` has one
+    pub subgroups: &'static [Subgroup<T>],
+}
+
+impl<T: LFSRState> Subgroup<T> {
+    /// `i` such that `generator^i = element`, if `element` is in this subgroup
+    fn log(&self, element: u128, degree: u32, low: u128) -> Option<u128> {
+        let steps = self.baby_steps.len() as u128;
+        let giant_step = self.giant_step.to_u128();
+        let mut element = element;
+        let mut offset = 0;
+        while offset < self.prime {
+            if let Ok(ix) = self
+                .baby_steps
+                .binary_search_by_key(&T::from_u128(element), |(step, _)| *step)
+            {
+                return Some(offset + self.baby_steps[ix].1 as u128);
+            }
+            element = mul_mod(element, giant_step, degree, low);
+            offset += steps;
+        }
+        None
+    }
+}
+
+impl<T: LFSRState> Field<T> {
+    /// `k` such that `x^k = element`, `None` for `0`
+    pub fn log(&self, element: T) -> Option<u128> {
+        let element = element.to_u128();
+        if element == 0 {
+            return None;
+        }
+
+        let order = u128::MAX >> (128 - self.degree);
+        // `log mod modulus`, growing by Chinese remainder theorem
+        let mut log = 0;
+        let mut modulus = 1;
+        for subgroup in self.subgroups {
+            let prime = subgroup.prime;
+            // `log mod prime^multiplicity`, one base-`prime` digit at a time
+            let mut residue = 0;
+            let mut prime_power = 1;
+            for _ in 0..subgroup.multiplicity {
+                let rest = mul_mod(
+                    element,
+                    x_pow_mod(order - residue, self.degree, self.low),
+                    self.degree,
+                    self.low,
+                );
+                let projected = pow_mod(rest, order / prime_power / prime, self.degree, self.low);
+                residue += subgroup.log(projected, self.degree, self.low)? * prime_power;
+                prime_power *= prime;
+            }
+
+            let difference = (residue + prime_power - log % prime_power) % prime_power;
+            let inverse = inverse_mod_n(modulus % prime_power, prime_power);
+            log += modulus * mul_mod_n(difference, inverse, prime_power);
+            modulus *= prime_power;
+        }
+        Some(log)
+    }
+
+    /// Number of `up` steps from `1` to `state` of the Galois LFSR with this feedback polynomial,
+    /// `None` for `0`
+    pub fn galois_position(&self, state: T) -> Option<T> {
+        // `up` multiplies by `x^-1`, so the `k`-th state is `x^-k`
+        let order = u128::MAX >> (128 - self.degree);
+        self.log(state).map(|log| T::from_u128((order - log) % order))
+    }
+}
+
+/// `a * b mod n`, without overflowing for any `n`
+fn mul_mod_n(a: u128, b: u128, n: u128) -> u128 {
+    let mut ret = 0;
+    let mut a = a % n;
+    let mut b = b;
+    while b != 0 {
+        if b & 1 == 1 {
+            ret = add_mod_n(ret, a, n);
+        }
+        a = add_mod_n(a, a, n);
+        b >>= 1;
+    }
+    ret
+}
+
+fn add_mod_n(a: u128, b: u128, n: u128) -> u128 {
+    if a >= n - b {
+        a - (n - b)
+    } else {
+        a + b
+    }
+}
+
+/// Inverse of `a` modulo a prime power `n`, `a` has to be coprime with `n`
+fn inverse_mod_n(a: u128, n: u128) -> u128 {
+    // extended Euclid, coefficients of `a` are kept modulo `n`
+    let (mut r0, mut r1) = (n, a % n);
+    let (mut s0, mut s1) = (0, 1 % n);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, add_mod_n(s0, n - mul_mod_n(q, s1, n), n));
+    }
+    s0
+}
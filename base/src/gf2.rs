@@ -0,0 +1,822 @@
+//! Polynomials over GF(2)
+//!
+//! [`Poly`] holds polynomials up to degree 255, tests them for irreducibility and primitivity
+//! and finds their orders, which are periods of LFSRs.
+//! Free functions work on polynomials packed into a `u128`, bit `i` being the coefficient of `x^i`,
+//! modulo `x^degree + low`. They are `const fn`, so generated LFSRs can use them in their own `const fn`s.
+
+use core::fmt;
+use core::ops::{BitXor, Div, Mul, Rem};
+use core::str::FromStr;
+
+use crate::mersenne::MERSENNE_PRIME_FACTORS;
+
+/// A polynomial over GF(2) of degree up to 255, bit `i` is the coefficient of `x^i`
+///
+/// Feedback polynomials of LFSRs are `1 + x^tap + ...` for 1-based taps:
+///
+/// ```rust
+/// use lfsr_base::gf2::Poly;
+///
+/// let polynomial = Poly::from_taps(&[16, 14, 13, 11]);
+/// assert_eq!(polynomial.to_string(), "x^16 + x^14 + x^13 + x^11 + 1");
+/// assert!(polynomial.is_primitive());
+/// assert_eq!(polynomial.order(), 65535);
+/// assert_eq!(polynomial.reciprocal(), Poly::from_taps(&[16, 5, 3, 2]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Poly {
+    lo: u128,
+    hi: u128,
+}
+
+impl Poly {
+    pub const ZERO: Poly = Poly { lo: 0, hi: 0 };
+    pub const ONE: Poly = Poly { lo: 1, hi: 0 };
+    pub const X: Poly = Poly { lo: 2, hi: 0 };
+
+    /// `x^power`
+    pub fn monomial(power: u32) -> Self {
+        Self::ONE.shl(power)
+    }
+
+    /// Feedback polynomial `1 + x^tap + ...` of an LFSR with 1-based taps, as in `galois_lfsr!`
+    pub fn from_taps(taps: &[u32]) -> Self {
+        taps.iter()
+            .fold(Self::ONE, |poly, tap| poly ^ Self::monomial(*tap))
+    }
+
+    /// 1-based taps of a feedback polynomial `1 + x^tap + ...`, in decreasing order,
+    /// the inverse of [`Poly::from_taps`]
+    pub fn taps(self) -> impl Iterator<Item = u32> {
+        (1..=self.degree().unwrap_or(0))
+            .rev()
+            .filter(move |power| self.coefficient(*power))
+    }
+
+    /// Polynomial of degree `degree` written as a `degree`-bit mask in `notation`,
+    /// `None` if the mask doesn't fit into `degree` bits or the degree isn't in `1..=128`
+    ///
+    /// The result may have a lower degree or no `+ 1`, if the mask says so.
+    pub fn from_notation(notation: Notation, mask: u128, degree: u32) -> Option<Self> {
+        if !(1..=128).contains(&degree) || mask.checked_shr(degree).unwrap_or(0) != 0 {
+            return None;
+        }
+        let top = Self::monomial(degree);
+        Some(match notation {
+            Notation::Koopman => (Self::from(mask) * Self::X) ^ Self::ONE,
+            Notation::Normal => Self::from(mask) ^ top,
+            Notation::Reversed => Self::from(mask.reverse_bits() >> (128 - degree)) ^ top,
+            Notation::Reciprocal => (Self::from(mask) ^ top).reciprocal(),
+        })
+    }
+
+    /// The mask of this polynomial in `notation`, `None` for degrees outside of `1..=128`
+    pub fn to_notation(self, notation: Notation) -> Option<u128> {
+        let degree = self.degree().filter(|degree| (1..=128).contains(degree))?;
+        let normal = |poly: Self| {
+            (0..degree)
+                .filter(|power| poly.coefficient(*power))
+                .fold(0u128, |mask, power| mask | (1 << power))
+        };
+        Some(match notation {
+            Notation::Koopman => (1..=degree)
+                .filter(|power| self.coefficient(*power))
+                .fold(0u128, |mask, power| mask | (1 << (power - 1))),
+            Notation::Normal => normal(self),
+            Notation::Reversed => normal(self).reverse_bits() >> (128 - degree),
+            Notation::Reciprocal => normal(self.reciprocal()),
+        })
+    }
+
+    /// `None` if the degree is 128 or more
+    pub fn to_u128(self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+
+    /// `None` for the zero polynomial
+    pub fn degree(self) -> Option<u32> {
+        if self.hi != 0 {
+            Some(255 - self.hi.leading_zeros())
+        } else if self.lo != 0 {
+            Some(127 - self.lo.leading_zeros())
+        } else {
+            None
+        }
+    }
+
+    /// Coefficient of `x^power`
+    pub fn coefficient(self, power: u32) -> bool {
+        if power < 128 {
+            (self.lo >> power) & 1 == 1
+        } else {
+            (self.hi >> (power - 128)) & 1 == 1
+        }
+    }
+
+    /// `true` for the zero polynomial
+    pub fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    fn shl(self, n: u32) -> Self {
+        match n {
+            0 => self,
+            1..=127 => Poly {
+                lo: self.lo << n,
+                hi: (self.hi << n) | (self.lo >> (128 - n)),
+            },
+            _ => Poly {
+                lo: 0,
+                hi: self.lo << (n - 128),
+            },
+        }
+    }
+
+    fn shr(self, n: u32) -> Self {
+        match n {
+            0 => self,
+            1..=127 => Poly {
+                lo: (self.lo >> n) | (self.hi << (128 - n)),
+                hi: self.hi >> n,
+            },
+            _ => Poly {
+                lo: self.hi >> (n - 128),
+                hi: 0,
+            },
+        }
+    }
+
+    /// Quotient and remainder, panics if `divisor` is zero
+    pub fn div_rem(self, divisor: Self) -> (Self, Self) {
+        let divisor_degree = divisor.degree().expect("division by zero polynomial");
+        let mut quotient = Self::ZERO;
+        let mut remainder = self;
+        while let Some(degree) = remainder.degree() {
+            if degree < divisor_degree {
+                break;
+            }
+            let shift = degree - divisor_degree;
+            quotient = quotient ^ Self::monomial(shift);
+            remainder = remainder ^ divisor.shl(shift);
+        }
+        (quotient, remainder)
+    }
+
+    /// Greatest common divisor, `0` only if both are `0`
+    pub fn gcd(self, other: Self) -> Self {
+        let (mut a, mut b) = (self, other);
+        while !b.is_zero() {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// `self * other mod modulus`, the product itself doesn't have to fit
+    pub fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let modulus_degree = modulus.degree().expect("division by zero polynomial");
+        let a = self % modulus;
+        let b = other % modulus;
+        let mut ret = Self::ZERO;
+        if let Some(degree) = b.degree() {
+            for power in (0..=degree).rev() {
+                ret = ret.shl(1);
+                if ret.coefficient(modulus_degree) {
+                    ret = ret ^ modulus;
+                }
+                if b.coefficient(power) {
+                    ret = ret ^ a;
+                }
+            }
+        }
+        ret
+    }
+
+    /// `self^exponent mod modulus`
+    pub fn pow_mod(self, mut exponent: u128, modulus: Self) -> Self {
+        let mut base = self % modulus;
+        let mut ret = Self::ONE % modulus;
+        while exponent != 0 {
+            if exponent & 1 == 1 {
+                ret = ret.mul_mod(base, modulus);
+            }
+            base = base.mul_mod(base, modulus);
+            exponent >>= 1;
+        }
+        ret
+    }
+
+    /// Formal derivative
+    pub fn derivative(self) -> Self {
+        // odd powers survive and lose one degree, even powers vanish
+        let odd = Poly {
+            lo: 0xaaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa,
+            hi: 0xaaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa_aaaa,
+        };
+        Poly {
+            lo: self.lo & odd.lo,
+            hi: self.hi & odd.hi,
+        }
+        .shr(1)
+    }
+
+    /// `x^degree * self(1 / x)`, the coefficients in reverse order
+    ///
+    /// An LFSR with the reciprocal feedback polynomial runs through the same states backwards,
+    /// with the state bits reversed.
+    pub fn reciprocal(self) -> Self {
+        let mut ret = Self::ZERO;
+        if let Some(degree) = self.degree() {
+            for power in 0..=degree {
+                if self.coefficient(power) {
+                    ret = ret ^ Self::monomial(degree - power);
+                }
+            }
+        }
+        ret
+    }
+
+    /// `x^(2^n) mod self`
+    fn x_pow_pow2(self, n: u32) -> Self {
+        let mut ret = Self::X % self;
+        for _ in 0..n {
+            ret = ret.mul_mod(ret, self);
+        }
+        ret
+    }
+
+    /// `true` if this polynomial has no factors but `1` and itself, constants aren't irreducible
+    pub fn is_irreducible(self) -> bool {
+        // Rabin's test: `x^(2^n) = x mod self`, and `x^(2^(n/p)) - x` is coprime
+        // with `self` for every prime `p` that divides `n`
+        let degree = match self.degree() {
+            Some(degree) if degree > 0 => degree,
+            _ => return false,
+        };
+        if self.x_pow_pow2(degree) != Self::X % self {
+            return false;
+        }
+        let mut rest = degree;
+        let mut p = 2;
+        while rest > 1 {
+            if rest % p == 0 {
+                while rest % p == 0 {
+                    rest /= p;
+                }
+                if self.gcd(self.x_pow_pow2(degree / p) ^ Self::X) != Self::ONE {
+                    return false;
+                }
+            }
+            p += 1;
+        }
+        true
+    }
+
+    /// `true` for feedback polynomials of maximum length LFSRs: irreducible ones of order `2^n - 1`
+    ///
+    /// Degree has to be up to 128.
+    pub fn is_primitive(self) -> bool {
+        let degree = match self.degree() {
+            Some(degree) if degree > 0 && self.coefficient(0) => degree,
+            _ => return false,
+        };
+        assert!(degree <= 128, "primitivity of polynomials over degree 128 isn't supported");
+        let order = u128::MAX >> (128 - degree);
+        self.is_irreducible()
+            && MERSENNE_PRIME_FACTORS[degree as usize]
+                .iter()
+                .all(|p| Self::X.pow_mod(order / p, self) != Self::ONE)
+    }
+
+    /// Square root of a polynomial with only even powers of `x`
+    fn sqrt(self) -> Self {
+        let mut ret = Self::ZERO;
+        if let Some(degree) = self.degree() {
+            for power in (0..=degree).step_by(2) {
+                if self.coefficient(power) {
+                    ret = ret ^ Self::monomial(power / 2);
+                }
+            }
+        }
+        ret
+    }
+
+    /// Calls `f(factor, multiplicity)` for square-free, pairwise coprime factors
+    /// whose product, counting multiplicities, is `self`
+    fn square_free_factors(self, multiplicity: u32, f: &mut dyn FnMut(Self, u32)) {
+        let mut c = self.gcd(self.derivative());
+        let mut w = self / c;
+        let mut i = 1;
+        while w != Self::ONE {
+            let y = w.gcd(c);
+            let factor = w / y;
+            if factor != Self::ONE {
+                f(factor, i * multiplicity);
+            }
+            w = y;
+            c = c / y;
+            i += 1;
+        }
+        if c != Self::ONE {
+            c.sqrt().square_free_factors(multiplicity * 2, f);
+        }
+    }
+
+    /// Calls `f(product, degree)` for products of all the irreducible factors
+    /// of a square-free polynomial that have the same degree
+    fn distinct_degree_factors(self, f: &mut dyn FnMut(Self, u32)) {
+        let mut rest = self;
+        let mut h = Self::X;
+        let mut degree = 1;
+        while rest.degree().unwrap_or(0) >= 2 * degree {
+            h = h.mul_mod(h, rest);
+            let factor = rest.gcd(h ^ Self::X);
+            if factor != Self::ONE {
+                f(factor, degree);
+                rest = rest / factor;
+                h = h % rest;
+            }
+            degree += 1;
+        }
+        if let Some(degree) = rest.degree().filter(|d| *d > 0) {
+            f(rest, degree);
+        }
+    }
+
+    /// Smallest `k > 0` such that `x^k = 1 (mod self)`
+    ///
+    /// The constant term has to be `1`, otherwise no such `k` exists. Irreducible factors
+    /// have to be of degree up to 128.
+    pub fn order(self) -> u128 {
+        assert!(self.coefficient(0), "polynomial is divisible by x");
+
+        let mut ret = 1;
+        let mut max_multiplicity = 1;
+        self.square_free_factors(1, &mut |factor, multiplicity| {
+            max_multiplicity = max_multiplicity.max(multiplicity);
+            factor.distinct_degree_factors(&mut |product, degree| {
+                // every irreducible factor of degree `d` divides `x^(2^d - 1) - 1`
+                let mut order = u128::MAX >> (128 - degree);
+                for p in MERSENNE_PRIME_FACTORS[degree as usize] {
+                    while order % p == 0 && Self::X.pow_mod(order / p, product) == Self::ONE {
+                        order /= p;
+                    }
+                }
+                ret = lcm(ret, order);
+            });
+        });
+
+        // `x^k - 1` is square-free for odd `k`, repeated factors need extra powers of 2
+        ret << (32 - (max_multiplicity - 1).leading_zeros())
+    }
+}
+
+/// Ways to write a polynomial of degree `n` as an `n`-bit mask, leaving out one of its terms,
+/// as in tables of CRC polynomials
+///
+/// ```rust
+/// use lfsr_base::gf2::{Notation, Poly};
+///
+/// let polynomial: Poly = "x^8 + x^6 + x^5 + x^4 + 1".parse().unwrap();
+/// assert_eq!(polynomial, Poly::from_taps(&[8, 6, 5, 4]));
+/// assert_eq!(polynomial.to_notation(Notation::Koopman), Some(0xB8));
+/// assert_eq!(Poly::from_notation(Notation::Normal, 0x71, 8), Some(polynomial));
+/// assert_eq!(Poly::from_notation(Notation::Reversed, 0x8E, 8), Some(polynomial));
+/// assert_eq!(Poly::from_notation(Notation::Reciprocal, 0x1D, 8), Some(polynomial));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Notation {
+    /// Bit `i` is `x^(i + 1)`, `+ 1` is implicit
+    Koopman,
+    /// Bit `i` is `x^i`, `x^n` is implicit
+    Normal,
+    /// Normal with its `n` bits reversed
+    Reversed,
+    /// Normal of the reciprocal polynomial
+    Reciprocal,
+}
+
+/// Iterator over all primitive polynomials of a degree, see [`primitive_polynomials`]
+#[derive(Clone, Debug)]
+pub struct PrimitivePolynomials {
+    leading: Poly,
+    low: Option<u128>,
+    last_low: u128,
+}
+
+impl Iterator for PrimitivePolynomials {
+    type Item = Poly;
+
+    fn next(&mut self) -> Option<Poly> {
+        while let Some(low) = self.low {
+            self.low = if low == self.last_low {
+                None
+            } else {
+                Some(low + 2)
+            };
+            let candidate = self.leading ^ Poly::from(low);
+            if candidate.is_primitive() {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+/// All primitive polynomials of degree `degree`, ordered by their coefficients read
+/// as a binary number, `x^4 + x + 1` comes before `x^4 + x^3 + 1`
+///
+/// Degree has to be between 1 and 128. There are `φ(2^degree - 1) / degree` of them,
+/// each is tested with [`Poly::is_primitive`] as the iterator gets to it.
+///
+/// ```rust
+/// use lfsr_base::gf2::{primitive_polynomials, Poly};
+///
+/// assert_eq!(primitive_polynomials(6).count(), 6);
+///
+/// let first = primitive_polynomials(64).next().unwrap();
+/// assert_eq!(first.taps().collect::<Vec<_>>(), vec![64, 4, 3, 1]);
+/// assert_eq!(first, Poly::from_taps(&[64, 4, 3, 1]));
+/// ```
+pub fn primitive_polynomials(degree: u32) -> PrimitivePolynomials {
+    assert!(
+        (1..=128).contains(&degree),
+        "degree of primitive polynomials has to be between 1 and 128"
+    );
+    PrimitivePolynomials {
+        leading: Poly::monomial(degree),
+        low: Some(1),
+        last_low: u128::MAX >> (128 - degree),
+    }
+}
+
+impl From<u128> for Poly {
+    fn from(lo: u128) -> Self {
+        Poly { lo, hi: 0 }
+    }
+}
+
+impl Mul for Poly {
+    type Output = Poly;
+
+    fn mul(self, other: Self) -> Self {
+        let mut ret = Self::ZERO;
+        if let (Some(a), Some(b)) = (self.degree(), other.degree()) {
+            assert!(a + b < 256, "product of polynomials doesn't fit into 256 bits");
+            for power in 0..=b {
+                if other.coefficient(power) {
+                    ret = ret ^ self.shl(power);
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl Div for Poly {
+    type Output = Poly;
+
+    fn div(self, divisor: Self) -> Self {
+        self.div_rem(divisor).0
+    }
+}
+
+impl Rem for Poly {
+    type Output = Poly;
+
+    fn rem(self, divisor: Self) -> Self {
+        self.div_rem(divisor).1
+    }
+}
+
+impl BitXor for Poly {
+    type Output = Poly;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Poly {
+            lo: self.lo ^ rhs.lo,
+            hi: self.hi ^ rhs.hi,
+        }
+    }
+}
+
+impl fmt::Display for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let degree = match self.degree() {
+            Some(degree) => degree,
+            None => return write!(f, "0"),
+        };
+        let mut first = true;
+        for power in (0..=degree).rev().filter(|p| self.coefficient(*p)) {
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+            match power {
+                0 => write!(f, "1")?,
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", power)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a string isn't a polynomial like `x^8 + x^6 + x^5 + x^4 + 1`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParsePolyError {
+    /// A term isn't `1`, `x` or `x^power`
+    Term,
+    /// A power is above 255
+    Power(u32),
+    /// A power is given twice
+    DuplicateTerm(u32),
+}
+
+impl fmt::Display for ParsePolyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsePolyError::Term => write!(f, "expected `1`, `x` or `x^power`"),
+            ParsePolyError::Power(power) => write!(f, "x^{} doesn't fit into 256 bits", power),
+            ParsePolyError::DuplicateTerm(power) => write!(f, "x^{} is given twice", power),
+        }
+    }
+}
+
+/// Parses what `Display` prints, in any order of terms and with any spaces, `0` is the zero polynomial
+impl FromStr for Poly {
+    type Err = ParsePolyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "0" {
+            return Ok(Self::ZERO);
+        }
+        let mut ret = Self::ZERO;
+        for term in s.split('+') {
+            let term = term.trim();
+            let power = match term {
+                "1" => 0,
+                "x" => 1,
+                _ => term
+                    .strip_prefix('x')
+                    .and_then(|power| power.trim_start().strip_prefix('^'))
+                    .and_then(|power| power.trim().parse::<u32>().ok())
+                    .ok_or(ParsePolyError::Term)?,
+            };
+            if power > 255 {
+                return Err(ParsePolyError::Power(power));
+            }
+            if ret.coefficient(power) {
+                return Err(ParsePolyError::DuplicateTerm(power));
+            }
+            ret = ret ^ Self::monomial(power);
+        }
+        Ok(ret)
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    a / gcd(a, b) * b
+}
+
+/// `x * a mod p`, where `p = x^degree + low` and `a` has degree below `degree`
+const fn mul_x_mod(a: u128, degree: u32, low: u128) -> u128 {
+    let mask = u128::MAX >> (128 - degree);
+    let overflow = (a >> (degree - 1)) & 1;
+    ((a << 1) & mask) ^ (low * overflow)
+}
+
+/// `a * b mod p`, where `p = x^degree + low` and both `a` and `b` have degree below `degree`
+pub const fn mul_mod(a: u128, b: u128, degree: u32, low: u128) -> u128 {
+    let mut ret = 0;
+    let mut power = degree;
+    while power > 0 {
+        power -= 1;
+        ret = mul_x_mod(ret, degree, low);
+        if (b >> power) & 1 == 1 {
+            ret ^= a;
+        }
+    }
+    ret
+}
+
+/// `a^n mod p`, where `p = x^degree + low` and `a` has degree below `degree`
+pub const fn pow_mod(a: u128, n: u128, degree: u32, low: u128) -> u128 {
+    let mut ret = 1;
+    let mut bit = 128 - n.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        ret = mul_mod(ret, ret, degree, low);
+        if (n >> bit) & 1 == 1 {
+            ret = mul_mod(ret, a, degree, low);
+        }
+    }
+    ret
+}
+
+/// `x^n mod p`, where `p = x^degree + low`
+pub const fn x_pow_mod(n: u128, degree: u32, low: u128) -> u128 {
+    let mut ret = 1;
+    let mut bit = 128 - n.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        ret = mul_mod(ret, ret, degree, low);
+        if (n >> bit) & 1 == 1 {
+            ret = mul_x_mod(ret, degree, low);
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    fn poly(powers: &[u32]) -> Poly {
+        powers
+            .iter()
+            .fold(Poly::ZERO, |p, power| p ^ Poly::monomial(*power))
+    }
+
+    #[test]
+    fn division() {
+        let a = poly(&[7, 4, 3, 0]);
+        let b = poly(&[3, 1, 0]);
+        let (q, r) = a.div_rem(b);
+        assert_eq!((q * b) ^ r, a);
+        assert!(r.degree().unwrap_or(0) < 3);
+        assert_eq!(poly(&[2, 0]).gcd(poly(&[3, 0])), poly(&[1, 0]));
+    }
+
+    #[test]
+    fn enumerating_primitive() {
+        // φ(2^n - 1) / n
+        let counts = [(1, 1), (2, 1), (3, 2), (4, 2), (5, 6), (8, 16), (10, 60), (12, 144)];
+        for (degree, count) in counts.iter() {
+            assert_eq!(primitive_polynomials(*degree).count(), *count, "degree {}", degree);
+        }
+
+        let four: std::vec::Vec<_> = primitive_polynomials(4).collect();
+        assert_eq!(four, [poly(&[4, 1, 0]), poly(&[4, 3, 0])]);
+        assert_eq!(poly(&[4, 3, 0]).taps().collect::<std::vec::Vec<_>>(), [4, 3]);
+        assert_eq!(primitive_polynomials(128).next(), Some(poly(&[128, 7, 2, 1, 0])));
+    }
+
+    #[test]
+    fn order_of_primitive() {
+        assert_eq!(poly(&[4, 1, 0]).order(), 15);
+        assert_eq!(poly(&[16, 14, 13, 11, 0]).order(), 65535);
+        assert_eq!(poly(&[64, 63, 61, 60, 0]).order(), u64::MAX as u128);
+        assert_eq!(poly(&[128, 126, 101, 99, 0]).order(), u128::MAX);
+    }
+
+    #[test]
+    fn order_of_non_primitive() {
+        // irreducible, but x is a 5th root of unity
+        assert_eq!(poly(&[4, 3, 2, 1, 0]).order(), 5);
+        // (x^2 + x + 1)^2
+        assert_eq!(poly(&[4, 2, 0]).order(), 6);
+        // (x + 1)^4
+        assert_eq!(poly(&[4, 0]).order(), 4);
+        // (x + 1)(x^3 + x + 1)
+        assert_eq!(poly(&[4, 3, 2, 0]).order(), 7);
+        // (x^2 + x + 1)(x^3 + x + 1)
+        assert_eq!(poly(&[5, 4, 0]).order(), 21);
+    }
+
+    #[test]
+    fn irreducible_and_primitive() {
+        // x^4 + x^3 + x^2 + x + 1 is irreducible but has order 5
+        assert!(poly(&[4, 3, 2, 1, 0]).is_irreducible());
+        assert!(!poly(&[4, 3, 2, 1, 0]).is_primitive());
+        assert!(!poly(&[4, 2, 0]).is_irreducible());
+        assert!(!poly(&[4, 2, 0]).is_primitive());
+        assert!(poly(&[1]).is_irreducible());
+        assert!(!poly(&[1]).is_primitive());
+        assert!(poly(&[1, 0]).is_primitive());
+        assert!(!Poly::ONE.is_irreducible());
+        assert!(!Poly::ZERO.is_irreducible());
+        assert!(Poly::from_taps(&[32, 30, 26, 25]).is_primitive());
+        assert!(Poly::from_taps(&[128, 126, 101, 99]).is_primitive());
+        // x^255 + x^5 + x^3 + x^2 + 1, beyond what `order` can handle
+        assert!(poly(&[255, 5, 3, 2, 0]).is_irreducible());
+        assert!(!poly(&[255, 5, 3, 2, 1]).is_irreducible());
+        // (x^2 + x + 1)(x^3 + x + 1)
+        assert!(!poly(&[5, 4, 0]).is_irreducible());
+    }
+
+    #[test]
+    fn notations_and_parsing() {
+        let notations = [
+            Notation::Koopman,
+            Notation::Normal,
+            Notation::Reversed,
+            Notation::Reciprocal,
+        ];
+        for taps in [&[8, 6, 5, 4][..], &[16, 14, 13, 11], &[128, 126, 101, 99], &[2, 1]].iter() {
+            let polynomial = Poly::from_taps(taps);
+            let degree = taps[0];
+            for notation in notations.iter() {
+                let mask = polynomial.to_notation(*notation).unwrap();
+                assert_eq!(Poly::from_notation(*notation, mask, degree), Some(polynomial));
+            }
+            assert_eq!(polynomial.to_string().parse(), Ok(polynomial));
+        }
+        // CRC-8: normal 0x07, reversed 0xE0, reciprocal 0xC1, Koopman 0x83
+        let crc8 = poly(&[8, 2, 1, 0]);
+        assert_eq!(crc8.to_notation(Notation::Normal), Some(0x07));
+        assert_eq!(crc8.to_notation(Notation::Reversed), Some(0xE0));
+        assert_eq!(crc8.to_notation(Notation::Reciprocal), Some(0xC1));
+        assert_eq!(crc8.to_notation(Notation::Koopman), Some(0x83));
+
+        assert_eq!(Poly::from_notation(Notation::Koopman, 0x1B8, 8), None);
+        assert_eq!(Poly::from_notation(Notation::Koopman, 1, 0), None);
+        assert_eq!(Poly::ONE.to_notation(Notation::Koopman), None);
+
+        assert_eq!("x^3+x+1".parse(), Ok(poly(&[3, 1, 0])));
+        assert_eq!(" 1 + x^ 200 ".parse(), Ok(poly(&[200, 0])));
+        assert_eq!("x ^ 8 + x ^ 4 + 1".parse(), Ok(poly(&[8, 4, 0])));
+        assert_eq!("0".parse(), Ok(Poly::ZERO));
+        assert_eq!("x^2 + y".parse::<Poly>(), Err(ParsePolyError::Term));
+        assert_eq!("x^256".parse::<Poly>(), Err(ParsePolyError::Power(256)));
+        assert_eq!("x + x".parse::<Poly>(), Err(ParsePolyError::DuplicateTerm(1)));
+        assert_eq!("".parse::<Poly>(), Err(ParsePolyError::Term));
+    }
+
+    #[test]
+    fn reciprocal_and_derivative() {
+        assert_eq!(poly(&[4, 1, 0]).reciprocal(), poly(&[4, 3, 0]));
+        assert_eq!(poly(&[4, 1]).reciprocal(), poly(&[3, 0]));
+        assert_eq!(Poly::ZERO.reciprocal(), Poly::ZERO);
+        assert_eq!(poly(&[5, 4, 3, 1, 0]).derivative(), poly(&[4, 2, 0]));
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let modulus = poly(&[200, 3, 0]);
+        let a = poly(&[199, 100, 1]);
+        let b = poly(&[150, 7, 0]);
+        // (a * b) doesn't fit, but `mul_mod` agrees with a product reduced on the way
+        assert_eq!(
+            a.mul_mod(b, modulus),
+            a.mul_mod(poly(&[150]), modulus) ^ a.mul_mod(poly(&[7, 0]), modulus)
+        );
+        let small = poly(&[4, 1, 0]);
+        assert_eq!(
+            poly(&[3, 0]).mul_mod(poly(&[2, 1]), small),
+            poly(&[5, 4, 2, 1]) % small
+        );
+        assert_eq!(Poly::X.pow_mod(15, poly(&[4, 1, 0])), Poly::ONE);
+        assert_eq!(Poly::X.pow_mod(0, Poly::ONE), Poly::ZERO);
+        assert_eq!(Poly::from_taps(&[4, 1]).to_u128(), Some(0b10011));
+        assert_eq!(Poly::monomial(128).to_u128(), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(poly(&[16, 14, 1, 0]).to_string(), "x^16 + x^14 + x + 1");
+        assert_eq!(Poly::ZERO.to_string(), "0");
+    }
+
+    #[test]
+    fn powers_of_x() {
+        // x^4 + x + 1 is primitive, x^15 = 1 and x^4 = x + 1
+        assert_eq!(x_pow_mod(15, 4, 0b0011), 1);
+        assert_eq!(x_pow_mod(4, 4, 0b0011), 0b0011);
+        assert_eq!(x_pow_mod(0, 4, 0b0011), 1);
+        assert_eq!(x_pow_mod(1 << 100, 4, 0b0011), x_pow_mod((1 << 100) % 15, 4, 0b0011));
+        // x^128 + x^7 + x^2 + x + 1
+        assert_eq!(x_pow_mod(128, 128, 0x87), 0x87);
+        assert_eq!(
+            x_pow_mod(u128::MAX, 128, 0x87),
+            mul_mod(x_pow_mod(u128::MAX - 1, 128, 0x87), 2, 128, 0x87)
+        );
+    }
+
+    #[test]
+    fn multiplication() {
+        // (x + 1)(x^2 + 1) = x^3 + x^2 + x + 1 = x^2 mod x^3 + x + 1
+        assert_eq!(mul_mod(0b011, 0b101, 3, 0b011), 0b100);
+        assert_eq!(mul_mod(0b101, 0b011, 3, 0b011), 0b100);
+        assert_eq!(pow_mod(0b011, 2, 3, 0b011), 0b101);
+        assert_eq!(pow_mod(0b010, 7, 3, 0b011), 1);
+    }
+}
@@ -0,0 +1,150 @@
+//! Iterators over states and output bits of an LFSR
+//!
+//! Forward iterators yield the current state (or its output bit) and then count up,
+//! reverse ones count down and then yield, so reversing the first `n` items of a forward
+//! iterator gives the same items as a reverse iterator started `n` steps later.
+
+use crate::{LFSRState, LFSR};
+
+/// Which bit of the state is the output of an LFSR
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputBit {
+    /// Bit `0`, the one that is shifted out of both Galois and Fibonacci LFSRs generated here
+    Lsb,
+    /// Bit `width - 1`
+    Msb,
+}
+
+/// Where the first of the packed bits goes in a word
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BitOrder {
+    /// The first bit is the least significant one
+    LsbFirst,
+    /// The first bit is the most significant one
+    MsbFirst,
+}
+
+impl OutputBit {
+    /// This bit of the current state of `lfsr`
+    pub fn of<L: LFSR + ?Sized>(self, lfsr: &L) -> bool {
+        let shift = match self {
+            OutputBit::Lsb => 0,
+            OutputBit::Msb => lfsr.width() - 1,
+        };
+        (lfsr.get_state() >> shift) & L::State::ONE == L::State::ONE
+    }
+}
+
+/// States of an LFSR, counting up, see [`LFSR::states`]
+#[derive(Clone, Debug)]
+pub struct States<L> {
+    lfsr: L,
+}
+
+impl<L: LFSR> Iterator for States<L> {
+    type Item = L::State;
+
+    fn next(&mut self) -> Option<L::State> {
+        let state = self.lfsr.get_state();
+        self.lfsr.inc();
+        Some(state)
+    }
+}
+
+/// States of an LFSR, counting down, see [`LFSR::states_rev`]
+#[derive(Clone, Debug)]
+pub struct StatesRev<L> {
+    lfsr: L,
+}
+
+impl<L: LFSR> Iterator for StatesRev<L> {
+    type Item = L::State;
+
+    fn next(&mut self) -> Option<L::State> {
+        self.lfsr.dec();
+        Some(self.lfsr.get_state())
+    }
+}
+
+/// Output bits of an LFSR, counting up, see [`LFSR::bits`]
+#[derive(Clone, Debug)]
+pub struct Bits<L> {
+    lfsr: L,
+    output: OutputBit,
+}
+
+impl<L: LFSR> Iterator for Bits<L> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.output.of(&self.lfsr);
+        self.lfsr.inc();
+        Some(bit)
+    }
+}
+
+/// Output bits of an LFSR, counting down, see [`LFSR::bits_rev`]
+#[derive(Clone, Debug)]
+pub struct BitsRev<L> {
+    lfsr: L,
+    output: OutputBit,
+}
+
+impl<L: LFSR> Iterator for BitsRev<L> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        self.lfsr.dec();
+        Some(self.output.of(&self.lfsr))
+    }
+}
+
+/// Bits packed into words of type `W`, see [`LFSR::packed`]
+#[derive(Clone, Debug)]
+pub struct Packed<I, W> {
+    bits: I,
+    order: BitOrder,
+    word: core::marker::PhantomData<W>,
+}
+
+impl<I: Iterator<Item = bool>, W: LFSRState> Iterator for Packed<I, W> {
+    type Item = W;
+
+    fn next(&mut self) -> Option<W> {
+        let mut word = W::ZERO;
+        for i in 0..W::BITS {
+            if self.bits.next()? {
+                word = word
+                    | match self.order {
+                        BitOrder::LsbFirst => W::ONE << i,
+                        BitOrder::MsbFirst => W::ONE << (W::BITS - 1 - i),
+                    };
+            }
+        }
+        Some(word)
+    }
+}
+
+pub(crate) fn states<L>(lfsr: L) -> States<L> {
+    States { lfsr }
+}
+
+pub(crate) fn states_rev<L>(lfsr: L) -> StatesRev<L> {
+    StatesRev { lfsr }
+}
+
+pub(crate) fn bits<L>(lfsr: L, output: OutputBit) -> Bits<L> {
+    Bits { lfsr, output }
+}
+
+pub(crate) fn bits_rev<L>(lfsr: L, output: OutputBit) -> BitsRev<L> {
+    BitsRev { lfsr, output }
+}
+
+pub(crate) fn packed<I, W>(bits: I, order: BitOrder) -> Packed<I, W> {
+    Packed {
+        bits,
+        order,
+        word: core::marker::PhantomData,
+    }
+}
@@ -1,19 +1,223 @@
 #![no_std]
 
+use core::fmt::{Binary, Debug, Display, LowerHex, UpperHex};
+use core::hash::Hash;
+use core::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Not, Rem, Shl, Shr, Sub};
+
+pub mod arguments;
+pub mod berlekamp_massey;
+pub mod convert;
+pub mod crc;
+pub mod discrete_log;
+pub mod gf2;
+pub mod iter;
+pub mod mersenne;
+#[cfg(feature = "rand_core")]
+pub mod rng;
+pub mod runtime;
+pub mod signature;
+
+#[cfg(feature = "rand_core")]
+pub use rand_core;
+
+pub use crate::iter::{BitOrder, OutputBit};
+pub use crate::runtime::{FibonacciLfsr, GaloisLfsr, TapsError};
+use crate::iter::{Bits, BitsRev, Packed, States, StatesRev};
+
+/// An unsigned integer type that can hold the state of an LFSR
+pub trait LFSRState:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Default
+    + Debug
+    + Display
+    + Binary
+    + LowerHex
+    + UpperHex
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    /** Number of bits in this type */
+    const BITS: u32;
+    /** `0` */
+    const ZERO: Self;
+    /** `1` */
+    const ONE: Self;
+
+    /** Truncates a `u128` to this type */
+    fn from_u128(value: u128) -> Self;
+    /** Widens this value to `u128` */
+    fn to_u128(self) -> u128;
+}
+
+macro_rules! lfsr_state {
+    ($($ty:ty),*) => {
+        $(
+            impl LFSRState for $ty {
+                const BITS: u32 = <$ty>::BITS;
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn from_u128(value: u128) -> Self {
+                    value as $ty
+                }
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+            }
+        )*
+    };
+}
+
+lfsr_state!(u8, u16, u32, u64, u128);
+
 /// An object-safe part of the LFSR trait that allows to count up, down and get a current state
 pub trait LFSR {
+    /** Type of the state, wide enough to hold every state and the sequence length */
+    type State: LFSRState;
+
     /** Retrieves the current state of the LFSR */
-    fn get_state(&self) -> u32;
+    fn get_state(&self) -> Self::State;
+    /** Replaces the current state, bits beyond `width` are dropped */
+    fn set_state(&mut self, state: Self::State);
     /** Count up */
     fn inc(&mut self);
     /** Count down */
     fn dec(&mut self);
     /** Sequence length of this LFSR */
-    fn sequence_length(&self) -> u32;
+    fn sequence_length(&self) -> Self::State;
+    /** Number of bits in the state */
+    fn width(&self) -> u32;
+
+    /** Count up `n` times, generated LFSRs do it in a logarithmic number of steps */
+    fn advance(&mut self, n: Self::State) {
+        let mut i = Self::State::ZERO;
+        while i != n {
+            self.inc();
+            i = i + Self::State::ONE;
+        }
+    }
+
+    /** Count down `n` times, generated LFSRs do it in a logarithmic number of steps */
+    fn rewind(&mut self, n: Self::State) {
+        let mut i = Self::State::ZERO;
+        while i != n {
+            self.dec();
+            i = i + Self::State::ONE;
+        }
+    }
+
+    /** Iterator over the current state and the ones after it, pass `&mut lfsr` to keep the LFSR */
+    fn states(self) -> States<Self>
+    where
+        Self: Sized,
+    {
+        iter::states(self)
+    }
+
+    /** Iterator over the states before the current one, latest first */
+    fn states_rev(self) -> StatesRev<Self>
+    where
+        Self: Sized,
+    {
+        iter::states_rev(self)
+    }
+
+    /** Iterator over the output bits of the current state and the ones after it */
+    fn bits(self, output: OutputBit) -> Bits<Self>
+    where
+        Self: Sized,
+    {
+        iter::bits(self, output)
+    }
+
+    /** Iterator over the output bits of the states before the current one, latest first */
+    fn bits_rev(self, output: OutputBit) -> BitsRev<Self>
+    where
+        Self: Sized,
+    {
+        iter::bits_rev(self, output)
+    }
+
+    /** Same bits as `bits`, packed into bytes or wider words, `W::BITS` of them in each */
+    fn packed<W: LFSRState>(self, output: OutputBit, order: BitOrder) -> Packed<Bits<Self>, W>
+    where
+        Self: Sized,
+    {
+        iter::packed(self.bits(output), order)
+    }
+
+    /** Same bits as `bits_rev`, packed into bytes or wider words, `W::BITS` of them in each */
+    fn packed_rev<W: LFSRState>(
+        self,
+        output: OutputBit,
+        order: BitOrder,
+    ) -> Packed<BitsRev<Self>, W>
+    where
+        Self: Sized,
+    {
+        iter::packed(self.bits_rev(output), order)
+    }
+}
+
+impl<L: LFSR + ?Sized> LFSR for &mut L {
+    type State = L::State;
+
+    fn get_state(&self) -> L::State {
+        (**self).get_state()
+    }
+
+    fn set_state(&mut self, state: L::State) {
+        (**self).set_state(state)
+    }
+
+    fn inc(&mut self) {
+        (**self).inc()
+    }
+
+    fn dec(&mut self) {
+        (**self).dec()
+    }
+
+    fn sequence_length(&self) -> L::State {
+        (**self).sequence_length()
+    }
+
+    fn width(&self) -> u32 {
+        (**self).width()
+    }
+
+    fn advance(&mut self, n: L::State) {
+        (**self).advance(n)
+    }
+
+    fn rewind(&mut self, n: L::State) {
+        (**self).rewind(n)
+    }
 }
 
+/// A Fibonacci LFSR with XOR feedback, as generated by `fibonacci_lfsr!`
+///
+/// Its state is the next `width` output bits, the first one in the least significant bit,
+/// and only the all-zero state locks up, so received bits can seed it.
+pub trait FibonacciXorLFSR: LFSR {}
+
+impl<L: FibonacciXorLFSR + ?Sized> FibonacciXorLFSR for &mut L {}
+
 /// A non-object-safe part of an LFSR
-pub trait LFSRStatic {
+pub trait LFSRStatic: LFSR {
     /** Sequence length of this LFSR */
-    fn sequence_length() -> u32;
+    fn sequence_length() -> Self::State;
 }
@@ -0,0 +1,135 @@
+//! Prime factors of Mersenne numbers, needed to find orders of polynomials over GF(2)
+//! and discrete logarithms in GF(2^n)
+
+/// Distinct prime factors of `2^d - 1`, indexed by `d`
+pub const MERSENNE_PRIME_FACTORS: [&[u128]; 129] = [
+    &[],
+    &[],
+    &[3],
+    &[7],
+    &[3, 5],
+    &[31],
+    &[3, 7],
+    &[127],
+    &[3, 5, 17],
+    &[7, 73],
+    &[3, 11, 31],
+    &[23, 89],
+    &[3, 5, 7, 13],
+    &[8191],
+    &[3, 43, 127],
+    &[7, 31, 151],
+    &[3, 5, 17, 257],
+    &[131071],
+    &[3, 7, 19, 73],
+    &[524287],
+    &[3, 5, 11, 31, 41],
+    &[7, 127, 337],
+    &[3, 23, 89, 683],
+    &[47, 178481],
+    &[3, 5, 7, 13, 17, 241],
+    &[31, 601, 1801],
+    &[3, 2731, 8191],
+    &[7, 73, 262657],
+    &[3, 5, 29, 43, 113, 127],
+    &[233, 1103, 2089],
+    &[3, 7, 11, 31, 151, 331],
+    &[2147483647],
+    &[3, 5, 17, 257, 65537],
+    &[7, 23, 89, 599479],
+    &[3, 43691, 131071],
+    &[31, 71, 127, 122921],
+    &[3, 5, 7, 13, 19, 37, 73, 109],
+    &[223, 616318177],
+    &[3, 174763, 524287],
+    &[7, 79, 8191, 121369],
+    &[3, 5, 11, 17, 31, 41, 61681],
+    &[13367, 164511353],
+    &[3, 7, 43, 127, 337, 5419],
+    &[431, 9719, 2099863],
+    &[3, 5, 23, 89, 397, 683, 2113],
+    &[7, 31, 73, 151, 631, 23311],
+    &[3, 47, 178481, 2796203],
+    &[2351, 4513, 13264529],
+    &[3, 5, 7, 13, 17, 97, 241, 257, 673],
+    &[127, 4432676798593],
+    &[3, 11, 31, 251, 601, 1801, 4051],
+    &[7, 103, 2143, 11119, 131071],
+    &[3, 5, 53, 157, 1613, 2731, 8191],
+    &[6361, 69431, 20394401],
+    &[3, 7, 19, 73, 87211, 262657],
+    &[23, 31, 89, 881, 3191, 201961],
+    &[3, 5, 17, 29, 43, 113, 127, 15790321],
+    &[7, 32377, 524287, 1212847],
+    &[3, 59, 233, 1103, 2089, 3033169],
+    &[179951, 3203431780337],
+    &[3, 5, 7, 11, 13, 31, 41, 61, 151, 331, 1321],
+    &[2305843009213693951],
+    &[3, 715827883, 2147483647],
+    &[7, 73, 127, 337, 92737, 649657],
+    &[3, 5, 17, 257, 641, 65537, 6700417],
+    &[31, 8191, 145295143558111],
+    &[3, 7, 23, 67, 89, 683, 20857, 599479],
+    &[193707721, 761838257287],
+    &[3, 5, 137, 953, 26317, 43691, 131071],
+    &[7, 47, 178481, 10052678938039],
+    &[3, 11, 31, 43, 71, 127, 281, 86171, 122921],
+    &[228479, 48544121, 212885833],
+    &[3, 5, 7, 13, 17, 19, 37, 73, 109, 241, 433, 38737],
+    &[439, 2298041, 9361973132609],
+    &[3, 223, 1777, 25781083, 616318177],
+    &[7, 31, 151, 601, 1801, 100801, 10567201],
+    &[3, 5, 229, 457, 174763, 524287, 525313],
+    &[23, 89, 127, 581283643249112959],
+    &[3, 7, 79, 2731, 8191, 121369, 22366891],
+    &[2687, 202029703, 1113491139767],
+    &[3, 5, 11, 17, 31, 41, 257, 61681, 4278255361],
+    &[7, 73, 2593, 71119, 262657, 97685839],
+    &[3, 83, 13367, 164511353, 8831418697],
+    &[167, 57912614113275649087721],
+    &[3, 5, 7, 13, 29, 43, 113, 127, 337, 1429, 5419, 14449],
+    &[31, 131071, 9520972806333758431],
+    &[3, 431, 9719, 2099863, 2932031007403],
+    &[7, 233, 1103, 2089, 4177, 9857737155463],
+    &[3, 5, 17, 23, 89, 353, 397, 683, 2113, 2931542417],
+    &[618970019642690137449562111],
+    &[3, 7, 11, 19, 31, 73, 151, 331, 631, 23311, 18837001],
+    &[127, 911, 8191, 112901153, 23140471537],
+    &[3, 5, 47, 277, 1013, 1657, 30269, 178481, 2796203],
+    &[7, 2147483647, 658812288653553079],
+    &[3, 283, 2351, 4513, 13264529, 165768537521],
+    &[31, 191, 524287, 420778751, 30327152671],
+    &[3, 5, 7, 13, 17, 97, 193, 241, 257, 673, 65537, 22253377],
+    &[11447, 13842607235828485645766393],
+    &[3, 43, 127, 4363953127297, 4432676798593],
+    &[7, 23, 73, 89, 199, 153649, 599479, 33057806959],
+    &[3, 5, 11, 31, 41, 101, 251, 601, 1801, 4051, 8101, 268501],
+    &[7432339208719, 341117531003194129],
+    &[3, 7, 103, 307, 2143, 2857, 6529, 11119, 43691, 131071],
+    &[2550183799, 3976656429941438590393],
+    &[3, 5, 17, 53, 157, 1613, 2731, 8191, 858001, 308761441],
+    &[7, 31, 71, 127, 151, 337, 29191, 106681, 122921, 152041],
+    &[3, 107, 6361, 69431, 20394401, 28059810762433],
+    &[162259276829213363391578010288127],
+    &[3, 5, 7, 13, 19, 37, 73, 109, 87211, 246241, 262657, 279073],
+    &[745988807, 870035986098720987332873],
+    &[3, 11, 23, 31, 89, 683, 881, 2971, 3191, 201961, 48912491],
+    &[7, 223, 321679, 26295457, 319020217, 616318177],
+    &[3, 5, 17, 29, 43, 113, 127, 257, 5153, 15790321, 54410972897],
+    &[3391, 23279, 65993, 1868569, 1066818132868207],
+    &[3, 7, 571, 32377, 174763, 524287, 1212847, 160465489],
+    &[31, 47, 14951, 178481, 4036961, 2646507710984041],
+    &[3, 5, 59, 233, 1103, 2089, 3033169, 107367629, 536903681],
+    &[7, 73, 79, 937, 6553, 8191, 86113, 121369, 7830118297],
+    &[3, 2833, 37171, 179951, 1824726041, 3203431780337],
+    &[127, 239, 20231, 131071, 62983048367, 131105292137],
+    &[3, 5, 7, 11, 13, 17, 31, 41, 61, 151, 241, 331, 1321, 61681, 4562284561],
+    &[23, 89, 727, 1786393878363164227858270210279],
+    &[3, 768614336404564651, 2305843009213693951],
+    &[7, 13367, 3887047, 164511353, 177722253954175633],
+    &[3, 5, 5581, 8681, 49477, 384773, 715827883, 2147483647],
+    &[31, 601, 1801, 269089806001, 4710883168879506001],
+    &[3, 7, 19, 43, 73, 127, 337, 5419, 92737, 649657, 77158673929],
+    &[170141183460469231731687303715884105727],
+    &[3, 5, 17, 257, 641, 65537, 274177, 6700417, 67280421310721],
+];
@@ -0,0 +1,37 @@
+//! How generated LFSRs produce random numbers for [`rand_core::RngCore`], with the `rand_core` feature
+//!
+//! Every random bit is the least significant bit of the current state, after which the LFSR counts up
+//! once, so the random bits are the output sequence of the LFSR, the same as
+//! [`LFSR::bits`]`(OutputBit::Lsb)`. They are packed least significant bit first: `fill_bytes` writes
+//! the same bytes as [`LFSR::packed`]`::<u8>(OutputBit::Lsb, BitOrder::LsbFirst)` and `next_u32`
+//! and `next_u64` are those bytes in little endian order.
+//!
+//! `SeedableRng::from_seed` reads the initial state from the seed in little endian order, drops bits
+//! that don't fit into the width and replaces the lock-up state with the default one.
+//!
+//! `width` consecutive output bits give away the state, so this is no cryptographically secure generator.
+
+use crate::{BitOrder, LFSRState, OutputBit, LFSR};
+
+fn next_word<L: LFSR, W: LFSRState>(lfsr: &mut L) -> W {
+    lfsr.packed(OutputBit::Lsb, BitOrder::LsbFirst)
+        .next()
+        .unwrap_or(W::ZERO)
+}
+
+/// `RngCore::next_u32`
+pub fn next_u32<L: LFSR>(lfsr: &mut L) -> u32 {
+    next_word(lfsr)
+}
+
+/// `RngCore::next_u64`
+pub fn next_u64<L: LFSR>(lfsr: &mut L) -> u64 {
+    next_word(lfsr)
+}
+
+/// `RngCore::fill_bytes`
+pub fn fill_bytes<L: LFSR>(lfsr: &mut L, dst: &mut [u8]) {
+    for byte in dst {
+        *byte = next_word(lfsr);
+    }
+}
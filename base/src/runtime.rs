@@ -0,0 +1,259 @@
+//! LFSRs configured at run time, stepping the same way as the generated ones
+
+use core::fmt;
+
+use crate::gf2::{x_pow_mod, Poly};
+use crate::{FibonacciXorLFSR, LFSR};
+
+/// Why a width and taps don't describe an LFSR
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TapsError {
+    /// Width isn't in `1..=128`
+    Width(u32),
+    /// A tap isn't in `1..=width`
+    Tap(u32),
+    /// A tap is given twice
+    DuplicateTap(u32),
+    /// None of the taps is `width`
+    NoTapAtWidth,
+}
+
+impl fmt::Display for TapsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TapsError::Width(width) => write!(f, "width {} is not in 1..=128", width),
+            TapsError::Tap(tap) => write!(f, "tap {} is not in 1..=width", tap),
+            TapsError::DuplicateTap(tap) => write!(f, "tap {} is given twice", tap),
+            TapsError::NoTapAtWidth => write!(f, "one of the taps has to be the width"),
+        }
+    }
+}
+
+/// Mask of 1-based `taps` with bit `tap - 1` for every tap, checked as in the macros
+fn taps_mask(width: u32, taps: &[u32]) -> Result<u128, TapsError> {
+    if !(1..=128).contains(&width) {
+        return Err(TapsError::Width(width));
+    }
+    let mut mask = 0u128;
+    for tap in taps.iter().copied() {
+        if !(1..=width).contains(&tap) {
+            return Err(TapsError::Tap(tap));
+        }
+        if mask & (1 << (tap - 1)) != 0 {
+            return Err(TapsError::DuplicateTap(tap));
+        }
+        mask |= 1 << (tap - 1);
+    }
+    if mask >> (width - 1) != 1 {
+        return Err(TapsError::NoTapAtWidth);
+    }
+    Ok(mask)
+}
+
+fn state_mask(width: u32) -> u128 {
+    u128::MAX >> (128 - width)
+}
+
+/// Feedback polynomial `1 + x^tap + ...`
+fn feedback_polynomial(mask: u128) -> Poly {
+    (Poly::from(mask) * Poly::X) ^ Poly::ONE
+}
+
+/// `n` steps with a linear `step` at once: it's annihilated by `x^width + low`,
+/// so `n` steps are `x^n` modulo that polynomial evaluated at `step`
+///
+/// `up` of both Galois and Fibonacci LFSRs is annihilated by the reciprocal of the feedback polynomial,
+/// `down` by the feedback polynomial itself.
+fn jump(state: u128, n: u128, width: u32, low: u128, step: impl Fn(u128) -> u128) -> u128 {
+    let powers = x_pow_mod(n, width, low);
+    let mut ret = 0;
+    let mut power = width;
+    while power > 0 {
+        power -= 1;
+        ret = step(ret);
+        if (powers >> power) & 1 == 1 {
+            ret ^= state;
+        }
+    }
+    ret
+}
+
+/// `1 + sum of x^(width - tap)`, reciprocal of the feedback polynomial without `x^width`
+fn reciprocal_low(width: u32, mask: u128) -> u128 {
+    mask.reverse_bits() >> (128 - width)
+}
+
+/// The feedback polynomial without `x^width`
+fn low(width: u32, mask: u128) -> u128 {
+    ((mask << 1) | 1) & state_mask(width)
+}
+
+/// A [Galois LFSR](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Galois_LFSRs)
+/// with XOR feedback configured at run time, it steps the same as one generated by `galois_lfsr!`
+/// with the same width and taps
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GaloisLfsr {
+    /// Number of bits in the state, `1..=128`
+    pub width: u32,
+    /// Bit `tap - 1` for every 1-based tap, bit `width - 1` has to be set
+    pub mask: u128,
+    pub state: u128,
+}
+
+impl GaloisLfsr {
+    /// Taps are 1-based as in `galois_lfsr!`, bits of `state` beyond `width` are dropped
+    pub fn new(width: u32, taps: &[u32], state: u128) -> Result<Self, TapsError> {
+        let mask = taps_mask(width, taps)?;
+        Ok(Self {
+            width,
+            mask,
+            state: state & state_mask(width),
+        })
+    }
+
+    pub fn up(&self, prev_state: u128) -> u128 {
+        let lsb = prev_state & 1;
+        (prev_state >> 1) ^ (lsb.wrapping_neg() & self.mask)
+    }
+
+    pub fn down(&self, prev_state: u128) -> u128 {
+        let msb = (prev_state >> (self.width - 1)) & 1;
+        let shifted = (prev_state << 1) & state_mask(self.width);
+        shifted ^ (msb.wrapping_neg() & low(self.width, self.mask))
+    }
+}
+
+impl LFSR for GaloisLfsr {
+    type State = u128;
+
+    fn get_state(&self) -> u128 {
+        self.state
+    }
+
+    fn set_state(&mut self, state: u128) {
+        self.state = state & state_mask(self.width);
+    }
+
+    fn inc(&mut self) {
+        self.state = self.up(self.state);
+    }
+
+    fn dec(&mut self) {
+        self.state = self.down(self.state);
+    }
+
+    /// Period of the current state, `0` if the taps don't include `width` and the state never comes back
+    fn sequence_length(&self) -> u128 {
+        if self.mask >> (self.width - 1) & 1 == 0 {
+            return 0;
+        }
+        // the state is a polynomial modulo the feedback one, `up` divides it by `x`
+        let polynomial = feedback_polynomial(self.mask);
+        (polynomial / polynomial.gcd(Poly::from(self.state))).order()
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn advance(&mut self, n: u128) {
+        let low = reciprocal_low(self.width, self.mask);
+        self.state = jump(self.state, n, self.width, low, |state| self.up(state));
+    }
+
+    fn rewind(&mut self, n: u128) {
+        let low = low(self.width, self.mask);
+        self.state = jump(self.state, n, self.width, low, |state| self.down(state));
+    }
+}
+
+/// A [Fibonacci LFSR](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Fibonacci_LFSRs)
+/// with XOR feedback configured at run time, it steps the same as one generated by `fibonacci_lfsr!`
+/// with the same width and taps
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FibonacciLfsr {
+    /// Number of bits in the state, `1..=128`
+    pub width: u32,
+    /// Bit `tap - 1` for every 1-based tap, bit `width - 1` has to be set
+    pub mask: u128,
+    pub state: u128,
+}
+
+impl FibonacciLfsr {
+    /// Taps are 1-based as in `fibonacci_lfsr!`, bits of `state` beyond `width` are dropped
+    pub fn new(width: u32, taps: &[u32], state: u128) -> Result<Self, TapsError> {
+        let mask = taps_mask(width, taps)?;
+        Ok(Self {
+            width,
+            mask,
+            state: state & state_mask(width),
+        })
+    }
+
+    /// Tap `t` is bit `width - t` of the state
+    fn feedback_mask(&self) -> u128 {
+        reciprocal_low(self.width, self.mask)
+    }
+
+    pub fn up(&self, prev_state: u128) -> u128 {
+        let feedback = ((prev_state & self.feedback_mask()).count_ones() & 1) as u128;
+        (prev_state >> 1) | (feedback << (self.width - 1))
+    }
+
+    pub fn down(&self, prev_state: u128) -> u128 {
+        // the bit that was shifted out is the only one in the feedback
+        // that isn't in the current state anymore
+        let msb = prev_state >> (self.width - 1);
+        let inv_mask = self.feedback_mask() >> 1;
+        let lsb = (msb ^ ((prev_state & inv_mask).count_ones() & 1) as u128) & 1;
+        ((prev_state << 1) | lsb) & state_mask(self.width)
+    }
+}
+
+impl LFSR for FibonacciLfsr {
+    type State = u128;
+
+    fn get_state(&self) -> u128 {
+        self.state
+    }
+
+    fn set_state(&mut self, state: u128) {
+        self.state = state & state_mask(self.width);
+    }
+
+    fn inc(&mut self) {
+        self.state = self.up(self.state);
+    }
+
+    fn dec(&mut self) {
+        self.state = self.down(self.state);
+    }
+
+    /// Period of the current state, `0` if the taps don't include `width` and the state never comes back
+    fn sequence_length(&self) -> u128 {
+        if self.mask >> (self.width - 1) & 1 == 0 {
+            return 0;
+        }
+        // the state holds the next `width` output bits, the numerator of their generating function
+        // shares factors with the feedback polynomial that don't matter for the period
+        let polynomial = feedback_polynomial(self.mask);
+        let numerator = polynomial * Poly::from(self.state) % Poly::monomial(self.width);
+        (polynomial / polynomial.gcd(numerator)).order()
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn advance(&mut self, n: u128) {
+        let low = reciprocal_low(self.width, self.mask);
+        self.state = jump(self.state, n, self.width, low, |state| self.up(state));
+    }
+
+    fn rewind(&mut self, n: u128) {
+        let low = low(self.width, self.mask);
+        self.state = jump(self.state, n, self.width, low, |state| self.down(state));
+    }
+}
+
+impl FibonacciXorLFSR for FibonacciLfsr {}
@@ -0,0 +1,74 @@
+//! Aliasing of signature analysers built on Galois LFSRs, as generated by `galois_misr!`
+//! and `galois_sisr!`
+//!
+//! A signature analyser compresses a stream of test responses into a `width`-bit signature.
+//! It's linear, so an erroneous stream gives the same signature as the good one exactly when
+//! the XOR of the two, the error stream, compresses to zero. With every one of the `2^bits - 1`
+//! error streams of `bits` bits equally likely, that happens with the probability
+//! `(2^(bits - width) - 1) / (2^bits - 1)`, which approaches `2^-width` for long streams.
+//! This holds for any feedback polynomial with a `+ 1`, primitive or not, since stepping is then
+//! invertible and `width` or more input bits reach every signature.
+//!
+//! ```rust
+//! use lfsr_base::signature::aliasing_probability;
+//!
+//! // a 16-bit analyser misses one error stream in 65536 in the long run
+//! assert!((aliasing_probability(16, 1 << 20) - 1.0 / 65536.0).abs() < 1e-12);
+//! // up to 16 bits the signature is a one-to-one function of the stream
+//! assert_eq!(aliasing_probability(16, 16), 0.0);
+//! ```
+//!
+
+/// Probability that a `width`-bit signature analyser doesn't detect an error in a stream of
+/// `bits` bits, every error stream being equally likely
+///
+/// `bits` counts all the input bits, so for a multiple input analyser it's `width` times
+/// the number of words.
+pub fn aliasing_probability(width: u32, bits: u128) -> f64 {
+    if bits == 0 {
+        // nothing to get wrong
+        return 0.0;
+    }
+    // only the signature bits that the stream reaches can tell errors apart
+    let rank = bits.min(u128::from(width));
+    // (2^(bits - rank) - 1) / (2^bits - 1), scaled down by 2^-bits to stay finite
+    pow2_neg(rank) * (1.0 - pow2_neg(bits - rank)) / (1.0 - pow2_neg(bits))
+}
+
+/// `2^-k`, which is `1` for `k == 0` and underflows to `0` beyond the smallest subnormal `f64`
+fn pow2_neg(k: u128) -> f64 {
+    if k <= 1022 {
+        f64::from_bits((1023 - k as u64) << 52)
+    } else if k <= 1074 {
+        // subnormals have the exponent bits at zero and the power of two in the mantissa
+        f64::from_bits(1 << (1074 - k as u64))
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powers_of_two() {
+        assert_eq!(pow2_neg(0), 1.0);
+        assert_eq!(pow2_neg(1), 0.5);
+        assert_eq!(pow2_neg(1022), f64::MIN_POSITIVE);
+        assert_eq!(pow2_neg(1023), f64::MIN_POSITIVE / 2.0);
+        assert_eq!(pow2_neg(1074), f64::from_bits(1));
+        assert_eq!(pow2_neg(1075), 0.0);
+    }
+
+    #[test]
+    fn aliasing() {
+        assert_eq!(aliasing_probability(8, 0), 0.0);
+        assert_eq!(aliasing_probability(8, 8), 0.0);
+        // one of the 511 non-zero 9-bit error streams compresses to zero
+        assert_eq!(aliasing_probability(8, 9), 1.0 / 511.0);
+        assert_eq!(aliasing_probability(8, 16), 255.0 / 65535.0);
+        assert_eq!(aliasing_probability(32, u128::MAX), 1.0 / 4294967296.0);
+        assert_eq!(aliasing_probability(128, 1 << 100), pow2_neg(128));
+    }
+}
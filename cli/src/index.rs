@@ -0,0 +1,159 @@
+//! Positions of states in a sequence
+
+use lfsr_base::discrete_log::{Field, Subgroup};
+use lfsr_base::gf2::{mul_mod, pow_mod, x_pow_mod};
+use lfsr_base::mersenne::MERSENNE_PRIME_FACTORS;
+use lfsr_base::LFSR;
+
+use crate::spec::{Register, Spec};
+
+/// Baby steps kept for every prime factor, at most `2^22` of them, `32` bytes each
+fn table_size(prime: u128) -> u128 {
+    let mut size = 1u128;
+    while size * size < prime && size < 1 << 22 {
+        size *= 2;
+    }
+    size.min(prime)
+}
+
+/// The same tables `discrete_log_lfsr_lookup!` builds at compile time, leaked since they live
+/// as long as the process anyway
+fn field(width: u32, low: u128) -> Field<u128> {
+    let order = u128::MAX >> (128 - width);
+    let subgroups: Vec<Subgroup<u128>> = MERSENNE_PRIME_FACTORS[width as usize]
+        .iter()
+        .map(|prime| {
+            let mut multiplicity = 0u32;
+            let mut rest = order;
+            while rest.is_multiple_of(*prime) {
+                rest /= prime;
+                multiplicity += 1;
+            }
+
+            let generator = x_pow_mod(order / prime, width, low);
+            let steps = table_size(*prime);
+            let mut baby_steps = Vec::with_capacity(steps as usize);
+            let mut element = 1;
+            for i in 0..steps as u32 {
+                baby_steps.push((element, i));
+                element = mul_mod(element, generator, width, low);
+            }
+            baby_steps.sort_unstable();
+            Subgroup {
+                prime: *prime,
+                multiplicity,
+                giant_step: pow_mod(generator, prime - steps, width, low),
+                baby_steps: Box::leak(baby_steps.into_boxed_slice()),
+            }
+        })
+        .collect();
+    Field {
+        degree: width,
+        low,
+        subgroups: Box::leak(subgroups.into_boxed_slice()),
+    }
+}
+
+/// Number of `up` steps from `from` to `to`
+///
+/// Maximum length Galois LFSRs take discrete logarithms with at most `limit` giant steps per prime
+/// factor, everything else steps at most `limit` times.
+pub fn steps_between(spec: &Spec, from: u128, to: u128, limit: u128) -> Result<u128, String> {
+    let width = spec.width();
+    let order = u128::MAX >> (128 - width);
+    if let Register::Galois(lfsr) = spec.register {
+        if width >= 2 && spec.polynomial().is_primitive() {
+            if from == 0 || to == 0 {
+                return Err("0 is the lock-up state, it's not in the sequence".to_string());
+            }
+            // `down` multiplies the state by `x` modulo the feedback polynomial
+            let low = ((lfsr.mask << 1) | 1) & order;
+            // every prime factor takes up to `prime / table_size` giant steps
+            if MERSENNE_PRIME_FACTORS[width as usize]
+                .iter()
+                .any(|prime| prime / table_size(*prime) > limit)
+            {
+                return Err(format!(
+                    "lookup is infeasible for this width, it takes more than {} steps",
+                    limit
+                ));
+            }
+            let field = field(width, low);
+            let position = |state| {
+                field
+                    .galois_position(state)
+                    .expect("non-zero states have positions")
+            };
+            let (pf, pt) = (position(from), position(to));
+            return Ok(if pt >= pf { pt - pf } else { order - (pf - pt) });
+        }
+    }
+
+    let mut lfsr = *spec;
+    lfsr.set_state(from);
+    let mut steps = 0;
+    while steps <= limit {
+        if lfsr.get_state() == to {
+            return Ok(steps);
+        }
+        lfsr.inc();
+        steps += 1;
+    }
+    Err(format!("not found within {} steps", limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discrete_logarithms_and_search_agree() {
+        for (spec, fibonacci) in [
+            ("galois16", false),
+            ("galois64", false),
+            ("16,14,13,11", true),
+            ("4,2", false),
+        ]
+        .iter()
+        {
+            let spec = Spec::parse(spec, *fibonacci).unwrap();
+            let mut lfsr = spec;
+            lfsr.advance(1000);
+            let from = lfsr.get_state();
+            lfsr.advance(12345);
+            assert_eq!(
+                steps_between(&spec, from, lfsr.get_state(), 20000),
+                Ok(12345 % spec.sequence_length())
+            );
+            assert_eq!(
+                steps_between(&spec, lfsr.get_state(), lfsr.get_state(), 20000),
+                Ok(0)
+            );
+        }
+
+        let galois64 = Spec::parse("galois64", false).unwrap();
+        let mut lfsr = galois64;
+        lfsr.advance(u64::MAX as u128 - 5);
+        assert_eq!(
+            steps_between(&galois64, 1, lfsr.get_state(), 20000),
+            Ok(u64::MAX as u128 - 5)
+        );
+        assert!(steps_between(&galois64, 0, 1, 20000).is_err());
+
+        let galois128 = Spec::parse("galois128", false).unwrap();
+        let mut lfsr = galois128;
+        lfsr.set_state(1);
+        lfsr.advance(5);
+        assert_eq!(
+            steps_between(&galois128, lfsr.get_state(), 1, 1 << 32),
+            Ok(u128::MAX - 5)
+        );
+        assert!(steps_between(&galois64, 1, 2, 1000).is_err());
+        assert!(steps_between(&Spec::parse("galois127", false).unwrap(), 1, 2, 1 << 32).is_err());
+
+        let fibonacci = Spec::parse("16,14,13,11", true).unwrap();
+        let mut lfsr = fibonacci;
+        lfsr.advance(101);
+        assert!(steps_between(&fibonacci, 1, lfsr.get_state(), 100).is_err());
+    }
+}
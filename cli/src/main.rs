@@ -0,0 +1,604 @@
+//! `lfsr`: steps, looks up, finds, tests and dumps linear feedback shift registers
+//!
+//! ```text
+//! $ lfsr step galois16 0x1234
+//! 0x091a
+//! $ lfsr index galois32 0xdeadbeef
+//! 2382457060
+//! $ lfsr taps 20 --taps 2
+//! 20,17  x^20 + x^17 + 1
+//! 20,3  x^20 + x^3 + 1
+//! $ lfsr dump prbs23 --bytes 1M --format raw --output prbs23.bin
+//! $ lfsr bm prbs23.bin
+//! ```
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use lfsr_base::berlekamp_massey::berlekamp_massey;
+use lfsr_base::gf2::{primitive_polynomials, Notation, Poly};
+use lfsr_base::LFSR;
+
+mod index;
+mod spec;
+
+use crate::spec::{Spec, SPEC_HELP};
+
+#[derive(Parser)]
+#[command(
+    name = "lfsr",
+    version,
+    about = "Steps, looks up, finds, tests and dumps linear feedback shift registers"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints the state some steps after or before a state
+    Step {
+        #[arg(help = SPEC_HELP)]
+        lfsr: String,
+        /// Hex with `0x`, binary with `0b` or decimal
+        state: String,
+        /// Number of steps
+        #[arg(short = 'n', long, default_value = "1")]
+        steps: String,
+        /// Steps backwards
+        #[arg(long)]
+        back: bool,
+        /// Polynomials and taps are Fibonacci LFSRs instead of Galois ones
+        #[arg(long)]
+        fibonacci: bool,
+    },
+    /// Prints how many steps it takes to get to a state
+    ///
+    /// Takes discrete logarithms for maximum length Galois LFSRs and steps through the sequence for others.
+    Index {
+        #[arg(help = SPEC_HELP)]
+        lfsr: String,
+        state: String,
+        /// Counts from this state instead of the default one
+        #[arg(long)]
+        from: Option<String>,
+        /// Gives up after this many steps when stepping through the sequence or taking discrete logarithms
+        #[arg(long, default_value = "0x100000000")]
+        limit: String,
+        /// Polynomials and taps are Fibonacci LFSRs instead of Galois ones
+        #[arg(long)]
+        fibonacci: bool,
+    },
+    /// Lists primitive polynomials of a width
+    ///
+    /// They come in the order of their coefficients or, with `--taps`, in decreasing order of the taps.
+    Taps {
+        width: u32,
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+        /// Only polynomials with this many taps, including the width
+        #[arg(long)]
+        taps: Option<u32>,
+    },
+    /// Tests a polynomial for irreducibility and primitivity and prints its order
+    Primitive {
+        /// A polynomial like 'x^16 + x^14 + x^13 + x^11 + 1' or taps like 16,14,13,11
+        polynomial: String,
+    },
+    /// Finds the shortest LFSR that generates the bits of a file with Berlekamp–Massey
+    Bm {
+        file: PathBuf,
+        /// Reads the bits of a byte from the least significant one
+        #[arg(long)]
+        lsb_first: bool,
+        /// Reads `0` and `1` characters, skipping whitespace, instead of bytes
+        #[arg(long)]
+        text: bool,
+    },
+    /// Writes output bits of an LFSR, the least significant bit of every state
+    Dump {
+        #[arg(help = SPEC_HELP)]
+        lfsr: String,
+        /// Starts here instead of the default state
+        #[arg(long)]
+        seed: Option<String>,
+        /// Number of bytes, with an optional K, M or G suffix
+        #[arg(short = 'n', long, default_value = "64")]
+        bytes: String,
+        #[arg(short, long, value_enum, default_value_t = Format::Hex)]
+        format: Format,
+        /// Packs the first bit of a byte into its least significant bit
+        #[arg(long)]
+        lsb_first: bool,
+        /// Writes to this file instead of standard output
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Polynomials and taps are Fibonacci LFSRs instead of Galois ones
+        #[arg(long)]
+        fibonacci: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum Format {
+    /// Bytes as they are
+    Raw,
+    /// 32 bytes per line
+    Hex,
+    /// `0` and `1`, 64 per line
+    Bits,
+}
+
+/// Hex with `0x`, binary with `0b` or decimal, with optional `_` separators
+fn parse_number(s: &str) -> Result<u128, String> {
+    let digits = s.trim().replace('_', "");
+    let parsed = if let Some(hex) = digits.strip_prefix("0x") {
+        u128::from_str_radix(hex, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        u128::from_str_radix(binary, 2)
+    } else {
+        digits.parse()
+    };
+    parsed.map_err(|_| format!("`{}` isn't a number", s))
+}
+
+/// A number with an optional binary `K`, `M` or `G` suffix
+fn parse_size(s: &str) -> Result<usize, String> {
+    let s = s.trim();
+    let (digits, unit) = match s.char_indices().last() {
+        Some((ix, 'K')) | Some((ix, 'k')) => (&s[..ix], 1 << 10),
+        Some((ix, 'M')) => (&s[..ix], 1 << 20),
+        Some((ix, 'G')) => (&s[..ix], 1 << 30),
+        _ => (s, 1),
+    };
+    parse_number(digits)?
+        .checked_mul(unit)
+        .and_then(|size| usize::try_from(size).ok())
+        .ok_or_else(|| format!("`{}` is too big", s))
+}
+
+/// `0x` and as many hex digits as the width needs
+fn format_state(state: u128, width: u32) -> String {
+    format!("0x{:01$x}", state, (width as usize).div_ceil(4))
+}
+
+fn format_taps(taps: &[u32]) -> String {
+    taps.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_state(spec: &Spec, s: &str) -> Result<u128, String> {
+    let state = parse_number(s)?;
+    if state >> (spec.width() - 1) > 1 {
+        return Err(format!("`{}` doesn't fit into {} bits", s, spec.width()));
+    }
+    Ok(state)
+}
+
+/// Calls `found` with primitive polynomials of degree `width` with `taps` taps, in decreasing order
+/// of the taps, until it returns `false`
+fn primitive_with_taps(width: u32, taps: u32, found: &mut dyn FnMut(Poly) -> bool) {
+    /// The rest of the taps below `below`, `false` once `found` is done
+    fn rest(polynomial: Poly, below: u32, left: u32, found: &mut dyn FnMut(Poly) -> bool) -> bool {
+        if left == 0 {
+            return !polynomial.is_primitive() || found(polynomial);
+        }
+        for tap in (left..below).rev() {
+            if !rest(polynomial ^ Poly::monomial(tap), tap, left - 1, found) {
+                return false;
+            }
+        }
+        true
+    }
+    if taps >= 1 {
+        rest(Poly::from_taps(&[width]), width, taps - 1, found);
+    }
+}
+
+fn read_bits(file: &PathBuf, lsb_first: bool, text: bool) -> Result<Vec<bool>, String> {
+    let bytes = std::fs::read(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+    if text {
+        return bytes
+            .iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .map(|byte| match byte {
+                b'0' => Ok(false),
+                b'1' => Ok(true),
+                _ => Err(format!(
+                    "{}: `{}` isn't a bit",
+                    file.display(),
+                    *byte as char
+                )),
+            })
+            .collect();
+    }
+    Ok(bytes
+        .iter()
+        .flat_map(|byte| {
+            (0..8).map(move |i| if lsb_first { byte >> i } else { byte >> (7 - i) } & 1 == 1)
+        })
+        .collect())
+}
+
+fn run(command: Command, out: &mut dyn Write) -> Result<(), String> {
+    let io = |err: io::Error| err.to_string();
+    match command {
+        Command::Step {
+            lfsr,
+            state,
+            steps,
+            back,
+            fibonacci,
+        } => {
+            let mut spec = Spec::parse(&lfsr, fibonacci)?;
+            spec.set_state(parse_state(&spec, &state)?);
+            let steps = parse_number(&steps)?;
+            if back {
+                spec.rewind(steps);
+            } else {
+                spec.advance(steps);
+            }
+            writeln!(out, "{}", format_state(spec.get_state(), spec.width())).map_err(io)?;
+        }
+        Command::Index {
+            lfsr,
+            state,
+            from,
+            limit,
+            fibonacci,
+        } => {
+            let spec = Spec::parse(&lfsr, fibonacci)?;
+            let to = parse_state(&spec, &state)?;
+            let from = match from {
+                Some(from) => parse_state(&spec, &from)?,
+                None => spec.get_state(),
+            };
+            let steps = index::steps_between(&spec, from, to, parse_number(&limit)?)?;
+            writeln!(out, "{}", steps).map_err(io)?;
+        }
+        Command::Taps { width, count, taps } => {
+            if !(1..=128).contains(&width) {
+                return Err(format!("width {} is not in 1..=128", width));
+            }
+            let mut polynomials = Vec::new();
+            let mut found = |polynomial| {
+                polynomials.push(polynomial);
+                polynomials.len() < count
+            };
+            match taps {
+                _ if count == 0 => {}
+                Some(taps) => primitive_with_taps(width, taps, &mut found),
+                None => primitive_polynomials(width)
+                    .take(count)
+                    .for_each(|polynomial| {
+                        found(polynomial);
+                    }),
+            }
+            for polynomial in polynomials {
+                let taps: Vec<u32> = polynomial.taps().collect();
+                writeln!(out, "{}  {}", format_taps(&taps), polynomial).map_err(io)?;
+            }
+        }
+        Command::Primitive { polynomial } => {
+            let polynomial = if polynomial.contains('x') || polynomial.trim() == "1" {
+                polynomial
+                    .parse::<Poly>()
+                    .map_err(|err| format!("`{}`: {}", polynomial, err))?
+            } else {
+                let taps = polynomial
+                    .split(',')
+                    .map(|tap| {
+                        parse_number(tap).and_then(|tap| {
+                            u32::try_from(tap)
+                                .ok()
+                                .filter(|tap| *tap <= 255)
+                                .ok_or_else(|| "taps have to be at most 255".to_string())
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Poly::from_taps(&taps)
+            };
+            let yes_no = |b: bool| if b { "yes" } else { "no" };
+            let degree = polynomial.degree().unwrap_or(0);
+            writeln!(out, "{}", polynomial).map_err(io)?;
+            writeln!(out, "degree: {}", degree).map_err(io)?;
+            writeln!(out, "irreducible: {}", yes_no(polynomial.is_irreducible())).map_err(io)?;
+            if (1..=128).contains(&degree) && polynomial.coefficient(0) {
+                writeln!(out, "primitive: {}", yes_no(polynomial.is_primitive())).map_err(io)?;
+                writeln!(out, "order: {}", polynomial.order()).map_err(io)?;
+                let taps: Vec<u32> = polynomial.taps().collect();
+                writeln!(out, "taps: {}", format_taps(&taps)).map_err(io)?;
+                for (name, notation) in [
+                    ("koopman", Notation::Koopman),
+                    ("normal", Notation::Normal),
+                    ("reversed", Notation::Reversed),
+                    ("reciprocal", Notation::Reciprocal),
+                ]
+                .iter()
+                {
+                    if let Some(mask) = polynomial.to_notation(*notation) {
+                        writeln!(out, "{}: {}", name, format_state(mask, degree)).map_err(io)?;
+                    }
+                }
+            }
+        }
+        Command::Bm {
+            file,
+            lsb_first,
+            text,
+        } => {
+            let bits = read_bits(&file, lsb_first, text)?;
+            let synthesis = berlekamp_massey(bits.iter().copied())
+                .ok_or("the shortest LFSR that generates these bits is longer than 128 bits")?;
+            let complexity = synthesis.linear_complexity;
+            let polynomial = synthesis.connection_polynomial();
+            writeln!(out, "bits: {}", bits.len()).map_err(io)?;
+            writeln!(out, "linear complexity: {}", complexity).map_err(io)?;
+            writeln!(out, "connection polynomial: {}", polynomial).map_err(io)?;
+            if bits.len() < 2 * complexity as usize {
+                writeln!(
+                    out,
+                    "only {} bits, at least {} are needed for the LFSR to be the only one",
+                    bits.len(),
+                    2 * complexity
+                )
+                .map_err(io)?;
+            }
+            if let Some(lfsr) = synthesis.lfsr() {
+                let taps: Vec<u32> = synthesis.taps().collect();
+                writeln!(
+                    out,
+                    "primitive: {}",
+                    if polynomial.is_primitive() {
+                        "yes"
+                    } else {
+                        "no"
+                    }
+                )
+                .map_err(io)?;
+                writeln!(out, "taps: {}", format_taps(&taps)).map_err(io)?;
+                writeln!(
+                    out,
+                    "fibonacci_lfsr!(Name, {}, {}) from {}",
+                    complexity,
+                    taps.iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    format_state(lfsr.state, complexity)
+                )
+                .map_err(io)?;
+            }
+        }
+        Command::Dump {
+            lfsr,
+            seed,
+            bytes,
+            format,
+            lsb_first,
+            output,
+            fibonacci,
+        } => {
+            let mut spec = Spec::parse(&lfsr, fibonacci)?;
+            if let Some(seed) = seed {
+                spec.set_state(parse_state(&spec, &seed)?);
+            }
+            let bytes = parse_size(&bytes)?;
+            match output {
+                Some(path) => {
+                    let file = File::create(&path)
+                        .map_err(|err| format!("{}: {}", path.display(), err))?;
+                    dump(spec, bytes, format, lsb_first, &mut BufWriter::new(file)).map_err(io)?;
+                }
+                None => dump(spec, bytes, format, lsb_first, out).map_err(io)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+fn dump(
+    spec: Spec,
+    bytes: usize,
+    format: Format,
+    lsb_first: bool,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut bits = spec.bits();
+    let mut buffer = vec![0u8; 4096];
+    let mut written = 0;
+    while written < bytes {
+        let chunk = &mut buffer[..(bytes - written).min(4096)];
+        bits.fill_bytes(chunk);
+        if lsb_first {
+            chunk
+                .iter_mut()
+                .for_each(|byte| *byte = byte.reverse_bits());
+        }
+        match format {
+            Format::Raw => out.write_all(chunk)?,
+            // chunks are a multiple of 32 bytes except the last one
+            Format::Hex => {
+                for line in chunk.chunks(32) {
+                    line.iter()
+                        .try_for_each(|byte| write!(out, "{:02x}", byte))?;
+                    writeln!(out)?;
+                }
+            }
+            Format::Bits => {
+                for line in chunk.chunks(8) {
+                    line.iter().try_for_each(|byte| {
+                        let first = if lsb_first {
+                            byte.reverse_bits()
+                        } else {
+                            *byte
+                        };
+                        write!(out, "{:08b}", first)
+                    })?;
+                    writeln!(out)?;
+                }
+            }
+        }
+        written += chunk.len();
+    }
+    out.flush()
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    if let Err(err) =
+        run(cli.command, &mut out).and_then(|_| out.flush().map_err(|err| err.to_string()))
+    {
+        eprintln!("lfsr: {}", err);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(args: &[&str]) -> Result<String, String> {
+        let cli = Cli::try_parse_from(std::iter::once("lfsr").chain(args.iter().copied()))
+            .map_err(|err| err.to_string())?;
+        let mut out = Vec::new();
+        run(cli.command, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn numbers_and_sizes() {
+        assert_eq!(parse_number("0x1234"), Ok(0x1234));
+        assert_eq!(parse_number("0b1010_1010"), Ok(0xaa));
+        assert_eq!(parse_number("1_000"), Ok(1000));
+        assert!(parse_number("0xg").is_err());
+        assert_eq!(parse_size("1M"), Ok(1 << 20));
+        assert_eq!(parse_size("3k"), Ok(3 << 10));
+        assert_eq!(parse_size("100"), Ok(100));
+        assert_eq!(format_state(0x91a, 16), "0x091a");
+        assert_eq!(format_state(1, 23), "0x000001");
+    }
+
+    #[test]
+    fn step_and_index() {
+        use lfsr_base::FibonacciLfsr;
+        use lfsr_instances::galois::Galois16;
+
+        let mut expected = Galois16::new(0x1234);
+        expected.inc();
+        assert_eq!(
+            output(&["step", "galois16", "0x1234"]).unwrap(),
+            format!("{:#06x}\n", expected.state)
+        );
+        assert_eq!(
+            output(&["step", "galois16", &format!("{}", expected.state), "--back"]).unwrap(),
+            "0x1234\n"
+        );
+        assert_eq!(
+            output(&["step", "x^16 + x^14 + x^13 + x^11 + 1", "1", "-n", "1000"]).unwrap(),
+            format!("{:#06x}\n", Galois16::advance(1, 1000))
+        );
+        assert!(output(&["step", "galois16", "0x10000"]).is_err());
+
+        let state = Galois16::advance(1, 54321);
+        assert_eq!(
+            output(&["index", "galois16", &state.to_string()]).unwrap(),
+            "54321\n"
+        );
+        let from = Galois16::advance(1, 1000);
+        assert_eq!(
+            output(&[
+                "index",
+                "galois16",
+                &state.to_string(),
+                "--from",
+                &from.to_string()
+            ])
+            .unwrap(),
+            "53321\n"
+        );
+        let mut fibonacci = FibonacciLfsr::new(16, &[16, 14, 13, 11], 1).unwrap();
+        fibonacci.advance(40000);
+        let state = fibonacci.state.to_string();
+        assert_eq!(
+            output(&["index", "16,14,13,11", &state, "--fibonacci"]).unwrap(),
+            "40000\n"
+        );
+    }
+
+    #[test]
+    fn finding_and_testing_polynomials() {
+        assert_eq!(
+            output(&["taps", "20", "--taps", "2"]).unwrap(),
+            "20,17  x^20 + x^17 + 1\n20,3  x^20 + x^3 + 1\n"
+        );
+        assert_eq!(
+            output(&["taps", "20", "--taps", "2", "-n", "1"]).unwrap(),
+            "20,17  x^20 + x^17 + 1\n"
+        );
+        assert_eq!(output(&["taps", "6"]).unwrap().lines().count(), 6);
+        assert_eq!(
+            output(&["taps", "64", "--taps", "4", "-n", "1"]).unwrap(),
+            "64,63,62,53  x^64 + x^63 + x^62 + x^53 + 1\n"
+        );
+
+        let primitive = output(&["primitive", "16,14,13,11"]).unwrap();
+        assert!(primitive.contains("primitive: yes\norder: 65535\n"));
+        assert!(primitive.contains("koopman: 0xb400\n"));
+        let not_primitive = output(&["primitive", "x^4 + x^2 + 1"]).unwrap();
+        assert!(not_primitive.contains("irreducible: no\nprimitive: no\norder: 6\n"));
+        assert!(output(&["primitive", "x^4 + y"]).is_err());
+    }
+
+    #[test]
+    fn dump_and_berlekamp_massey() {
+        // the published first bytes of PRBS9
+        assert_eq!(output(&["dump", "prbs9", "-n", "4"]).unwrap(), "ff83df17\n");
+        assert_eq!(
+            output(&["dump", "prbs9", "-n", "2", "--format", "bits"]).unwrap(),
+            "1111111110000011\n"
+        );
+        assert_eq!(
+            output(&["dump", "prbs9", "-n", "1", "--lsb-first"]).unwrap(),
+            "ff\n"
+        );
+        assert_eq!(
+            output(&["dump", "galois16", "-n", "100"])
+                .unwrap()
+                .lines()
+                .count(),
+            4
+        );
+
+        let path = std::env::temp_dir().join(format!("lfsr-cli-test-{}.bin", process::id()));
+        let path_str = path.to_str().unwrap();
+        output(&[
+            "dump",
+            "galois32",
+            "--seed",
+            "0xdeadbeef",
+            "-n",
+            "1K",
+            "-f",
+            "raw",
+            "-o",
+            path_str,
+        ])
+        .unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 1024);
+        let bm = output(&["bm", path_str]).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(bm.contains("bits: 8192\nlinear complexity: 32\n"), "{}", bm);
+        assert!(bm.contains("primitive: yes\n"));
+    }
+}
@@ -0,0 +1,258 @@
+//! LFSRs named on the command line
+
+use lfsr_base::gf2::Poly;
+use lfsr_base::{FibonacciLfsr, GaloisLfsr, LFSR};
+use lfsr_instances::galois;
+use lfsr_instances::prbs::{self, Prbs};
+
+macro_rules! galois_taps {
+    ($($name:ident),*) => {
+        /// Taps of `lfsr_instances::galois` types, from `Galois2` up
+        const GALOIS_TAPS: &[&[u32]] = &[$(galois::$name::TAPS),*];
+    };
+}
+
+galois_taps!(
+    Galois2, Galois3, Galois4, Galois5, Galois6, Galois7, Galois8, Galois9, Galois10, Galois11,
+    Galois12, Galois13, Galois14, Galois15, Galois16, Galois17, Galois18, Galois19, Galois20,
+    Galois21, Galois22, Galois23, Galois24, Galois25, Galois26, Galois27, Galois28, Galois29,
+    Galois30, Galois31, Galois32, Galois33, Galois34, Galois35, Galois36, Galois37, Galois38,
+    Galois39, Galois40, Galois41, Galois42, Galois43, Galois44, Galois45, Galois46, Galois47,
+    Galois48, Galois49, Galois50, Galois51, Galois52, Galois53, Galois54, Galois55, Galois56,
+    Galois57, Galois58, Galois59, Galois60, Galois61, Galois62, Galois63, Galois64, Galois65,
+    Galois66, Galois67, Galois68, Galois69, Galois70, Galois71, Galois72, Galois73, Galois74,
+    Galois75, Galois76, Galois77, Galois78, Galois79, Galois80, Galois81, Galois82, Galois83,
+    Galois84, Galois85, Galois86, Galois87, Galois88, Galois89, Galois90, Galois91, Galois92,
+    Galois93, Galois94, Galois95, Galois96, Galois97, Galois98, Galois99, Galois100, Galois101,
+    Galois102, Galois103, Galois104, Galois105, Galois106, Galois107, Galois108, Galois109,
+    Galois110, Galois111, Galois112, Galois113, Galois114, Galois115, Galois116, Galois117,
+    Galois118, Galois119, Galois120, Galois121, Galois122, Galois123, Galois124, Galois125,
+    Galois126, Galois127, Galois128
+);
+
+macro_rules! o150_patterns {
+    ($($name:literal => $pattern:ident, $register:ident),*) => {
+        /// Width, taps and inversion of an O.150 pattern
+        fn o150(name: &str) -> Option<(u32, &'static [u32], bool)> {
+            match name {
+                $($name => Some((prbs::$register::WIDTH, prbs::$register::TAPS, prbs::$pattern::default().inverted)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+o150_patterns!(
+    "prbs7" => Prbs7, Prbs7Register,
+    "prbs9" => Prbs9, Prbs9Register,
+    "prbs11" => Prbs11, Prbs11Register,
+    "prbs15" => Prbs15, Prbs15Register,
+    "prbs20" => Prbs20, Prbs20Register,
+    "prbs23" => Prbs23, Prbs23Register,
+    "prbs31" => Prbs31, Prbs31Register
+);
+
+/// How the help describes an LFSR argument
+pub const SPEC_HELP: &str = "galois2 to galois128, prbs7 to prbs31 (O.150), \
+a feedback polynomial like 'x^16 + x^14 + x^13 + x^11 + 1' or taps like 16,14,13,11";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Register {
+    Galois(GaloisLfsr),
+    Fibonacci(FibonacciLfsr),
+}
+
+/// An LFSR given on the command line, in its default state
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Spec {
+    pub register: Register,
+    /// Output bits are inverted, as in some O.150 patterns
+    pub inverted: bool,
+}
+
+/// A feedback polynomial or 1-based taps, `None` for anything else
+fn parse_taps(s: &str) -> Option<Result<Vec<u32>, String>> {
+    if s.contains('x') {
+        return Some(match s.parse::<Poly>() {
+            Ok(polynomial) if polynomial.coefficient(0) && polynomial.degree() > Some(0) => {
+                Ok(polynomial.taps().collect())
+            }
+            Ok(polynomial) => Err(format!(
+                "`{}` has no `+ 1`, it isn't a feedback polynomial",
+                polynomial
+            )),
+            Err(err) => Err(format!("`{}`: {}", s, err)),
+        });
+    }
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || c == ',' || c == ' ')
+    {
+        return Some(
+            s.split(',')
+                .map(|tap| {
+                    tap.trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("`{}` isn't a tap", tap))
+                })
+                .collect(),
+        );
+    }
+    None
+}
+
+impl Spec {
+    /// Polynomials and taps make a Galois LFSR unless `fibonacci`, named LFSRs are what they are
+    pub fn parse(s: &str, fibonacci: bool) -> Result<Self, String> {
+        let name = s.trim().to_lowercase();
+        if let Some(width) = name
+            .strip_prefix("galois")
+            .and_then(|width| width.parse::<usize>().ok())
+        {
+            let taps = width
+                .checked_sub(2)
+                .and_then(|ix| GALOIS_TAPS.get(ix))
+                .ok_or_else(|| format!("there's no galois{}, widths are 2 to 128", width))?;
+            let lfsr = GaloisLfsr::new(width as u32, taps, 1).map_err(|err| err.to_string())?;
+            return Ok(Self {
+                register: Register::Galois(lfsr),
+                inverted: false,
+            });
+        }
+        if let Some((width, taps, inverted)) = o150(&name) {
+            // O.150 patterns start at the longest run
+            let lfsr = FibonacciLfsr::new(width, taps, u128::MAX).map_err(|err| err.to_string())?;
+            return Ok(Self {
+                register: Register::Fibonacci(lfsr),
+                inverted,
+            });
+        }
+
+        let taps = parse_taps(s)
+            .unwrap_or_else(|| Err(format!("`{}` isn't an LFSR: {}", s, SPEC_HELP)))?;
+        let width = taps.iter().copied().max().unwrap_or(0);
+        let register = if fibonacci {
+            Register::Fibonacci(FibonacciLfsr::new(width, &taps, 1).map_err(|err| err.to_string())?)
+        } else {
+            Register::Galois(GaloisLfsr::new(width, &taps, 1).map_err(|err| err.to_string())?)
+        };
+        Ok(Self {
+            register,
+            inverted: false,
+        })
+    }
+
+    pub fn lfsr(&self) -> &dyn LFSR<State = u128> {
+        match &self.register {
+            Register::Galois(lfsr) => lfsr,
+            Register::Fibonacci(lfsr) => lfsr,
+        }
+    }
+
+    pub fn lfsr_mut(&mut self) -> &mut dyn LFSR<State = u128> {
+        match &mut self.register {
+            Register::Galois(lfsr) => lfsr,
+            Register::Fibonacci(lfsr) => lfsr,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.lfsr().width()
+    }
+
+    /// Bit `tap - 1` for every 1-based tap
+    pub fn mask(&self) -> u128 {
+        match self.register {
+            Register::Galois(lfsr) => lfsr.mask,
+            Register::Fibonacci(lfsr) => lfsr.mask,
+        }
+    }
+
+    pub fn polynomial(&self) -> Poly {
+        (Poly::from(self.mask()) * Poly::X) ^ Poly::ONE
+    }
+
+    /// The output bits from the current state on, inverted if the pattern is
+    pub fn bits(self) -> Prbs<Spec> {
+        let inverted = self.inverted;
+        Prbs::new(self, inverted)
+    }
+}
+
+/// Steps the register, so that `Prbs` can invert its output bits
+impl LFSR for Spec {
+    type State = u128;
+
+    fn get_state(&self) -> u128 {
+        self.lfsr().get_state()
+    }
+
+    fn set_state(&mut self, state: u128) {
+        self.lfsr_mut().set_state(state)
+    }
+
+    fn inc(&mut self) {
+        self.lfsr_mut().inc()
+    }
+
+    fn dec(&mut self) {
+        self.lfsr_mut().dec()
+    }
+
+    fn sequence_length(&self) -> u128 {
+        self.lfsr().sequence_length()
+    }
+
+    fn width(&self) -> u32 {
+        self.lfsr().width()
+    }
+
+    fn advance(&mut self, n: u128) {
+        self.lfsr_mut().advance(n)
+    }
+
+    fn rewind(&mut self, n: u128) {
+        self.lfsr_mut().rewind(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lfsr_instances::prbs::Prbs23;
+
+    #[test]
+    fn builtin_and_given_lfsrs() {
+        let galois16 = Spec::parse("Galois16", false).unwrap();
+        assert_eq!(
+            galois16.register,
+            Register::Galois(GaloisLfsr::new(16, galois::Galois16::TAPS, 1).unwrap())
+        );
+        assert_eq!(Spec::parse("galois128", false).unwrap().width(), 128);
+        assert!(Spec::parse("galois1", false).is_err());
+        assert!(Spec::parse("galois129", false).is_err());
+
+        assert_eq!(
+            Spec::parse("x^16 + x^14 + x^13 + x^11 + 1", false),
+            Ok(galois16)
+        );
+        assert_eq!(Spec::parse("16,14,13,11", false), Ok(galois16));
+        assert_eq!(
+            Spec::parse("16, 14, 13, 11", true).unwrap().register,
+            Register::Fibonacci(FibonacciLfsr::new(16, &[16, 14, 13, 11], 1).unwrap())
+        );
+        assert!(Spec::parse("x^16 + x^14", false).is_err());
+        assert!(Spec::parse("16,14,14", false).is_err());
+        assert!(Spec::parse("fibonacci16", false).is_err());
+    }
+
+    #[test]
+    fn prbs_patterns() {
+        let prbs23 = Spec::parse("PRBS23", false).unwrap();
+        assert!(prbs23.inverted);
+        assert_eq!(prbs23.width(), 23);
+        let expected: Vec<bool> = Prbs23::default().take(1000).collect();
+        assert_eq!(prbs23.bits().take(1000).collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,563 @@
+//! Verilog and VHDL for the LFSRs that `galois_lfsr!`, `fibonacci_lfsr!` and their XNOR variants generate
+//!
+//! A [`Description`] is the argument list of those macros, [`Hdl`] adds the kind of LFSR, the state after
+//! reset and an optional terminal count, and emits a synthesizable module, a file of test vectors
+//! stepped with the same `up()` as the Rust type, and a testbench that checks the module against it:
+//!
+//! ```rust
+//! use lfsr_hdl::{Description, Feedback, Hdl, Kind};
+//!
+//! let description: Description = "Counter8, 8, 8, 6, 5, 4".parse().unwrap();
+//! let hdl = Hdl::new(description, Kind::Galois, Feedback::Xor).with_terminal_count(100);
+//!
+//! let verilog = hdl.verilog();
+//! assert!(verilog.contains("module counter8 ("));
+//! assert!(verilog.contains("assign next_state[5] = state[6] ^ state[0];"));
+//! assert!(hdl.vhdl().contains("entity counter8 is"));
+//!
+//! // the state after reset and the next 254 ones, as binary numbers
+//! let vectors = hdl.test_vectors(255);
+//! assert_eq!(vectors.lines().next(), Some("00000001"));
+//! assert!(hdl.verilog_testbench("counter8.vec", 255).contains("$readmemb(\"counter8.vec\", vectors);"));
+//! ```
+//!
+//! Named arguments are the same as in `galois_lfsr!` as well, `"Counter8, 8, koopman = 0xB8"` is the same
+//! description as the one above.
+
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use lfsr_base::arguments::{self, ArgumentError, Arguments};
+use lfsr_base::gf2::Poly;
+use lfsr_base::{FibonacciLfsr, GaloisLfsr, TapsError, LFSR};
+
+mod verilog;
+mod vhdl;
+
+/// Why a string isn't a description of an LFSR
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DescriptionError {
+    /// Not `Name, width, [sequence_length,] taps...` or `Name, width, [sequence_length,] notation = ...`
+    Syntax(String),
+    /// An argument after the name doesn't parse, as in the macros
+    Argument {
+        argument: String,
+        error: ArgumentError,
+    },
+    /// Width and taps don't describe an LFSR
+    Taps(TapsError),
+    /// The sequence length is given and it's not the period of the LFSR
+    SequenceLength { stated: u128, actual: u128 },
+}
+
+impl fmt::Display for DescriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DescriptionError::Syntax(message) => write!(f, "{}", message),
+            DescriptionError::Argument { argument, error } => {
+                write!(f, "`{}`: {}", argument, error)
+            }
+            DescriptionError::Taps(err) => write!(f, "{}", err),
+            DescriptionError::SequenceLength { stated, actual } => {
+                write!(f, "sequence length is {}, not {}", actual, stated)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DescriptionError {}
+
+impl From<TapsError> for DescriptionError {
+    fn from(err: TapsError) -> Self {
+        DescriptionError::Taps(err)
+    }
+}
+
+/// Name, width and 1-based taps, as passed to `galois_lfsr!`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Description {
+    pub name: String,
+    pub width: u32,
+    pub taps: Vec<u32>,
+}
+
+impl Description {
+    /// Checks the taps the same way `GaloisLfsr::new` does
+    pub fn new(name: &str, width: u32, taps: &[u32]) -> Result<Self, DescriptionError> {
+        GaloisLfsr::new(width, taps, 1)?;
+        Ok(Self {
+            name: name.to_string(),
+            width,
+            taps: taps.to_vec(),
+        })
+    }
+
+    /// Feedback polynomial `1 + x^tap + ...`
+    pub fn polynomial(&self) -> Poly {
+        Poly::from_taps(&self.taps)
+    }
+
+    /// The name in `snake_case`, for module, entity and file names: `MyLFSR32` is `my_lfsr32`
+    pub fn module_name(&self) -> String {
+        let chars: Vec<char> = self.name.chars().collect();
+        let mut ret = String::new();
+        for (ix, c) in chars.iter().enumerate() {
+            if c.is_uppercase() && ix > 0 {
+                let prev = chars[ix - 1];
+                let next_lowercase = chars.get(ix + 1).is_some_and(|c| c.is_lowercase());
+                if prev.is_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_uppercase() && next_lowercase)
+                {
+                    ret.push('_');
+                }
+            }
+            ret.extend(c.to_lowercase());
+        }
+        ret
+    }
+}
+
+/// `Name, width, [sequence_length,] taps...` or `Name, width, [sequence_length,] notation = ...`,
+/// the arguments of `galois_lfsr!`, a suffix on the width is ignored
+impl FromStr for Description {
+    type Err = DescriptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let arguments: Vec<&str> = arguments::split(s).collect();
+        if arguments.len() < 3 {
+            return Err(DescriptionError::Syntax(
+                "expected `Name, width, [sequence_length,] taps...`".to_string(),
+            ));
+        }
+        let name = arguments[0];
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(DescriptionError::Syntax(format!("`{}` isn't a name", name)));
+        }
+        let parsed =
+            Arguments::parse(&arguments[1..]).map_err(|error| DescriptionError::Argument {
+                argument: arguments[1 + error.argument].to_string(),
+                error,
+            })?;
+
+        let description = Description::new(name, parsed.width, parsed.taps())?;
+        if let Some(stated) = parsed.sequence_length {
+            let actual = GaloisLfsr::new(parsed.width, parsed.taps(), 1)?.sequence_length();
+            if stated != actual {
+                return Err(DescriptionError::SequenceLength { stated, actual });
+            }
+        }
+        Ok(description)
+    }
+}
+
+/// Where the feedback goes, as in `galois_lfsr!` and `fibonacci_lfsr!`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Kind {
+    Galois,
+    Fibonacci,
+}
+
+/// XOR or XNOR feedback, the XNOR LFSR steps through the inverted states of the XOR one
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Feedback {
+    Xor,
+    Xnor,
+}
+
+impl Feedback {
+    /// The state `Default` gives in the macros: `1` for XOR and `0` for XNOR
+    pub fn default_state(self) -> u128 {
+        match self {
+            Feedback::Xor => 1,
+            Feedback::Xnor => 0,
+        }
+    }
+
+    fn xor_equivalent(self, state: u128, width: u32) -> u128 {
+        match self {
+            Feedback::Xor => state,
+            Feedback::Xnor => !state & (u128::MAX >> (128 - width)),
+        }
+    }
+}
+
+/// Bit of the next state, XOR of `sources` bits of the current state, inverted if `invert`
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct NextBit {
+    pub sources: Vec<u32>,
+    pub invert: bool,
+}
+
+/// An LFSR to emit as Verilog or VHDL
+///
+/// The module has `clk`, synchronous active high `rst` that loads `seed` and `en` inputs,
+/// `state`, its least significant bit `serial` and, with a terminal count, `tc` outputs.
+/// `tc` is high while the state is the one `terminal_count` steps after `seed`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hdl {
+    pub description: Description,
+    pub kind: Kind,
+    pub feedback: Feedback,
+    /// State after reset, [`Feedback::default_state`] unless set
+    pub seed: u128,
+    pub terminal_count: Option<u128>,
+}
+
+impl Hdl {
+    pub fn new(description: Description, kind: Kind, feedback: Feedback) -> Self {
+        Self {
+            description,
+            kind,
+            feedback,
+            seed: feedback.default_state(),
+            terminal_count: None,
+        }
+    }
+
+    /// Bits beyond the width are dropped
+    pub fn with_seed(self, seed: u128) -> Self {
+        let seed = seed & self.state_mask();
+        Self { seed, ..self }
+    }
+
+    pub fn with_terminal_count(self, steps: u128) -> Self {
+        Self {
+            terminal_count: Some(steps),
+            ..self
+        }
+    }
+
+    fn state_mask(&self) -> u128 {
+        u128::MAX >> (128 - self.description.width)
+    }
+
+    /// The macro that generates the same LFSR in Rust: `galois_lfsr!(Name, 8, 8, 6, 5, 4)`
+    pub fn macro_invocation(&self) -> String {
+        let name = match (self.kind, self.feedback) {
+            (Kind::Galois, Feedback::Xor) => "galois_lfsr",
+            (Kind::Galois, Feedback::Xnor) => "galois_xnor_lfsr",
+            (Kind::Fibonacci, Feedback::Xor) => "fibonacci_lfsr",
+            (Kind::Fibonacci, Feedback::Xnor) => "fibonacci_xnor_lfsr",
+        };
+        let taps: Vec<String> = self
+            .description
+            .taps
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        format!(
+            "{}!({}, {}, {})",
+            name,
+            self.description.name,
+            self.description.width,
+            taps.join(", ")
+        )
+    }
+
+    /// The XOR LFSR in the XOR equivalent of `state`
+    fn xor_lfsr(&self, state: u128) -> Box<dyn LFSR<State = u128>> {
+        let Description { width, taps, .. } = &self.description;
+        let state = self.feedback.xor_equivalent(state, *width);
+        match self.kind {
+            Kind::Galois => Box::new(GaloisLfsr::new(*width, taps, state).unwrap()),
+            Kind::Fibonacci => Box::new(FibonacciLfsr::new(*width, taps, state).unwrap()),
+        }
+    }
+
+    /// The state after `state`, same as `up()` of the generated LFSR
+    pub fn up(&self, state: u128) -> u128 {
+        let mut lfsr = self.xor_lfsr(state);
+        lfsr.inc();
+        self.feedback
+            .xor_equivalent(lfsr.get_state(), self.description.width)
+    }
+
+    /// Period of the sequence from `seed`
+    pub fn sequence_length(&self) -> u128 {
+        self.xor_lfsr(self.seed).sequence_length()
+    }
+
+    /// The state `tc` decodes, `terminal_count` steps after `seed`
+    pub fn terminal_state(&self) -> Option<u128> {
+        self.terminal_count.map(|steps| {
+            let mut lfsr = self.xor_lfsr(self.seed);
+            lfsr.advance(steps);
+            self.feedback
+                .xor_equivalent(lfsr.get_state(), self.description.width)
+        })
+    }
+
+    /// Next state bits from the least significant one
+    pub(crate) fn next_state(&self) -> Vec<NextBit> {
+        let Description { width, taps, .. } = &self.description;
+        let xnor = self.feedback == Feedback::Xnor;
+        (0..*width)
+            .map(|bit| match self.kind {
+                // `up` shifts right and XORs the taps with the bit that's shifted out,
+                // with XNOR feedback the double inversion of that bit cancels out
+                Kind::Galois if bit == width - 1 => NextBit {
+                    sources: vec![0],
+                    invert: false,
+                },
+                Kind::Galois if taps.contains(&(bit + 1)) => NextBit {
+                    sources: vec![bit + 1, 0],
+                    invert: xnor,
+                },
+                // XNOR of the inverted tapped bits is their XNOR or XOR depending on how many there are
+                Kind::Fibonacci if bit == width - 1 => NextBit {
+                    sources: taps.iter().map(|tap| width - tap).collect(),
+                    invert: xnor && taps.len() % 2 == 0,
+                },
+                _ => NextBit {
+                    sources: vec![bit + 1],
+                    invert: false,
+                },
+            })
+            .collect()
+    }
+
+    fn header_comment(&self) -> Vec<String> {
+        let kind = match self.kind {
+            Kind::Galois => "Galois",
+            Kind::Fibonacci => "Fibonacci",
+        };
+        let feedback = match self.feedback {
+            Feedback::Xor => "XOR",
+            Feedback::Xnor => "XNOR",
+        };
+        let mut ret = vec![
+            format!(
+                "{}-bit {} LFSR with {} feedback, {}",
+                self.description.width,
+                kind,
+                feedback,
+                self.description.polynomial()
+            ),
+            format!(
+                "Steps through the same states as `up()` of {}",
+                self.macro_invocation()
+            ),
+            format!(
+                "Sequence length from the seed is {}",
+                self.sequence_length()
+            ),
+        ];
+        if let Some(steps) = self.terminal_count {
+            ret.push(format!("tc is high {} steps after reset", steps));
+        }
+        ret
+    }
+
+    /// `count` states starting from `seed`, one binary number of `width` digits per line,
+    /// for `$readmemb` and `std.textio`
+    pub fn test_vectors(&self, count: usize) -> String {
+        let width = self.description.width as usize;
+        let mut ret = String::with_capacity(count * (width + 1));
+        let mut lfsr = self.xor_lfsr(self.seed);
+        for _ in 0..count {
+            let state = self
+                .feedback
+                .xor_equivalent(lfsr.get_state(), self.description.width);
+            writeln!(ret, "{:0width$b}", state, width = width).unwrap();
+            lfsr.inc();
+        }
+        ret
+    }
+
+    /// Synthesizable Verilog 2001 module
+    pub fn verilog(&self) -> String {
+        verilog::module(self)
+    }
+
+    /// Verilog testbench that checks `count` states of the module against `vectors_path`
+    pub fn verilog_testbench(&self, vectors_path: &str, count: usize) -> String {
+        verilog::testbench(self, vectors_path, count)
+    }
+
+    /// Synthesizable VHDL-93 entity and architecture
+    pub fn vhdl(&self) -> String {
+        vhdl::module(self)
+    }
+
+    /// VHDL testbench that checks the states of the module against all the lines in `vectors_path`
+    pub fn vhdl_testbench(&self, vectors_path: &str) -> String {
+        vhdl::testbench(self, vectors_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lfsr_base::arguments::ArgumentErrorKind;
+
+    use lfsr::{fibonacci_lfsr, fibonacci_xnor_lfsr, galois_lfsr, galois_xnor_lfsr};
+
+    galois_lfsr!(Galois5, 5, 5, 3);
+    galois_xnor_lfsr!(GaloisXnor5, 5, 5, 3);
+    fibonacci_lfsr!(Fibonacci8, 8, 8, 6, 5, 4);
+    fibonacci_xnor_lfsr!(FibonacciXnor8, 8, 8, 6, 5, 4);
+    fibonacci_xnor_lfsr!(FibonacciXnor7, 7, 7, 6, 5);
+
+    /// Steps the netlist of `next_state` and compares it with the generated LFSR's states
+    fn check_netlist<L: LFSR>(mut lfsr: L, taps: &[u32], kind: Kind, feedback: Feedback)
+    where
+        L::State: Into<u128>,
+    {
+        let description = Description::new("Netlist", lfsr.width(), taps).unwrap();
+        let hdl = Hdl::new(description, kind, feedback);
+        let netlist = hdl.next_state();
+        let vectors = hdl.test_vectors(300);
+        let mut state = hdl.seed;
+        for line in vectors.lines() {
+            assert_eq!(
+                u128::from_str_radix(line, 2).unwrap(),
+                lfsr.get_state().into()
+            );
+            assert_eq!(state, lfsr.get_state().into());
+            let next = netlist.iter().enumerate().fold(0, |next, (bit, next_bit)| {
+                let value = next_bit
+                    .sources
+                    .iter()
+                    .fold(next_bit.invert as u128, |value, source| {
+                        value ^ (state >> source) & 1
+                    });
+                next | (value << bit)
+            });
+            assert_eq!(next, hdl.up(state));
+            state = next;
+            lfsr.inc();
+        }
+    }
+
+    #[test]
+    fn netlists_step_like_generated_lfsrs() {
+        check_netlist(Galois5::default(), &[5, 3], Kind::Galois, Feedback::Xor);
+        check_netlist(
+            GaloisXnor5::default(),
+            &[5, 3],
+            Kind::Galois,
+            Feedback::Xnor,
+        );
+        check_netlist(
+            Fibonacci8::default(),
+            &[8, 6, 5, 4],
+            Kind::Fibonacci,
+            Feedback::Xor,
+        );
+        check_netlist(
+            FibonacciXnor8::default(),
+            &[8, 6, 5, 4],
+            Kind::Fibonacci,
+            Feedback::Xnor,
+        );
+        check_netlist(
+            FibonacciXnor7::default(),
+            &[7, 6, 5],
+            Kind::Fibonacci,
+            Feedback::Xnor,
+        );
+    }
+
+    #[test]
+    fn parsing_descriptions() {
+        let expected = Description::new("Counter8", 8, &[8, 6, 5, 4]).unwrap();
+        for s in [
+            "Counter8, 8, 8, 6, 5, 4",
+            "Counter8, 8u16, 255, 8, 6, 5, 4",
+            "Counter8, 8, taps = [8, 6, 5, 4]",
+            "Counter8, 8, 255, polynomial = x^8 + x^6 + x^5 + x^4 + 1",
+            "Counter8, 8, koopman = 0xB8",
+            "Counter8, 8, normal = 0x71",
+            "Counter8, 8, reversed = 0x8e",
+            "Counter8, 8, reciprocal = 0x1D,",
+        ]
+        .iter()
+        {
+            assert_eq!(s.parse::<Description>().as_ref(), Ok(&expected), "{}", s);
+        }
+
+        assert_eq!(
+            "A, 4, 15, 4, 2".parse::<Description>(),
+            Err(DescriptionError::SequenceLength {
+                stated: 15,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            "A, 4, 3".parse::<Description>(),
+            Err(DescriptionError::Taps(TapsError::NoTapAtWidth))
+        );
+        let argument_error = |s: &str| match s.parse::<Description>() {
+            Err(DescriptionError::Argument { argument, error }) => (argument, error.kind),
+            other => panic!("{}: {:?}", s, other),
+        };
+        assert_eq!(
+            argument_error("A, 8, koopman = 0x38"),
+            (
+                "koopman = 0x38".to_string(),
+                ArgumentErrorKind::NotFeedbackPolynomial("x^6 + x^5 + x^4 + 1".parse().unwrap())
+            )
+        );
+        assert_eq!(
+            argument_error("A, 8, octal = 0x38").1,
+            ArgumentErrorKind::UnknownNotation
+        );
+        assert_eq!(
+            argument_error("A, 8, 1, 2, koopman = 0xB8"),
+            ("2".to_string(), ArgumentErrorKind::TapsAlreadyNamed)
+        );
+        // values beyond `u32` aren't truncated into taps or widths that happen to fit
+        assert_eq!(
+            argument_error("A, 8, 8, 4294967300"),
+            ("4294967300".to_string(), ArgumentErrorKind::TooLarge)
+        );
+        assert_eq!(
+            argument_error("A, 4294967304, 8, 6, 5, 4"),
+            ("4294967304".to_string(), ArgumentErrorKind::TooLarge)
+        );
+        // the first number is a sequence length unless it's the width, as in the macros
+        assert_eq!(
+            "A, 8, 4, 5, 6, 8".parse::<Description>(),
+            Err(DescriptionError::SequenceLength {
+                stated: 4,
+                actual: 93
+            })
+        );
+        assert!(matches!(
+            "A-1, 8, 8, 6, 5, 4".parse::<Description>(),
+            Err(DescriptionError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn module_names() {
+        let name = |name| Description::new(name, 4, &[4, 3]).unwrap().module_name();
+        assert_eq!(name("Counter8"), "counter8");
+        assert_eq!(name("MyLFSR32"), "my_lfsr32");
+        assert_eq!(name("Prbs7Register"), "prbs7_register");
+        assert_eq!(name("LFSRCounter"), "lfsr_counter");
+        assert_eq!(name("counter"), "counter");
+    }
+
+    #[test]
+    fn terminal_count() {
+        let description = Description::new("Counter8", 8, &[8, 6, 5, 4]).unwrap();
+        let hdl = Hdl::new(description, Kind::Fibonacci, Feedback::Xnor).with_terminal_count(100);
+        let mut lfsr = FibonacciXnor8::default();
+        lfsr.advance(100);
+        assert_eq!(hdl.terminal_state(), Some(u128::from(lfsr.get_state())));
+        assert_eq!(hdl.sequence_length(), 255);
+        assert_eq!(
+            hdl.macro_invocation(),
+            "fibonacci_xnor_lfsr!(Counter8, 8, 8, 6, 5, 4)"
+        );
+
+        let hdl = hdl.with_seed(0x1ff).with_terminal_count(0);
+        assert_eq!(hdl.seed, 0xff);
+        // all ones is the lock-up state with XNOR feedback
+        assert_eq!(hdl.terminal_state(), Some(0xff));
+        assert_eq!(hdl.sequence_length(), 1);
+    }
+}
@@ -0,0 +1,214 @@
+//! Verilog 2001
+
+use std::fmt::Write;
+
+use crate::{Hdl, NextBit};
+
+fn expression(next_bit: &NextBit) -> String {
+    let sources: Vec<String> = next_bit
+        .sources
+        .iter()
+        .map(|source| format!("state[{}]", source))
+        .collect();
+    let xor = sources.join(" ^ ");
+    if next_bit.invert {
+        format!("~({})", xor)
+    } else {
+        xor
+    }
+}
+
+fn constant(width: u32, value: u128) -> String {
+    format!("{}'h{:x}", width, value)
+}
+
+pub(crate) fn module(hdl: &Hdl) -> String {
+    let width = hdl.description.width;
+    let name = hdl.description.module_name();
+    let terminal_state = hdl.terminal_state();
+    let mut ret = String::new();
+
+    for line in hdl.header_comment() {
+        writeln!(ret, "// {}", line).unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "module {} (", name).unwrap();
+    writeln!(ret, "    input  wire clk,").unwrap();
+    writeln!(ret, "    input  wire rst,").unwrap();
+    writeln!(ret, "    input  wire en,").unwrap();
+    writeln!(ret, "    output reg  [{}:0] state,", width - 1).unwrap();
+    if terminal_state.is_some() {
+        writeln!(ret, "    output wire serial,").unwrap();
+        writeln!(ret, "    output wire tc").unwrap();
+    } else {
+        writeln!(ret, "    output wire serial").unwrap();
+    }
+    writeln!(ret, ");").unwrap();
+    writeln!(ret).unwrap();
+    writeln!(
+        ret,
+        "    localparam [{}:0] SEED = {};",
+        width - 1,
+        constant(width, hdl.seed)
+    )
+    .unwrap();
+    if let Some(terminal_state) = terminal_state {
+        writeln!(
+            ret,
+            "    localparam [{}:0] TERMINAL = {};",
+            width - 1,
+            constant(width, terminal_state)
+        )
+        .unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "    wire [{}:0] next_state;", width - 1).unwrap();
+    for (bit, next_bit) in hdl.next_state().iter().enumerate().rev() {
+        writeln!(
+            ret,
+            "    assign next_state[{}] = {};",
+            bit,
+            expression(next_bit)
+        )
+        .unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "    always @(posedge clk) begin").unwrap();
+    writeln!(ret, "        if (rst)").unwrap();
+    writeln!(ret, "            state <= SEED;").unwrap();
+    writeln!(ret, "        else if (en)").unwrap();
+    writeln!(ret, "            state <= next_state;").unwrap();
+    writeln!(ret, "    end").unwrap();
+    writeln!(ret).unwrap();
+    writeln!(ret, "    assign serial = state[0];").unwrap();
+    if terminal_state.is_some() {
+        writeln!(ret, "    assign tc = state == TERMINAL;").unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "endmodule").unwrap();
+    ret
+}
+
+pub(crate) fn testbench(hdl: &Hdl, vectors_path: &str, count: usize) -> String {
+    let width = hdl.description.width;
+    let name = hdl.description.module_name();
+    let tc = if hdl.terminal_count.is_some() {
+        ", .tc()"
+    } else {
+        ""
+    };
+
+    format!(
+        r#"// Checks {name} against {count} states of `up()` in {vectors_path}
+
+`timescale 1ns / 1ps
+
+module {name}_tb;
+
+    reg clk = 0;
+    reg rst = 1;
+    reg en = 1;
+    wire [{msb}:0] state;
+
+    reg [{msb}:0] vectors [0:{last}];
+    integer i;
+    integer errors = 0;
+
+    {name} dut (.clk(clk), .rst(rst), .en(en), .state(state), .serial(){tc});
+
+    always #5 clk = ~clk;
+
+    initial begin
+        $readmemb("{vectors_path}", vectors);
+        @(posedge clk);
+        #1 rst = 0;
+        for (i = 0; i < {count}; i = i + 1) begin
+            if (state !== vectors[i]) begin
+                $display("step %0d: state %b, expected %b", i, state, vectors[i]);
+                errors = errors + 1;
+            end
+            @(posedge clk);
+            #1;
+        end
+        $display("{name}: %0d states checked, %0d errors", {count}, errors);
+        $finish;
+    end
+
+endmodule
+"#,
+        name = name,
+        count = count,
+        vectors_path = vectors_path,
+        msb = width - 1,
+        last = count.saturating_sub(1),
+        tc = tc,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Description, Feedback, Hdl, Kind};
+
+    #[test]
+    fn galois_xnor_with_terminal_count() {
+        let description = Description::new("Counter4", 4, &[4, 3]).unwrap();
+        let hdl = Hdl::new(description, Kind::Galois, Feedback::Xnor).with_terminal_count(3);
+        assert_eq!(
+            hdl.verilog(),
+            "// 4-bit Galois LFSR with XNOR feedback, x^4 + x^3 + 1
+// Steps through the same states as `up()` of galois_xnor_lfsr!(Counter4, 4, 4, 3)
+// Sequence length from the seed is 15
+// tc is high 3 steps after reset
+
+module counter4 (
+    input  wire clk,
+    input  wire rst,
+    input  wire en,
+    output reg  [3:0] state,
+    output wire serial,
+    output wire tc
+);
+
+    localparam [3:0] SEED = 4'h0;
+    localparam [3:0] TERMINAL = 4'h7;
+
+    wire [3:0] next_state;
+    assign next_state[3] = state[0];
+    assign next_state[2] = ~(state[3] ^ state[0]);
+    assign next_state[1] = state[2];
+    assign next_state[0] = state[1];
+
+    always @(posedge clk) begin
+        if (rst)
+            state <= SEED;
+        else if (en)
+            state <= next_state;
+    end
+
+    assign serial = state[0];
+    assign tc = state == TERMINAL;
+
+endmodule
+"
+        );
+    }
+
+    #[test]
+    fn fibonacci_without_terminal_count() {
+        let description = Description::new("Fibonacci8", 8, &[8, 6, 5, 4]).unwrap();
+        let hdl = Hdl::new(description, Kind::Fibonacci, Feedback::Xor).with_seed(0xa5);
+        let verilog = hdl.verilog();
+        assert!(verilog.contains("    output wire serial\n);"));
+        assert!(verilog.contains("localparam [7:0] SEED = 8'ha5;"));
+        assert!(
+            verilog.contains("assign next_state[7] = state[0] ^ state[2] ^ state[3] ^ state[4];")
+        );
+        assert!(verilog.contains("assign next_state[6] = state[7];"));
+        assert!(!verilog.contains("tc"));
+
+        let testbench = hdl.verilog_testbench("fibonacci8.vec", 300);
+        assert!(testbench.contains("reg [7:0] vectors [0:299];"));
+        assert!(testbench
+            .contains("fibonacci8 dut (.clk(clk), .rst(rst), .en(en), .state(state), .serial());"));
+    }
+}
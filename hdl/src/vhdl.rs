@@ -0,0 +1,240 @@
+//! VHDL-93
+
+use std::fmt::Write;
+
+use crate::{Hdl, NextBit};
+
+fn expression(next_bit: &NextBit) -> String {
+    let sources: Vec<String> = next_bit
+        .sources
+        .iter()
+        .map(|source| format!("q({})", source))
+        .collect();
+    let xor = sources.join(" xor ");
+    if next_bit.invert {
+        format!("not ({})", xor)
+    } else {
+        xor
+    }
+}
+
+fn constant(width: u32, value: u128) -> String {
+    format!("\"{:0width$b}\"", value, width = width as usize)
+}
+
+pub(crate) fn module(hdl: &Hdl) -> String {
+    let width = hdl.description.width;
+    let name = hdl.description.module_name();
+    let terminal_state = hdl.terminal_state();
+    let vector = format!("std_logic_vector({} downto 0)", width - 1);
+    let mut ret = String::new();
+
+    for line in hdl.header_comment() {
+        writeln!(ret, "-- {}", line).unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "library ieee;").unwrap();
+    writeln!(ret, "use ieee.std_logic_1164.all;").unwrap();
+    writeln!(ret).unwrap();
+    writeln!(ret, "entity {} is", name).unwrap();
+    writeln!(ret, "    port (").unwrap();
+    writeln!(ret, "        clk    : in  std_logic;").unwrap();
+    writeln!(ret, "        rst    : in  std_logic;").unwrap();
+    writeln!(ret, "        en     : in  std_logic;").unwrap();
+    writeln!(ret, "        state  : out {};", vector).unwrap();
+    if terminal_state.is_some() {
+        writeln!(ret, "        serial : out std_logic;").unwrap();
+        writeln!(ret, "        tc     : out std_logic").unwrap();
+    } else {
+        writeln!(ret, "        serial : out std_logic").unwrap();
+    }
+    writeln!(ret, "    );").unwrap();
+    writeln!(ret, "end entity {};", name).unwrap();
+    writeln!(ret).unwrap();
+    writeln!(ret, "architecture rtl of {} is", name).unwrap();
+    writeln!(
+        ret,
+        "    constant SEED : {} := {};",
+        vector,
+        constant(width, hdl.seed)
+    )
+    .unwrap();
+    if let Some(terminal_state) = terminal_state {
+        writeln!(
+            ret,
+            "    constant TERMINAL : {} := {};",
+            vector,
+            constant(width, terminal_state)
+        )
+        .unwrap();
+    }
+    writeln!(ret, "    signal q : {};", vector).unwrap();
+    writeln!(ret, "    signal q_next : {};", vector).unwrap();
+    writeln!(ret, "begin").unwrap();
+    for (bit, next_bit) in hdl.next_state().iter().enumerate().rev() {
+        writeln!(ret, "    q_next({}) <= {};", bit, expression(next_bit)).unwrap();
+    }
+    writeln!(ret).unwrap();
+    writeln!(ret, "    process (clk)").unwrap();
+    writeln!(ret, "    begin").unwrap();
+    writeln!(ret, "        if rising_edge(clk) then").unwrap();
+    writeln!(ret, "            if rst = '1' then").unwrap();
+    writeln!(ret, "                q <= SEED;").unwrap();
+    writeln!(ret, "            elsif en = '1' then").unwrap();
+    writeln!(ret, "                q <= q_next;").unwrap();
+    writeln!(ret, "            end if;").unwrap();
+    writeln!(ret, "        end if;").unwrap();
+    writeln!(ret, "    end process;").unwrap();
+    writeln!(ret).unwrap();
+    writeln!(ret, "    state <= q;").unwrap();
+    writeln!(ret, "    serial <= q(0);").unwrap();
+    if terminal_state.is_some() {
+        writeln!(ret, "    tc <= '1' when q = TERMINAL else '0';").unwrap();
+    }
+    writeln!(ret, "end architecture rtl;").unwrap();
+    ret
+}
+
+pub(crate) fn testbench(hdl: &Hdl, vectors_path: &str) -> String {
+    let width = hdl.description.width;
+    let name = hdl.description.module_name();
+    let (tc_signal, tc_port) = if hdl.terminal_count.is_some() {
+        ("    signal tc : std_logic;\n", ", tc => tc")
+    } else {
+        ("", "")
+    };
+
+    format!(
+        r#"-- Checks {name} against the states of `up()` in {vectors_path}
+
+library ieee;
+use ieee.std_logic_1164.all;
+use std.textio.all;
+
+entity {name}_tb is
+end entity {name}_tb;
+
+architecture sim of {name}_tb is
+    signal clk : std_logic := '0';
+    signal rst : std_logic := '1';
+    signal en : std_logic := '1';
+    signal state : std_logic_vector({msb} downto 0);
+    signal serial : std_logic;
+{tc_signal}begin
+    dut : entity work.{name}
+        port map (clk => clk, rst => rst, en => en, state => state, serial => serial{tc_port});
+
+    process
+        file vectors : text open read_mode is "{vectors_path}";
+        variable l : line;
+        variable expected : bit_vector({msb} downto 0);
+        variable step : natural := 0;
+        variable errors : natural := 0;
+    begin
+        clk <= '1';
+        wait for 5 ns;
+        rst <= '0';
+        while not endfile(vectors) loop
+            readline(vectors, l);
+            read(l, expected);
+            clk <= '0';
+            wait for 5 ns;
+            if state /= to_stdlogicvector(expected) then
+                report "step " & integer'image(step) & ": unexpected state" severity error;
+                errors := errors + 1;
+            end if;
+            clk <= '1';
+            wait for 5 ns;
+            step := step + 1;
+        end loop;
+        report "{name}: " & integer'image(step) & " states checked, "
+            & integer'image(errors) & " errors" severity note;
+        wait;
+    end process;
+end architecture sim;
+"#,
+        name = name,
+        vectors_path = vectors_path,
+        msb = width - 1,
+        tc_signal = tc_signal,
+        tc_port = tc_port,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Description, Feedback, Hdl, Kind};
+
+    #[test]
+    fn fibonacci_xnor_with_terminal_count() {
+        let description = Description::new("Counter4", 4, &[4, 3]).unwrap();
+        let hdl = Hdl::new(description, Kind::Fibonacci, Feedback::Xnor).with_terminal_count(3);
+        assert_eq!(
+            hdl.vhdl(),
+            "-- 4-bit Fibonacci LFSR with XNOR feedback, x^4 + x^3 + 1
+-- Steps through the same states as `up()` of fibonacci_xnor_lfsr!(Counter4, 4, 4, 3)
+-- Sequence length from the seed is 15
+-- tc is high 3 steps after reset
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity counter4 is
+    port (
+        clk    : in  std_logic;
+        rst    : in  std_logic;
+        en     : in  std_logic;
+        state  : out std_logic_vector(3 downto 0);
+        serial : out std_logic;
+        tc     : out std_logic
+    );
+end entity counter4;
+
+architecture rtl of counter4 is
+    constant SEED : std_logic_vector(3 downto 0) := \"0000\";
+    constant TERMINAL : std_logic_vector(3 downto 0) := \"1110\";
+    signal q : std_logic_vector(3 downto 0);
+    signal q_next : std_logic_vector(3 downto 0);
+begin
+    q_next(3) <= not (q(0) xor q(1));
+    q_next(2) <= q(3);
+    q_next(1) <= q(2);
+    q_next(0) <= q(1);
+
+    process (clk)
+    begin
+        if rising_edge(clk) then
+            if rst = '1' then
+                q <= SEED;
+            elsif en = '1' then
+                q <= q_next;
+            end if;
+        end if;
+    end process;
+
+    state <= q;
+    serial <= q(0);
+    tc <= '1' when q = TERMINAL else '0';
+end architecture rtl;
+"
+        );
+    }
+
+    #[test]
+    fn galois_without_terminal_count() {
+        let description = Description::new("Galois8", 8, &[8, 6, 5, 4]).unwrap();
+        let hdl = Hdl::new(description, Kind::Galois, Feedback::Xor);
+        let vhdl = hdl.vhdl();
+        assert!(vhdl.contains("        serial : out std_logic\n    );"));
+        assert!(vhdl.contains("constant SEED : std_logic_vector(7 downto 0) := \"00000001\";"));
+        assert!(vhdl.contains("q_next(5) <= q(6) xor q(0);"));
+        assert!(!vhdl.contains("tc"));
+
+        let testbench = hdl.vhdl_testbench("galois8.vec");
+        assert!(testbench.contains("dut : entity work.galois8"));
+        assert!(testbench.contains(
+            "port map (clk => clk, rst => rst, en => en, state => state, serial => serial);"
+        ));
+        assert!(testbench.contains("file vectors : text open read_mode is \"galois8.vec\";"));
+    }
+}
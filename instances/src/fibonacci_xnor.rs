@@ -0,0 +1,140 @@
+//!
+//! Maximum sequence length [Fibonacci LFSRs](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Fibonacci_LFSRs)
+//! with XNOR feedback, up to 128 bits wide.
+//!
+//! Taps up to 32 bits taken from [XAPP052](https://docs.xilinx.com/v/u/en-US/xapp052) by Peter Alfke,
+//! so the states match the shift registers described there, wider ones are the same as in [`galois`](../galois/index.html).
+//! All of them are checked to be primitive at compile time.
+//!
+//! The default state is `0`, all ones is the lock-up state.
+//!
+
+use lfsr_macro_generate::fibonacci_xnor_lfsr;
+
+fibonacci_xnor_lfsr!(FibonacciXnor128, 128, 128, 126, 101, 99);
+fibonacci_xnor_lfsr!(FibonacciXnor127, 127, 127, 126);
+fibonacci_xnor_lfsr!(FibonacciXnor126, 126, 126, 125, 90, 89);
+fibonacci_xnor_lfsr!(FibonacciXnor125, 125, 125, 124, 18, 17);
+fibonacci_xnor_lfsr!(FibonacciXnor124, 124, 124, 87);
+fibonacci_xnor_lfsr!(FibonacciXnor123, 123, 123, 121);
+fibonacci_xnor_lfsr!(FibonacciXnor122, 122, 122, 121, 63, 62);
+fibonacci_xnor_lfsr!(FibonacciXnor121, 121, 121, 103);
+fibonacci_xnor_lfsr!(FibonacciXnor120, 120, 120, 113, 9, 2);
+fibonacci_xnor_lfsr!(FibonacciXnor119, 119, 119, 111);
+fibonacci_xnor_lfsr!(FibonacciXnor118, 118, 118, 85);
+fibonacci_xnor_lfsr!(FibonacciXnor117, 117, 117, 115, 99, 97);
+fibonacci_xnor_lfsr!(FibonacciXnor116, 116, 116, 115, 46, 45);
+fibonacci_xnor_lfsr!(FibonacciXnor115, 115, 115, 114, 101, 100);
+fibonacci_xnor_lfsr!(FibonacciXnor114, 114, 114, 113, 33, 32);
+fibonacci_xnor_lfsr!(FibonacciXnor113, 113, 113, 104);
+fibonacci_xnor_lfsr!(FibonacciXnor112, 112, 112, 110, 69, 67);
+fibonacci_xnor_lfsr!(FibonacciXnor111, 111, 111, 101);
+fibonacci_xnor_lfsr!(FibonacciXnor110, 110, 110, 109, 98, 97);
+fibonacci_xnor_lfsr!(FibonacciXnor109, 109, 109, 108, 103, 102);
+fibonacci_xnor_lfsr!(FibonacciXnor108, 108, 108, 77);
+fibonacci_xnor_lfsr!(FibonacciXnor107, 107, 107, 105, 44, 42);
+fibonacci_xnor_lfsr!(FibonacciXnor106, 106, 106, 91);
+fibonacci_xnor_lfsr!(FibonacciXnor105, 105, 105, 89);
+fibonacci_xnor_lfsr!(FibonacciXnor104, 104, 104, 103, 94, 93);
+fibonacci_xnor_lfsr!(FibonacciXnor103, 103, 103, 94);
+fibonacci_xnor_lfsr!(FibonacciXnor102, 102, 102, 101, 26, 25);
+fibonacci_xnor_lfsr!(FibonacciXnor101, 101, 101, 100, 95, 94);
+fibonacci_xnor_lfsr!(FibonacciXnor100, 100, 100, 63);
+fibonacci_xnor_lfsr!(FibonacciXnor99, 99, 99, 97, 54, 52);
+fibonacci_xnor_lfsr!(FibonacciXnor98, 98, 98, 87);
+fibonacci_xnor_lfsr!(FibonacciXnor97, 97, 97, 91);
+fibonacci_xnor_lfsr!(FibonacciXnor96, 96, 96, 94, 49, 47);
+fibonacci_xnor_lfsr!(FibonacciXnor95, 95, 95, 84);
+fibonacci_xnor_lfsr!(FibonacciXnor94, 94, 94, 73);
+fibonacci_xnor_lfsr!(FibonacciXnor93, 93, 93, 91);
+fibonacci_xnor_lfsr!(FibonacciXnor92, 92, 92, 91, 80, 79);
+fibonacci_xnor_lfsr!(FibonacciXnor91, 91, 91, 90, 8, 7);
+fibonacci_xnor_lfsr!(FibonacciXnor90, 90, 90, 89, 72, 71);
+fibonacci_xnor_lfsr!(FibonacciXnor89, 89, 89, 51);
+fibonacci_xnor_lfsr!(FibonacciXnor88, 88, 88, 87, 17, 16);
+fibonacci_xnor_lfsr!(FibonacciXnor87, 87, 87, 74);
+fibonacci_xnor_lfsr!(FibonacciXnor86, 86, 86, 85, 74, 73);
+fibonacci_xnor_lfsr!(FibonacciXnor85, 85, 85, 84, 58, 57);
+fibonacci_xnor_lfsr!(FibonacciXnor84, 84, 84, 71);
+fibonacci_xnor_lfsr!(FibonacciXnor83, 83, 83, 82, 38, 37);
+fibonacci_xnor_lfsr!(FibonacciXnor82, 82, 82, 79, 47, 44);
+fibonacci_xnor_lfsr!(FibonacciXnor81, 81, 81, 77);
+fibonacci_xnor_lfsr!(FibonacciXnor80, 80, 80, 79, 43, 42);
+fibonacci_xnor_lfsr!(FibonacciXnor79, 79, 79, 70);
+fibonacci_xnor_lfsr!(FibonacciXnor78, 78, 78, 77, 59, 58);
+fibonacci_xnor_lfsr!(FibonacciXnor77, 77, 77, 76, 47, 46);
+fibonacci_xnor_lfsr!(FibonacciXnor76, 76, 76, 75, 41, 40);
+fibonacci_xnor_lfsr!(FibonacciXnor75, 75, 75, 74, 65, 64);
+fibonacci_xnor_lfsr!(FibonacciXnor74, 74, 74, 73, 59, 58);
+fibonacci_xnor_lfsr!(FibonacciXnor73, 73, 73, 48);
+fibonacci_xnor_lfsr!(FibonacciXnor72, 72, 72, 66, 25, 19);
+fibonacci_xnor_lfsr!(FibonacciXnor71, 71, 71, 65);
+fibonacci_xnor_lfsr!(FibonacciXnor70, 70, 70, 69, 55, 54);
+fibonacci_xnor_lfsr!(FibonacciXnor69, 69, 69, 67, 42, 40);
+fibonacci_xnor_lfsr!(FibonacciXnor68, 68, 68, 59);
+fibonacci_xnor_lfsr!(FibonacciXnor67, 67, 67, 66, 58, 57);
+fibonacci_xnor_lfsr!(FibonacciXnor66, 66, 66, 65, 57, 56);
+fibonacci_xnor_lfsr!(FibonacciXnor65, 65, 65, 47);
+fibonacci_xnor_lfsr!(FibonacciXnor64, 64, 64, 63, 61, 60);
+fibonacci_xnor_lfsr!(FibonacciXnor63, 63, 63, 62);
+fibonacci_xnor_lfsr!(FibonacciXnor62, 62, 62, 61, 6, 5);
+fibonacci_xnor_lfsr!(FibonacciXnor61, 61, 61, 60, 46, 45);
+fibonacci_xnor_lfsr!(FibonacciXnor60, 60, 60, 59);
+fibonacci_xnor_lfsr!(FibonacciXnor59, 59, 59, 58, 38, 37);
+fibonacci_xnor_lfsr!(FibonacciXnor58, 58, 58, 39);
+fibonacci_xnor_lfsr!(FibonacciXnor57, 57, 57, 50);
+fibonacci_xnor_lfsr!(FibonacciXnor56, 56, 56, 55, 35, 34);
+fibonacci_xnor_lfsr!(FibonacciXnor55, 55, 55, 31);
+fibonacci_xnor_lfsr!(FibonacciXnor54, 54, 54, 53, 18, 17);
+fibonacci_xnor_lfsr!(FibonacciXnor53, 53, 53, 52, 38, 37);
+fibonacci_xnor_lfsr!(FibonacciXnor52, 52, 52, 49);
+fibonacci_xnor_lfsr!(FibonacciXnor51, 51, 51, 50, 36, 35);
+fibonacci_xnor_lfsr!(FibonacciXnor50, 50, 50, 49, 24, 23);
+fibonacci_xnor_lfsr!(FibonacciXnor49, 49, 49, 40);
+fibonacci_xnor_lfsr!(FibonacciXnor48, 48, 48, 47, 21, 20);
+fibonacci_xnor_lfsr!(FibonacciXnor47, 47, 47, 42);
+fibonacci_xnor_lfsr!(FibonacciXnor46, 46, 46, 45, 26, 25);
+fibonacci_xnor_lfsr!(FibonacciXnor45, 45, 45, 44, 42, 41);
+fibonacci_xnor_lfsr!(FibonacciXnor44, 44, 44, 43, 18, 17);
+fibonacci_xnor_lfsr!(FibonacciXnor43, 43, 43, 42, 38, 37);
+fibonacci_xnor_lfsr!(FibonacciXnor42, 42, 42, 41, 20, 19);
+fibonacci_xnor_lfsr!(FibonacciXnor41, 41, 41, 38);
+fibonacci_xnor_lfsr!(FibonacciXnor40, 40, 40, 38, 21, 19);
+fibonacci_xnor_lfsr!(FibonacciXnor39, 39, 39, 35);
+fibonacci_xnor_lfsr!(FibonacciXnor38, 38, 38, 6, 5, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor37, 37, 37, 5, 4, 3, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor36, 36, 36, 25);
+fibonacci_xnor_lfsr!(FibonacciXnor35, 35, 35, 33);
+fibonacci_xnor_lfsr!(FibonacciXnor34, 34, 34, 27, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor33, 33, 33, 20);
+fibonacci_xnor_lfsr!(FibonacciXnor32, 32, 32, 22, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor31, 31, 31, 28);
+fibonacci_xnor_lfsr!(FibonacciXnor30, 30, 30, 6, 4, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor29, 29, 29, 27);
+fibonacci_xnor_lfsr!(FibonacciXnor28, 28, 28, 25);
+fibonacci_xnor_lfsr!(FibonacciXnor27, 27, 27, 5, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor26, 26, 26, 6, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor25, 25, 25, 22);
+fibonacci_xnor_lfsr!(FibonacciXnor24, 24, 24, 23, 22, 17);
+fibonacci_xnor_lfsr!(FibonacciXnor23, 23, 23, 18);
+fibonacci_xnor_lfsr!(FibonacciXnor22, 22, 22, 21);
+fibonacci_xnor_lfsr!(FibonacciXnor21, 21, 21, 19);
+fibonacci_xnor_lfsr!(FibonacciXnor20, 20, 20, 17);
+fibonacci_xnor_lfsr!(FibonacciXnor19, 19, 19, 6, 2, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor18, 18, 18, 11);
+fibonacci_xnor_lfsr!(FibonacciXnor17, 17, 17, 14);
+fibonacci_xnor_lfsr!(FibonacciXnor16, 16, 16, 15, 13, 4);
+fibonacci_xnor_lfsr!(FibonacciXnor15, 15, 15, 14);
+fibonacci_xnor_lfsr!(FibonacciXnor14, 14, 14, 5, 3, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor13, 13, 13, 4, 3, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor12, 12, 12, 6, 4, 1);
+fibonacci_xnor_lfsr!(FibonacciXnor11, 11, 11, 9);
+fibonacci_xnor_lfsr!(FibonacciXnor10, 10, 10, 7);
+fibonacci_xnor_lfsr!(FibonacciXnor9, 9, 9, 5);
+fibonacci_xnor_lfsr!(FibonacciXnor8, 8, 8, 6, 5, 4);
+fibonacci_xnor_lfsr!(FibonacciXnor7, 7, 7, 6);
+fibonacci_xnor_lfsr!(FibonacciXnor6, 6, 6, 5);
+fibonacci_xnor_lfsr!(FibonacciXnor5, 5, 5, 3);
+fibonacci_xnor_lfsr!(FibonacciXnor4, 4, 4, 3);
+fibonacci_xnor_lfsr!(FibonacciXnor3, 3, 3, 2);
+fibonacci_xnor_lfsr!(FibonacciXnor2, 2, 2, 1);
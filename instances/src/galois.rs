@@ -1,40 +1,139 @@
 //!
-//! Maximum sequence length [Galois LFSRs](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Galois_LFSRs), up to 32 bits wide.
+//! Maximum sequence length [Galois LFSRs](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Galois_LFSRs), up to 128 bits wide.
 //!
-//! Taps taken from the [Table of Linear Feedback Shift Registers](http://courses.cse.tamu.edu/walker/csce680/lfsr_table.pdf) by Roy Ward, Tim Molteno
+//! Taps up to 32 bits taken from the [Table of Linear Feedback Shift Registers](http://courses.cse.tamu.edu/walker/csce680/lfsr_table.pdf) by Roy Ward, Tim Molteno,
+//! wider ones mostly follow [XAPP052](https://docs.xilinx.com/v/u/en-US/xapp052) by Peter Alfke.
+//! All of them are checked to be primitive at compile time.
+//!
+//! State is stored in the smallest unsigned integer type that fits the width.
 //!
 
-use lfsr_base::LFSR;
 use lfsr_macro_generate::galois_lfsr;
 
-galois_lfsr!(Galois32, 32, 4294967295, 32, 30, 26, 25);
-galois_lfsr!(Galois31, 31, 2147483647, 31, 28);
-galois_lfsr!(Galois30, 30, 1073741823, 30, 29, 26, 24);
-galois_lfsr!(Galois29, 29, 536870911, 29, 27);
-galois_lfsr!(Galois28, 28, 268435455, 28, 25);
-galois_lfsr!(Galois27, 27, 134217727, 27, 26, 25, 22);
-galois_lfsr!(Galois26, 26, 67108863, 26, 25, 24, 20);
-galois_lfsr!(Galois25, 25, 33554431, 25, 22);
-galois_lfsr!(Galois24, 24, 16777215, 24, 23, 21, 20);
-galois_lfsr!(Galois23, 23, 8388607, 23, 18);
-galois_lfsr!(Galois22, 22, 4194303, 22, 21);
-galois_lfsr!(Galois21, 21, 2097151, 21, 19);
-galois_lfsr!(Galois20, 20, 1048575, 20, 17);
-galois_lfsr!(Galois19, 19, 524287, 19, 18, 17, 14);
-galois_lfsr!(Galois18, 18, 262143, 18, 11);
-galois_lfsr!(Galois17, 17, 131071, 17, 14);
-galois_lfsr!(Galois16, 16, 65535, 16, 14, 13, 11);
-galois_lfsr!(Galois15, 15, 32767, 15, 14);
-galois_lfsr!(Galois14, 14, 16383, 14, 13, 11, 9);
-galois_lfsr!(Galois13, 13, 8191, 13, 12, 10, 9);
-galois_lfsr!(Galois12, 12, 4095, 12, 11, 8, 6);
-galois_lfsr!(Galois11, 11, 2047, 11, 9);
-galois_lfsr!(Galois10, 10, 1023, 10, 7);
-galois_lfsr!(Galois9, 9, 511, 9, 5);
-galois_lfsr!(Galois8, 8, 255, 8, 6, 5, 4);
-galois_lfsr!(Galois7, 7, 127, 7, 6);
-galois_lfsr!(Galois6, 6, 63, 6, 5);
-galois_lfsr!(Galois5, 5, 31, 5, 3);
-galois_lfsr!(Galois4, 4, 15, 4, 3);
-galois_lfsr!(Galois3, 3, 7, 3, 2);
-galois_lfsr!(Galois2, 2, 3, 2, 1);
+galois_lfsr!(Galois128, 128, 128, 126, 101, 99);
+galois_lfsr!(Galois127, 127, 127, 126);
+galois_lfsr!(Galois126, 126, 126, 125, 90, 89);
+galois_lfsr!(Galois125, 125, 125, 124, 18, 17);
+galois_lfsr!(Galois124, 124, 124, 87);
+galois_lfsr!(Galois123, 123, 123, 121);
+galois_lfsr!(Galois122, 122, 122, 121, 63, 62);
+galois_lfsr!(Galois121, 121, 121, 103);
+galois_lfsr!(Galois120, 120, 120, 113, 9, 2);
+galois_lfsr!(Galois119, 119, 119, 111);
+galois_lfsr!(Galois118, 118, 118, 85);
+galois_lfsr!(Galois117, 117, 117, 115, 99, 97);
+galois_lfsr!(Galois116, 116, 116, 115, 46, 45);
+galois_lfsr!(Galois115, 115, 115, 114, 101, 100);
+galois_lfsr!(Galois114, 114, 114, 113, 33, 32);
+galois_lfsr!(Galois113, 113, 113, 104);
+galois_lfsr!(Galois112, 112, 112, 110, 69, 67);
+galois_lfsr!(Galois111, 111, 111, 101);
+galois_lfsr!(Galois110, 110, 110, 109, 98, 97);
+galois_lfsr!(Galois109, 109, 109, 108, 103, 102);
+galois_lfsr!(Galois108, 108, 108, 77);
+galois_lfsr!(Galois107, 107, 107, 105, 44, 42);
+galois_lfsr!(Galois106, 106, 106, 91);
+galois_lfsr!(Galois105, 105, 105, 89);
+galois_lfsr!(Galois104, 104, 104, 103, 94, 93);
+galois_lfsr!(Galois103, 103, 103, 94);
+galois_lfsr!(Galois102, 102, 102, 101, 26, 25);
+galois_lfsr!(Galois101, 101, 101, 100, 95, 94);
+galois_lfsr!(Galois100, 100, 100, 63);
+galois_lfsr!(Galois99, 99, 99, 97, 54, 52);
+galois_lfsr!(Galois98, 98, 98, 87);
+galois_lfsr!(Galois97, 97, 97, 91);
+galois_lfsr!(Galois96, 96, 96, 94, 49, 47);
+galois_lfsr!(Galois95, 95, 95, 84);
+galois_lfsr!(Galois94, 94, 94, 73);
+galois_lfsr!(Galois93, 93, 93, 91);
+galois_lfsr!(Galois92, 92, 92, 91, 80, 79);
+galois_lfsr!(Galois91, 91, 91, 90, 8, 7);
+galois_lfsr!(Galois90, 90, 90, 89, 72, 71);
+galois_lfsr!(Galois89, 89, 89, 51);
+galois_lfsr!(Galois88, 88, 88, 87, 17, 16);
+galois_lfsr!(Galois87, 87, 87, 74);
+galois_lfsr!(Galois86, 86, 86, 85, 74, 73);
+galois_lfsr!(Galois85, 85, 85, 84, 58, 57);
+galois_lfsr!(Galois84, 84, 84, 71);
+galois_lfsr!(Galois83, 83, 83, 82, 38, 37);
+galois_lfsr!(Galois82, 82, 82, 79, 47, 44);
+galois_lfsr!(Galois81, 81, 81, 77);
+galois_lfsr!(Galois80, 80, 80, 79, 43, 42);
+galois_lfsr!(Galois79, 79, 79, 70);
+galois_lfsr!(Galois78, 78, 78, 77, 59, 58);
+galois_lfsr!(Galois77, 77, 77, 76, 47, 46);
+galois_lfsr!(Galois76, 76, 76, 75, 41, 40);
+galois_lfsr!(Galois75, 75, 75, 74, 65, 64);
+galois_lfsr!(Galois74, 74, 74, 73, 59, 58);
+galois_lfsr!(Galois73, 73, 73, 48);
+galois_lfsr!(Galois72, 72, 72, 66, 25, 19);
+galois_lfsr!(Galois71, 71, 71, 65);
+galois_lfsr!(Galois70, 70, 70, 69, 55, 54);
+galois_lfsr!(Galois69, 69, 69, 67, 42, 40);
+galois_lfsr!(Galois68, 68, 68, 59);
+galois_lfsr!(Galois67, 67, 67, 66, 58, 57);
+galois_lfsr!(Galois66, 66, 66, 65, 57, 56);
+galois_lfsr!(Galois65, 65, 65, 47);
+galois_lfsr!(Galois64, 64, 64, 63, 61, 60);
+galois_lfsr!(Galois63, 63, 63, 62);
+galois_lfsr!(Galois62, 62, 62, 61, 6, 5);
+galois_lfsr!(Galois61, 61, 61, 60, 46, 45);
+galois_lfsr!(Galois60, 60, 60, 59);
+galois_lfsr!(Galois59, 59, 59, 58, 38, 37);
+galois_lfsr!(Galois58, 58, 58, 39);
+galois_lfsr!(Galois57, 57, 57, 50);
+galois_lfsr!(Galois56, 56, 56, 55, 35, 34);
+galois_lfsr!(Galois55, 55, 55, 31);
+galois_lfsr!(Galois54, 54, 54, 53, 18, 17);
+galois_lfsr!(Galois53, 53, 53, 52, 38, 37);
+galois_lfsr!(Galois52, 52, 52, 49);
+galois_lfsr!(Galois51, 51, 51, 50, 36, 35);
+galois_lfsr!(Galois50, 50, 50, 49, 24, 23);
+galois_lfsr!(Galois49, 49, 49, 40);
+galois_lfsr!(Galois48, 48, 48, 47, 21, 20);
+galois_lfsr!(Galois47, 47, 47, 42);
+galois_lfsr!(Galois46, 46, 46, 45, 26, 25);
+galois_lfsr!(Galois45, 45, 45, 44, 42, 41);
+galois_lfsr!(Galois44, 44, 44, 43, 18, 17);
+galois_lfsr!(Galois43, 43, 43, 42, 38, 37);
+galois_lfsr!(Galois42, 42, 42, 41, 20, 19);
+galois_lfsr!(Galois41, 41, 41, 38);
+galois_lfsr!(Galois40, 40, 40, 38, 21, 19);
+galois_lfsr!(Galois39, 39, 39, 35);
+galois_lfsr!(Galois38, 38, 38, 6, 5, 1);
+galois_lfsr!(Galois37, 37, 37, 5, 4, 3, 2, 1);
+galois_lfsr!(Galois36, 36, 36, 25);
+galois_lfsr!(Galois35, 35, 35, 33);
+galois_lfsr!(Galois34, 34, 34, 27, 2, 1);
+galois_lfsr!(Galois33, 33, 33, 20);
+galois_lfsr!(Galois32, 32, 32, 30, 26, 25);
+galois_lfsr!(Galois31, 31, 31, 28);
+galois_lfsr!(Galois30, 30, 30, 29, 26, 24);
+galois_lfsr!(Galois29, 29, 29, 27);
+galois_lfsr!(Galois28, 28, 28, 25);
+galois_lfsr!(Galois27, 27, 27, 26, 25, 22);
+galois_lfsr!(Galois26, 26, 26, 25, 24, 20);
+galois_lfsr!(Galois25, 25, 25, 22);
+galois_lfsr!(Galois24, 24, 24, 23, 21, 20);
+galois_lfsr!(Galois23, 23, 23, 18);
+galois_lfsr!(Galois22, 22, 22, 21);
+galois_lfsr!(Galois21, 21, 21, 19);
+galois_lfsr!(Galois20, 20, 20, 17);
+galois_lfsr!(Galois19, 19, 19, 18, 17, 14);
+galois_lfsr!(Galois18, 18, 18, 11);
+galois_lfsr!(Galois17, 17, 17, 14);
+galois_lfsr!(Galois16, 16, 16, 14, 13, 11);
+galois_lfsr!(Galois15, 15, 15, 14);
+galois_lfsr!(Galois14, 14, 14, 13, 11, 9);
+galois_lfsr!(Galois13, 13, 13, 12, 10, 9);
+galois_lfsr!(Galois12, 12, 12, 11, 8, 6);
+galois_lfsr!(Galois11, 11, 11, 9);
+galois_lfsr!(Galois10, 10, 10, 7);
+galois_lfsr!(Galois9, 9, 9, 5);
+galois_lfsr!(Galois8, 8, 8, 6, 5, 4);
+galois_lfsr!(Galois7, 7, 7, 6);
+galois_lfsr!(Galois6, 6, 6, 5);
+galois_lfsr!(Galois5, 5, 5, 3);
+galois_lfsr!(Galois4, 4, 4, 3);
+galois_lfsr!(Galois3, 3, 3, 2);
+galois_lfsr!(Galois2, 2, 2, 1);
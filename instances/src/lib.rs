@@ -1,3 +1,8 @@
 #![no_std]
 
+pub mod fibonacci_xnor;
 pub mod galois;
+pub mod prbs;
+pub mod scrambler;
+pub mod spreading;
+pub mod taps;
@@ -0,0 +1,467 @@
+//!
+//! Pseudo-random binary sequences of [ITU-T O.150](https://www.itu.int/rec/T-REC-O.150),
+//! the test patterns of bit error rate testers.
+//!
+//! Every pattern is the output of a Fibonacci LFSR whose stages `a` and `n` are XORed and fed back
+//! to stage 1, `x^n + x^a + 1` in the notation of the recommendation. The output is stage `n`,
+//! the least significant bit of the register, and it's inverted for PRBS15, PRBS23 and PRBS31,
+//! so their longest run is `n` zeros rather than `n` ones.
+//!
+//! Default patterns start at their longest run, with all the stages set to `1`, and their bytes
+//! hold the first bit in the most significant one, the order bits are sent in:
+//!
+//! ```rust
+//! use lfsr_instances::prbs::Prbs7;
+//!
+//! let mut prbs = Prbs7::default();
+//! let mut bytes = [0u8; 4];
+//! prbs.fill_bytes(&mut bytes);
+//! assert_eq!(bytes, [0xfe, 0x04, 0x18, 0x51]);
+//! ```
+//!
+//! [`PrbsChecker`] is the receiving end of a bit error rate test, it locks onto a pattern
+//! anywhere in its sequence and counts errors:
+//!
+//! ```rust
+//! use lfsr_instances::prbs::{Prbs31, PrbsChecker, ResyncThreshold};
+//!
+//! let mut bytes = [0u8; 1000];
+//! Prbs31::default().fill_bytes(&mut bytes);
+//! bytes[500] ^= 0b0010_0000;
+//!
+//! let mut checker = PrbsChecker::new(Prbs31::default(), ResyncThreshold::default());
+//! checker.check_bytes(&bytes[100..]);
+//! assert!(checker.is_synchronised());
+//! assert_eq!(checker.statistics().errors, 1);
+//! assert_eq!(checker.statistics().bits, 900 * 8 - 31);
+//! ```
+//!
+
+use lfsr_base::{FibonacciXorLFSR, LFSRState, LFSR};
+use lfsr_macro_generate::fibonacci_lfsr;
+
+fibonacci_lfsr!(Prbs7Register, 7, 7, 6);
+fibonacci_lfsr!(Prbs9Register, 9, 9, 5);
+fibonacci_lfsr!(Prbs11Register, 11, 11, 9);
+fibonacci_lfsr!(Prbs15Register, 15, 15, 14);
+fibonacci_lfsr!(Prbs20Register, 20, 20, 3);
+fibonacci_lfsr!(Prbs23Register, 23, 23, 18);
+fibonacci_lfsr!(Prbs31Register, 31, 31, 28);
+
+/// A pattern generator: output bits of an LFSR, optionally inverted
+#[derive(Clone, Debug)]
+pub struct Prbs<L> {
+    pub register: L,
+    pub inverted: bool,
+}
+
+impl<L: LFSR> Prbs<L> {
+    pub const fn new(register: L, inverted: bool) -> Self {
+        Self { register, inverted }
+    }
+
+    /// The same pattern with every bit inverted, from the same register state
+    pub fn invert(self) -> Self {
+        Self {
+            register: self.register,
+            inverted: !self.inverted,
+        }
+    }
+
+    /// Returns the output bit and steps the register
+    pub fn next_bit(&mut self) -> bool {
+        let bit = self.register.get_state().to_u128() & 1 == 1;
+        self.register.inc();
+        bit != self.inverted
+    }
+
+    /// Fills `dst` with the next `8 * dst.len()` bits, the first one in the most significant bit
+    pub fn fill_bytes(&mut self, dst: &mut [u8]) {
+        for byte in dst {
+            *byte = 0;
+            for _ in 0..8 {
+                *byte = (*byte << 1) | self.next_bit() as u8;
+            }
+        }
+    }
+}
+
+impl<L: LFSR> Iterator for Prbs<L> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        Some(self.next_bit())
+    }
+}
+
+macro_rules! o150 {
+    ($(#[$doc:meta])* $name:ident, $register:ident, $inverted:literal) => {
+        $(#[$doc])*
+        pub type $name = Prbs<$register>;
+
+        impl Default for Prbs<$register> {
+            /// Starts at the longest run, as in O.150
+            fn default() -> Self {
+                let all_ones = u128::MAX >> (128 - $register::WIDTH);
+                let all_ones = <$register as LFSR>::State::from_u128(all_ones);
+                Prbs::new($register::new(all_ones), $inverted)
+            }
+        }
+    };
+}
+
+o150!(
+    /// 2^7 - 1 pattern, `x^7 + x^6 + 1`, not inverted
+    Prbs7, Prbs7Register, false
+);
+o150!(
+    /// 2^9 - 1 pattern, `x^9 + x^5 + 1`, not inverted
+    Prbs9, Prbs9Register, false
+);
+o150!(
+    /// 2^11 - 1 pattern, `x^11 + x^9 + 1`, not inverted
+    Prbs11, Prbs11Register, false
+);
+o150!(
+    /// 2^15 - 1 pattern, `x^15 + x^14 + 1`, inverted
+    Prbs15, Prbs15Register, true
+);
+o150!(
+    /// 2^20 - 1 pattern, `x^20 + x^3 + 1`, not inverted
+    Prbs20, Prbs20Register, false
+);
+o150!(
+    /// 2^23 - 1 pattern, `x^23 + x^18 + 1`, inverted
+    Prbs23, Prbs23Register, true
+);
+o150!(
+    /// 2^31 - 1 pattern, `x^31 + x^28 + 1`, inverted
+    Prbs31, Prbs31Register, true
+);
+
+/// When [`PrbsChecker`] gives up on the current alignment: `errors` errors among the last `window`
+/// compared bits
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ResyncThreshold {
+    pub errors: u32,
+    /// Up to 128 bits
+    pub window: u32,
+}
+
+impl Default for ResyncThreshold {
+    /// 16 errors in 64 bits, a pattern compared with the wrong alignment gets 32 on average
+    fn default() -> Self {
+        Self {
+            errors: 16,
+            window: 64,
+        }
+    }
+}
+
+/// Counters of a [`PrbsChecker`]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct Statistics {
+    /// Bits compared with the pattern while in sync
+    pub bits: u64,
+    /// Compared bits that didn't match the pattern
+    pub errors: u64,
+    /// Groups of errors less than `width` correct bits apart
+    pub bursts: u64,
+    /// Times the errors reached the threshold and the checker went out of sync
+    pub sync_losses: u64,
+    /// Bits received out of sync, the ones that seed the register included
+    pub unsynced_bits: u64,
+}
+
+impl Statistics {
+    /// Bit error rate, `errors / bits`, `0` before any bits were compared
+    pub fn bit_error_rate(&self) -> f64 {
+        if self.bits == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.bits as f64
+        }
+    }
+}
+
+/// A self-synchronising pattern checker
+///
+/// Out of sync, received bits go straight into the register of the pattern, once it's full the
+/// checker is in sync and compares every bit with the next one of the pattern, counting errors.
+/// Errors don't get into the register, so every one of them is counted once. When they reach
+/// the [`ResyncThreshold`] the checker goes out of sync and seeds the register again.
+///
+/// The register is a [`FibonacciXorLFSR`], like the ones of the O.150 patterns, since its state
+/// is then the next `width` output bits. Received zeros that would seed it with the lock-up state
+/// are skipped.
+#[derive(Clone, Debug)]
+pub struct PrbsChecker<L> {
+    pattern: Prbs<L>,
+    threshold: ResyncThreshold,
+    statistics: Statistics,
+    /// Received bits and their number while out of sync, the first one in the least significant bit
+    seed: Option<(u128, u32)>,
+    /// Results of the last `threshold.window` comparisons, `1` for errors
+    recent_errors: u128,
+    /// Correct bits since the last error
+    since_error: u32,
+}
+
+impl<L: FibonacciXorLFSR> PrbsChecker<L> {
+    /// Checks bits against `pattern`, the state of its register doesn't matter
+    pub fn new(pattern: Prbs<L>, threshold: ResyncThreshold) -> Self {
+        assert!(
+            (1..=128).contains(&threshold.window),
+            "window has to be between 1 and 128 bits"
+        );
+        assert!(
+            (1..=threshold.window).contains(&threshold.errors),
+            "errors have to be between 1 and the window"
+        );
+        Self {
+            pattern,
+            threshold,
+            statistics: Statistics::default(),
+            seed: Some((0, 0)),
+            recent_errors: 0,
+            since_error: u32::MAX,
+        }
+    }
+
+    pub fn is_synchronised(&self) -> bool {
+        self.seed.is_none()
+    }
+
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Zeroes the counters, keeping the synchronisation
+    pub fn reset_statistics(&mut self) {
+        self.statistics = Statistics::default();
+    }
+
+    /// Goes out of sync and seeds the register with the next received bits
+    pub fn resync(&mut self) {
+        self.seed = Some((0, 0));
+    }
+
+    pub fn check_bit(&mut self, bit: bool) {
+        match self.seed {
+            Some((bits, count)) => {
+                self.statistics.unsynced_bits += 1;
+                let bits = bits | ((bit != self.pattern.inverted) as u128) << count;
+                let width = self.pattern.register.width();
+                if count + 1 < width {
+                    self.seed = Some((bits, count + 1));
+                } else if bits == 0 {
+                    self.seed = Some((0, width - 1));
+                } else {
+                    // that state would have sent the received bits, the next one comes after them
+                    let register = &mut self.pattern.register;
+                    register.set_state(L::State::from_u128(bits));
+                    register.advance(L::State::from_u128(width.into()));
+                    self.seed = None;
+                    self.recent_errors = 0;
+                    self.since_error = u32::MAX;
+                }
+            }
+            None => {
+                let error = self.pattern.next_bit() != bit;
+                let window = u128::MAX >> (128 - self.threshold.window);
+                self.recent_errors = ((self.recent_errors << 1) | error as u128) & window;
+                self.statistics.bits += 1;
+                if error {
+                    self.statistics.errors += 1;
+                    if self.since_error >= self.pattern.register.width() {
+                        self.statistics.bursts += 1;
+                    }
+                    self.since_error = 0;
+                    if self.recent_errors.count_ones() >= self.threshold.errors {
+                        self.statistics.sync_losses += 1;
+                        self.resync();
+                    }
+                } else {
+                    self.since_error = self.since_error.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Checks `8 * bytes.len()` bits, the first one in the most significant bit, as in [`Prbs::fill_bytes`]
+    pub fn check_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            for i in (0..8).rev() {
+                self.check_bit((byte >> i) & 1 == 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shift register as drawn in O.150: stage outputs `a` and `n` are added modulo two and fed back
+    /// to the input of stage 1, stage `n` is the output
+    fn reference(n: usize, a: usize, inverted: bool) -> impl Iterator<Item = bool> {
+        let mut stages = [true; 31];
+        core::iter::repeat_with(move || {
+            let output = stages[n - 1];
+            let feedback = stages[a - 1] != stages[n - 1];
+            stages.copy_within(0..n - 1, 1);
+            stages[0] = feedback;
+            output != inverted
+        })
+    }
+
+    fn bytes<L: LFSR>(mut prbs: Prbs<L>) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        prbs.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn published_sequences() {
+        assert_eq!(
+            bytes(Prbs7::default()),
+            [
+                0xfe, 0x04, 0x18, 0x51, 0xe4, 0x59, 0xd4, 0xfa, 0x1c, 0x49, 0xb5, 0xbd, 0x8d, 0x2e,
+                0xe6, 0x55
+            ]
+        );
+        // also known as PN9
+        assert_eq!(
+            bytes(Prbs9::default()),
+            [
+                0xff, 0x83, 0xdf, 0x17, 0x32, 0x09, 0x4e, 0xd1, 0xe7, 0xcd, 0x8a, 0x91, 0xc6, 0xd5,
+                0xc4, 0xc4
+            ]
+        );
+        assert_eq!(bytes(Prbs15::default())[..2], [0x00, 0x01]);
+        assert_eq!(bytes(Prbs31::default())[..4], [0x00, 0x00, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn matches_reference_shift_register() {
+        fn check<L: LFSR>(prbs: Prbs<L>, n: usize, a: usize, inverted: bool) {
+            assert_eq!(prbs.inverted, inverted);
+            assert!(
+                prbs.take(5000).eq(reference(n, a, inverted).take(5000)),
+                "PRBS{}",
+                n
+            );
+        }
+        check(Prbs7::default(), 7, 6, false);
+        check(Prbs9::default(), 9, 5, false);
+        check(Prbs11::default(), 11, 9, false);
+        check(Prbs15::default(), 15, 14, true);
+        check(Prbs20::default(), 20, 3, false);
+        check(Prbs23::default(), 23, 18, true);
+        check(Prbs31::default(), 31, 28, true);
+        check(Prbs15::default().invert(), 15, 14, false);
+        check(Prbs9::default().invert(), 9, 5, true);
+    }
+
+    #[test]
+    fn longest_runs() {
+        fn check<L: LFSR>(prbs: Prbs<L>, n: u32) {
+            let period = (1u32 << n) - 1;
+            let (mut longest_ones, mut longest_zeros, mut run, mut last, mut ones) =
+                (0, 0, 0, None, 0);
+            for bit in prbs.take(period as usize) {
+                run = if Some(bit) == last { run + 1 } else { 1 };
+                last = Some(bit);
+                if bit {
+                    ones += 1;
+                    longest_ones = longest_ones.max(run);
+                } else {
+                    longest_zeros = longest_zeros.max(run);
+                }
+            }
+            assert_eq!(ones, 1 << (n - 1), "PRBS{}", n);
+            assert_eq!((longest_ones, longest_zeros), (n, n - 1), "PRBS{}", n);
+        }
+        check(Prbs7::default(), 7);
+        check(Prbs9::default(), 9);
+        check(Prbs11::default(), 11);
+        check(Prbs15::default().invert(), 15);
+        check(Prbs20::default(), 20);
+    }
+
+    #[test]
+    fn checker_locks_anywhere() {
+        let mut checker = PrbsChecker::new(Prbs23::default(), ResyncThreshold::default());
+        for bit in Prbs23::default().skip(12345).take(1000) {
+            checker.check_bit(bit);
+        }
+        assert!(checker.is_synchronised());
+        let expected = Statistics {
+            bits: 1000 - 23,
+            unsynced_bits: 23,
+            ..Statistics::default()
+        };
+        assert_eq!(*checker.statistics(), expected);
+
+        // a checker that expects a non-inverted pattern sees only errors
+        let mut checker = PrbsChecker::new(Prbs23::default().invert(), ResyncThreshold::default());
+        for bit in Prbs23::default().take(1000) {
+            checker.check_bit(bit);
+        }
+        assert!(checker.statistics().sync_losses > 0);
+    }
+
+    #[test]
+    fn checker_counts_errors_and_bursts() {
+        let mut checker = PrbsChecker::new(Prbs15::default(), ResyncThreshold::default());
+        let flipped = [100, 500, 501, 510, 600, 900];
+        for (i, bit) in Prbs15::default().take(2000).enumerate() {
+            checker.check_bit(bit != flipped.contains(&i));
+        }
+        let statistics = checker.statistics();
+        assert_eq!(statistics.errors, 6);
+        // 500, 501 and 510 are less than 15 bits apart
+        assert_eq!(statistics.bursts, 4);
+        assert_eq!(statistics.sync_losses, 0);
+        assert_eq!(statistics.bit_error_rate(), 6.0 / (2000.0 - 15.0));
+    }
+
+    #[test]
+    fn checker_resyncs_after_a_slip() {
+        let threshold = ResyncThreshold {
+            errors: 4,
+            window: 16,
+        };
+        let mut checker = PrbsChecker::new(Prbs7::default(), threshold);
+        let mut prbs = Prbs7::default();
+        for _ in 0..100 {
+            checker.check_bit(prbs.next_bit());
+        }
+        // one bit gets lost
+        prbs.next_bit();
+        for _ in 0..100 {
+            checker.check_bit(prbs.next_bit());
+        }
+        assert!(checker.is_synchronised());
+        assert_eq!(checker.statistics().sync_losses, 1);
+
+        checker.reset_statistics();
+        for _ in 0..1000 {
+            checker.check_bit(prbs.next_bit());
+        }
+        assert_eq!(
+            *checker.statistics(),
+            Statistics {
+                bits: 1000,
+                ..Statistics::default()
+            }
+        );
+
+        // the lock-up state never seeds the register
+        checker.resync();
+        for _ in 0..100 {
+            checker.check_bit(false);
+        }
+        assert!(!checker.is_synchronised());
+    }
+}
@@ -0,0 +1,351 @@
+//!
+//! LFSR-based scramblers of link layers.
+//!
+//! An [`Additive`] (frame-synchronous) scrambler XORs data with the output bits of an LFSR, so the
+//! descrambler has to start from the same state at the same bit, usually at the start of a frame.
+//! A [`Multiplicative`] (self-synchronous) scrambler shifts the scrambled bits themselves through
+//! the LFSR, so its descrambler catches up after `width` bits from any state, at the cost of every
+//! bit error turning into as many errors as there are taps.
+//!
+//! Both implement [`Scrambler`], which streams over bits and bytes: every call continues where
+//! the previous one stopped. Bytes are sent in the [`BitOrder`] of the standard:
+//!
+//! ```rust
+//! use lfsr_instances::scrambler::{Ieee80211, Scrambler};
+//!
+//! let mut data = *b"Hello";
+//! Ieee80211::with_seed(0b1011101).scramble(&mut data);
+//! assert_ne!(&data, b"Hello");
+//!
+//! Ieee80211::with_seed(0b1011101).descramble(&mut data);
+//! assert_eq!(&data, b"Hello");
+//! ```
+//!
+
+use lfsr_base::{BitOrder, FibonacciXorLFSR, LFSRState, OutputBit, LFSR};
+use lfsr_macro_generate::{fibonacci_lfsr, galois_lfsr};
+
+/// Streaming scrambler and descrambler
+pub trait Scrambler {
+    /// Scrambles the next bit of data
+    fn scramble_bit(&mut self, bit: bool) -> bool;
+    /// Descrambles the next received bit
+    fn descramble_bit(&mut self, bit: bool) -> bool;
+    /// Which bit of a byte is sent first
+    fn bit_order(&self) -> BitOrder;
+
+    fn scramble_bits(&mut self, bits: &mut [bool]) {
+        for bit in bits {
+            *bit = self.scramble_bit(*bit);
+        }
+    }
+
+    fn descramble_bits(&mut self, bits: &mut [bool]) {
+        for bit in bits {
+            *bit = self.descramble_bit(*bit);
+        }
+    }
+
+    /// Scrambles bytes in place, bit by bit in the [`Scrambler::bit_order`]
+    fn scramble(&mut self, bytes: &mut [u8]) {
+        let order = self.bit_order();
+        for byte in bytes {
+            *byte = map_bits(*byte, order, |bit| self.scramble_bit(bit));
+        }
+    }
+
+    /// Descrambles bytes in place, bit by bit in the [`Scrambler::bit_order`]
+    fn descramble(&mut self, bytes: &mut [u8]) {
+        let order = self.bit_order();
+        for byte in bytes {
+            *byte = map_bits(*byte, order, |bit| self.descramble_bit(bit));
+        }
+    }
+}
+
+fn map_bits(byte: u8, order: BitOrder, mut f: impl FnMut(bool) -> bool) -> u8 {
+    let mut ret = 0;
+    for i in 0..8 {
+        let shift = match order {
+            BitOrder::LsbFirst => i,
+            BitOrder::MsbFirst => 7 - i,
+        };
+        ret |= (f((byte >> shift) & 1 == 1) as u8) << shift;
+    }
+    ret
+}
+
+/// Additive scrambler, XORs data with the `output` bit of every state of the register
+#[derive(Clone, Debug)]
+pub struct Additive<L> {
+    pub register: L,
+    pub output: OutputBit,
+    pub order: BitOrder,
+}
+
+impl<L: LFSR> Additive<L> {
+    pub const fn new(register: L, output: OutputBit, order: BitOrder) -> Self {
+        Self {
+            register,
+            output,
+            order,
+        }
+    }
+
+    /// Returns the bit data is XORed with and steps the register
+    pub fn next_key_bit(&mut self) -> bool {
+        let bit = self.output.of(&self.register);
+        self.register.inc();
+        bit
+    }
+}
+
+impl<L: LFSR> Scrambler for Additive<L> {
+    fn scramble_bit(&mut self, bit: bool) -> bool {
+        bit != self.next_key_bit()
+    }
+
+    fn descramble_bit(&mut self, bit: bool) -> bool {
+        bit != self.next_key_bit()
+    }
+
+    fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+}
+
+/// Multiplicative scrambler, shifts scrambled bits through the register
+///
+/// The register is a Fibonacci LFSR with XOR feedback, see [`FibonacciXorLFSR`]. It holds the last `width` scrambled
+/// bits, the latest in the most significant bit, and the feedback of the taps is XORed with data.
+#[derive(Clone, Debug)]
+pub struct Multiplicative<L: FibonacciXorLFSR> {
+    pub register: L,
+    pub order: BitOrder,
+}
+
+impl<L: FibonacciXorLFSR> Multiplicative<L> {
+    pub const fn new(register: L, order: BitOrder) -> Self {
+        Self { register, order }
+    }
+
+    /// Steps the register, the feedback of the taps is now the most significant bit
+    fn feedback(&mut self) -> bool {
+        self.register.inc();
+        OutputBit::Msb.of(&self.register)
+    }
+
+    /// Replaces the feedback with the scrambled bit, `data ^ feedback`
+    fn shift_in(&mut self, data: bool) {
+        if data {
+            let msb = L::State::ONE << (self.register.width() - 1);
+            self.register.set_state(self.register.get_state() ^ msb);
+        }
+    }
+}
+
+impl<L: FibonacciXorLFSR> Scrambler for Multiplicative<L> {
+    fn scramble_bit(&mut self, bit: bool) -> bool {
+        let feedback = self.feedback();
+        self.shift_in(bit);
+        bit != feedback
+    }
+
+    fn descramble_bit(&mut self, bit: bool) -> bool {
+        let data = bit != self.feedback();
+        self.shift_in(data);
+        data
+    }
+
+    fn bit_order(&self) -> BitOrder {
+        self.order
+    }
+}
+
+fibonacci_lfsr!(Ieee80211Register, 7, 7, 4);
+fibonacci_lfsr!(DvbSRegister, 15, 15, 14);
+// PCIe draws its register shifting the other way, `x^16 + x^5 + x^4 + x^3 + 1` mirrored
+galois_lfsr!(PcieRegister, 16, 16, 13, 12, 11);
+fibonacci_lfsr!(Scrambler64b66bRegister, 58, 58, 39);
+
+/// IEEE 802.11 OFDM data scrambler, `x^7 + x^4 + 1`, bytes are sent least significant bit first
+pub type Ieee80211 = Additive<Ieee80211Register>;
+
+impl Additive<Ieee80211Register> {
+    /// `seed` is the register `x1` to `x7` of the standard written left to right, `x1` being
+    /// the most significant of the 7 bits
+    pub fn with_seed(seed: u8) -> Self {
+        // the register of the standard holds the last 7 bits it sent, this one the next 7
+        let mut register = Ieee80211Register::new(seed & 0x7f);
+        register.advance(7);
+        Additive::new(register, OutputBit::Lsb, BitOrder::LsbFirst)
+    }
+}
+
+impl Default for Additive<Ieee80211Register> {
+    /// All ones, the seed of the 127-bit sequence in the standard
+    fn default() -> Self {
+        Self::with_seed(0x7f)
+    }
+}
+
+/// DVB-S energy dispersal, `x^15 + x^14 + 1`, bytes are sent most significant bit first
+///
+/// The standard starts it over from the default state every 8 transport packets. It's not applied
+/// to the sync bytes, though it keeps running over the 7 of them after the first, which is
+/// `register.advance(8)`.
+pub type DvbS = Additive<DvbSRegister>;
+
+impl Default for Additive<DvbSRegister> {
+    /// `100101010000000` in stages 1 to 15, the initialization sequence of EN 300 421
+    fn default() -> Self {
+        // stage 1 is the most significant bit, as in `Ieee80211::with_seed`
+        let mut register = DvbSRegister::new(0b100101010000000);
+        register.advance(15);
+        Additive::new(register, OutputBit::Lsb, BitOrder::MsbFirst)
+    }
+}
+
+/// PCI Express 2.5 and 5 GT/s scrambler, `x^16 + x^5 + x^4 + x^3 + 1`, bytes are sent
+/// least significant bit first
+///
+/// The standard resets it on every COM symbol and doesn't apply it to other K symbols, that's
+/// up to the caller.
+pub type Pcie = Additive<PcieRegister>;
+/// USB 3 Gen 1 scrambler, the same as the PCIe one
+pub type Usb3 = Pcie;
+
+impl Additive<PcieRegister> {
+    /// `seed` is the register of the standard, the output is its most significant bit
+    pub fn with_seed(seed: u16) -> Self {
+        Additive::new(
+            PcieRegister::new(seed.reverse_bits()),
+            OutputBit::Lsb,
+            BitOrder::LsbFirst,
+        )
+    }
+}
+
+impl Default for Additive<PcieRegister> {
+    /// All ones, the state after a reset
+    fn default() -> Self {
+        Self::with_seed(0xffff)
+    }
+}
+
+/// 64b/66b self-synchronous scrambler of IEEE 802.3 clause 49, `x^58 + x^39 + 1`,
+/// bits are sent least significant bit first
+pub type Scrambler64b66b = Multiplicative<Scrambler64b66bRegister>;
+
+impl Default for Multiplicative<Scrambler64b66bRegister> {
+    /// All ones, the standard leaves the initial state arbitrary
+    fn default() -> Self {
+        Multiplicative::new(
+            Scrambler64b66bRegister::new((1 << 58) - 1),
+            BitOrder::LsbFirst,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_bytes<S: Scrambler>(mut scrambler: S) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        scrambler.scramble(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn ieee80211_sequence() {
+        // 127-bit sequence of the all ones seed, as printed in the standard
+        let expected = "00001110 11110010 11001001 00000010 00100110 00101110 10110110 00001100 \
+                        11010100 11100111 10110100 00101010 11111010 01010001 10111000 1111111";
+        let mut scrambler = Ieee80211::default();
+        let mut bits = [false; 254];
+        scrambler.scramble_bits(&mut bits);
+        let expected = expected
+            .bytes()
+            .filter(|b| *b == b'0' || *b == b'1')
+            .map(|b| b == b'1');
+        assert!(bits[..127].iter().copied().eq(expected));
+        assert_eq!(bits[..127], bits[127..]);
+
+        // after 7 bits the register holds the last ones it sent, `x1` being the latest
+        let seed = (0..7).fold(0, |seed, i| seed | (bits[20 + i] as u8) << i);
+        let mut reseeded = [false; 30];
+        Ieee80211::with_seed(seed).scramble_bits(&mut reseeded);
+        assert_eq!(reseeded, bits[27..57]);
+    }
+
+    #[test]
+    fn pcie_sequence() {
+        // scrambled zeros after a reset
+        let expected = [0xff, 0x17, 0xc0, 0x14, 0xb2, 0xe7, 0x02, 0x82];
+        assert_eq!(key_bytes(Pcie::default()), expected);
+        assert_eq!(key_bytes(Usb3::with_seed(0xffff)), expected);
+    }
+
+    #[test]
+    fn dvb_s_sequence() {
+        // stages 14 and 15 of the register in EN 300 421, started from the initialization sequence
+        let mut stages = [
+            true, false, false, true, false, true, false, true, false, false, false, false, false,
+            false, false,
+        ];
+        let mut expected = [0u8; 8];
+        for byte in expected.iter_mut() {
+            for _ in 0..8 {
+                let bit = stages[13] != stages[14];
+                stages.copy_within(0..14, 1);
+                stages[0] = bit;
+                *byte = (*byte << 1) | bit as u8;
+            }
+        }
+        assert_eq!(expected, [0x03, 0xf6, 0x08, 0x34, 0x30, 0xb8, 0xa3, 0x93]);
+        assert_eq!(key_bytes(DvbS::default()), expected);
+    }
+
+    #[test]
+    fn scrambler_64b66b() {
+        // out[k] = in[k] ^ out[k - 39] ^ out[k - 58], starting with ones
+        let data: [bool; 300] = core::array::from_fn(|i| i % 3 == 0 || i % 7 == 0);
+        let mut expected = [true; 358];
+        for (i, bit) in data.iter().enumerate() {
+            expected[i + 58] = (*bit != expected[i + 58 - 39]) != expected[i];
+        }
+        let mut bits = data;
+        Scrambler64b66b::default().scramble_bits(&mut bits);
+        assert_eq!(bits[..], expected[58..]);
+
+        // a descrambler in any state recovers data after 58 bits
+        let mut descrambler =
+            Scrambler64b66b::new(Scrambler64b66bRegister::new(12345), BitOrder::LsbFirst);
+        descrambler.descramble_bits(&mut bits);
+        assert_ne!(bits[..58], data[..58]);
+        assert_eq!(bits[58..], data[58..]);
+    }
+
+    #[test]
+    fn round_trips() {
+        fn round_trip<S: Scrambler>(mut scrambler: S, mut descrambler: S) {
+            let data: [u8; 64] = core::array::from_fn(|i| (i * 37) as u8);
+            let mut bytes = data;
+            // streaming in uneven chunks
+            scrambler.scramble(&mut bytes[..5]);
+            scrambler.scramble(&mut bytes[5..]);
+            assert_ne!(bytes, data);
+            descrambler.descramble(&mut bytes[..40]);
+            descrambler.descramble(&mut bytes[40..]);
+            assert_eq!(bytes, data);
+        }
+        round_trip(
+            Ieee80211::with_seed(0b1011101),
+            Ieee80211::with_seed(0b1011101),
+        );
+        round_trip(DvbS::default(), DvbS::default());
+        round_trip(Pcie::default(), Pcie::default());
+        round_trip(Scrambler64b66b::default(), Scrambler64b66b::default());
+    }
+}
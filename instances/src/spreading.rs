@@ -0,0 +1,315 @@
+//!
+//! Spreading codes made of two maximum length sequences XORed together.
+//!
+//! A [Gold code](https://en.wikipedia.org/wiki/Gold_code) family takes a preferred pair of LFSRs
+//! of the same width `n`: a phase offset of one against the other gives `2^n - 1` codes,
+//! and the two sequences by themselves make two more. The cross-correlation of any two of them
+//! is at most [`gold_bound`]. A small [Kasami](https://en.wikipedia.org/wiki/Kasami_code) set takes
+//! an LFSR of even width `n` and one of width `n / 2` whose sequence is every `2^(n/2) + 1`-th bit
+//! of the first one, giving `2^(n/2)` codes with a lower [`kasami_bound`].
+//!
+//! [`CodeGenerator`] XORs the outputs of two LFSRs, which are XORs of the state bits in a mask,
+//! so codes are selected either by a phase offset or by tapping different bits of the second LFSR,
+//! like GPS does:
+//!
+//! ```rust
+//! use lfsr_instances::spreading::{max_cross_correlation, GpsCa};
+//!
+//! let mut prn1 = [false; 1023];
+//! let mut prn2 = [false; 1023];
+//! GpsCa::prn(1).unwrap().fill(&mut prn1);
+//! GpsCa::prn(2).unwrap().fill(&mut prn2);
+//! // first 10 chips, 1440 in octal
+//! assert_eq!(prn1[..10], [true, true, false, false, true, false, false, false, false, false]);
+//! assert!(max_cross_correlation(&prn1, &prn2) <= 65);
+//! ```
+//!
+
+use lfsr_base::{LFSRState, LFSR};
+use lfsr_macro_generate::fibonacci_lfsr;
+
+/// XOR of the outputs of two LFSRs, the output of each one is the XOR of its state bits in the mask
+#[derive(Clone, Debug)]
+pub struct CodeGenerator<A: LFSR, B: LFSR> {
+    pub a: A,
+    pub b: B,
+    pub a_output: A::State,
+    pub b_output: B::State,
+}
+
+impl<A: LFSR, B: LFSR> CodeGenerator<A, B> {
+    /// Outputs are the least significant bits, as in the generated LFSRs
+    pub fn new(a: A, b: B) -> Self {
+        Self::with_outputs(a, b, A::State::ONE, B::State::ONE)
+    }
+
+    pub fn with_outputs(a: A, b: B, a_output: A::State, b_output: B::State) -> Self {
+        Self {
+            a,
+            b,
+            a_output,
+            b_output,
+        }
+    }
+
+    /// The code with `b` delayed by `n` more chips against `a`
+    pub fn shifted(mut self, n: B::State) -> Self {
+        self.b.advance(n);
+        self
+    }
+
+    /// Returns the chip and steps both LFSRs
+    pub fn next_chip(&mut self) -> bool {
+        let a = (self.a.get_state() & self.a_output).to_u128().count_ones();
+        let b = (self.b.get_state() & self.b_output).to_u128().count_ones();
+        self.a.inc();
+        self.b.inc();
+        (a + b) & 1 == 1
+    }
+
+    /// Fills `chips` with the next chips
+    pub fn fill(&mut self, chips: &mut [bool]) {
+        for chip in chips {
+            *chip = self.next_chip();
+        }
+    }
+}
+
+impl<A: LFSR, B: LFSR> Iterator for CodeGenerator<A, B> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        Some(self.next_chip())
+    }
+}
+
+/// Periodic correlation of two codes of the same length with `b` shifted by `shift` chips:
+/// agreements minus disagreements between `a[i]` and `b[(i + shift) % len]`
+pub fn periodic_correlation(a: &[bool], b: &[bool], shift: usize) -> i64 {
+    assert_eq!(a.len(), b.len(), "codes have to be of the same length");
+    let (b_head, b_tail) = b.split_at(shift % b.len().max(1));
+    let disagreements = a
+        .iter()
+        .zip(b_tail.iter().chain(b_head))
+        .filter(|(a, b)| a != b)
+        .count() as i64;
+    a.len() as i64 - 2 * disagreements
+}
+
+/// Largest magnitude of the periodic correlation of `a` and `b` over all shifts
+pub fn max_cross_correlation(a: &[bool], b: &[bool]) -> u64 {
+    (0..b.len())
+        .map(|shift| periodic_correlation(a, b, shift).unsigned_abs())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Largest magnitude of the periodic autocorrelation of `a` over shifts other than `0`
+pub fn max_autocorrelation_sidelobe(a: &[bool]) -> u64 {
+    (1..a.len())
+        .map(|shift| periodic_correlation(a, a, shift).unsigned_abs())
+        .max()
+        .unwrap_or(0)
+}
+
+/// `true` if autocorrelation sidelobes of all the codes and cross-correlations of all pairs
+/// of them are at most `bound`
+pub fn within_bound(codes: &[&[bool]], bound: u64) -> bool {
+    codes.iter().enumerate().all(|(i, a)| {
+        max_autocorrelation_sidelobe(a) <= bound
+            && codes[i + 1..]
+                .iter()
+                .all(|b| max_cross_correlation(a, b) <= bound)
+    })
+}
+
+/// Bound on the correlation of Gold codes of LFSRs of width `n`: `2^((n + 1) / 2) + 1` for odd `n`,
+/// `2^((n + 2) / 2) + 1` for even `n`
+pub fn gold_bound(n: u32) -> u64 {
+    (1 << ((n + 2) / 2)) + 1
+}
+
+/// Bound on the correlation of a small Kasami set of LFSRs of even width `n`: `2^(n / 2) + 1`
+pub fn kasami_bound(n: u32) -> u64 {
+    (1 << (n / 2)) + 1
+}
+
+fibonacci_lfsr!(GpsG1, 10, 10, 3);
+fibonacci_lfsr!(GpsG2, 10, 10, 9, 8, 6, 3, 2);
+
+/// GPS L1 C/A codes: G1 `1 + x^3 + x^10` XOR G2 `1 + x^2 + x^3 + x^6 + x^8 + x^9 + x^10`,
+/// both starting with all ones, G2 output being the XOR of two of its stages
+pub type GpsCa = CodeGenerator<GpsG1, GpsG2>;
+
+/// G2 stages XORed for PRN 1 to 32, IS-GPS-200 table 3-Ia
+#[rustfmt::skip]
+pub const GPS_CA_PHASE_SELECTORS: [(u32, u32); 32] = [
+    (2, 6), (3, 7), (4, 8), (5, 9), (1, 9), (2, 10), (1, 8), (2, 9),
+    (3, 10), (2, 3), (3, 4), (5, 6), (6, 7), (7, 8), (8, 9), (9, 10),
+    (1, 4), (2, 5), (3, 6), (4, 7), (5, 8), (6, 9), (1, 3), (4, 6),
+    (5, 7), (6, 8), (7, 9), (8, 10), (1, 6), (2, 7), (3, 8), (4, 9),
+];
+
+impl CodeGenerator<GpsG1, GpsG2> {
+    /// C/A code of a PRN between 1 and 32, at its first chip
+    pub fn prn(prn: u32) -> Option<Self> {
+        let (first, second) = *GPS_CA_PHASE_SELECTORS.get((prn as usize).checked_sub(1)?)?;
+        // stage `s` of the registers in the standard is bit `10 - s` of these ones
+        Some(Self::with_outputs(
+            GpsG1::new(0x3ff),
+            GpsG2::new(0x3ff),
+            1,
+            (1 << (10 - first)) | (1 << (10 - second)),
+        ))
+    }
+}
+
+macro_rules! kasami {
+    ($(#[$doc:meta])* $name:ident, $a:ident, $b:ident) => {
+        $(#[$doc])*
+        pub type $name = CodeGenerator<$a, $b>;
+
+        impl CodeGenerator<$a, $b> {
+            /// Code `0` is the sequence of the wide LFSR, code `k` is XORed with the narrow one
+            /// delayed by `k - 1` chips
+            pub fn member(k: u32) -> Option<Self> {
+                let shifts = (1 << ($b::WIDTH)) - 1;
+                if k > shifts {
+                    return None;
+                }
+                let code = Self::new($a::default(), $b::default());
+                if k == 0 {
+                    Some(Self { b_output: 0, ..code })
+                } else {
+                    Some(code.shifted((k - 1) as _))
+                }
+            }
+        }
+    };
+}
+
+fibonacci_lfsr!(Kasami6A, 6, 6, 5);
+fibonacci_lfsr!(Kasami6B, 3, 3, 1);
+fibonacci_lfsr!(Kasami10A, 10, 10, 3);
+fibonacci_lfsr!(Kasami10B, 5, 5, 4, 3, 2);
+
+kasami!(
+    /// Small Kasami set of 8 codes of 63 chips, `1 + x^5 + x^6` and its decimation by 9, `1 + x + x^3`
+    Kasami6, Kasami6A, Kasami6B
+);
+kasami!(
+    /// Small Kasami set of 32 codes of 1023 chips, `1 + x^3 + x^10` and its decimation by 33,
+    /// `1 + x^2 + x^3 + x^4 + x^5`
+    Kasami10, Kasami10A, Kasami10B
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code<A: LFSR, B: LFSR, const N: usize>(mut generator: CodeGenerator<A, B>) -> [bool; N] {
+        let mut chips = [false; N];
+        generator.fill(&mut chips);
+        chips
+    }
+
+    #[test]
+    fn gps_ca_first_chips() {
+        // IS-GPS-200 table 3-Ia, first 10 chips in octal
+        let octal = [
+            0o1440, 0o1620, 0o1710, 0o1744, 0o1133, 0o1455, 0o1131, 0o1454, 0o1626, 0o1504, 0o1642,
+            0o1750, 0o1764, 0o1772, 0o1775, 0o1776, 0o1156, 0o1467, 0o1633, 0o1715, 0o1746, 0o1763,
+            0o1063, 0o1706, 0o1743, 0o1761, 0o1770, 0o1774, 0o1127, 0o1453, 0o1625, 0o1712,
+        ];
+        for (prn, expected) in (1..=32).zip(octal.iter()) {
+            let chips = GpsCa::prn(prn)
+                .unwrap()
+                .take(10)
+                .fold(0, |octal, chip| octal << 1 | chip as u32);
+            assert_eq!(chips, *expected, "PRN {}", prn);
+        }
+        assert!(GpsCa::prn(0).is_none());
+        assert!(GpsCa::prn(33).is_none());
+    }
+
+    #[test]
+    fn gps_ca_code_delays() {
+        // IS-GPS-200 table 3-Ia, G2 delays of the phase selectors
+        let delays = [
+            5, 6, 7, 8, 17, 18, 139, 140, 141, 251, 252, 254, 255, 256, 257, 258, 469, 470, 471,
+            472, 473, 474, 509, 512, 513, 514, 515, 516, 859, 860, 861, 862,
+        ];
+        for (prn, delay) in (1..=32).zip(delays.iter()) {
+            let selected = GpsCa::prn(prn).unwrap();
+            // G2 delayed by `delay` chips is G2 advanced by `1023 - delay`
+            let delayed = GpsCa::new(GpsG1::new(0x3ff), GpsG2::new(0x3ff)).shifted(1023 - delay);
+            assert!(selected.take(1023).eq(delayed.take(1023)), "PRN {}", prn);
+        }
+    }
+
+    #[test]
+    fn gps_ca_correlation() {
+        let prn1: [bool; 1023] = code(GpsCa::prn(1).unwrap());
+        assert_eq!(max_autocorrelation_sidelobe(&prn1), 65);
+        for prn in [2, 17, 32].iter() {
+            let other: [bool; 1023] = code(GpsCa::prn(*prn).unwrap());
+            assert_eq!(max_cross_correlation(&prn1, &other), gold_bound(10));
+        }
+    }
+
+    // a preferred pair of width 5
+    fibonacci_lfsr!(A, 5, 5, 2);
+    fibonacci_lfsr!(B, 5, 5, 4, 3, 2);
+    fibonacci_lfsr!(C, 5, 5, 3);
+
+    #[test]
+    fn gold_family() {
+        let mut codes = [[false; 31]; 33];
+        codes[0] = code(CodeGenerator::with_outputs(
+            A::default(),
+            B::default(),
+            1,
+            0,
+        ));
+        codes[1] = code(CodeGenerator::with_outputs(
+            A::default(),
+            B::default(),
+            0,
+            1,
+        ));
+        for (shift, chips) in codes[2..].iter_mut().enumerate() {
+            *chips = code(CodeGenerator::new(A::default(), B::default()).shifted(shift as u8));
+        }
+        let codes: [&[bool]; 33] = core::array::from_fn(|i| &codes[i][..]);
+        assert!(within_bound(&codes, gold_bound(5)));
+
+        // a pair that isn't preferred
+        let a: [bool; 31] = code(CodeGenerator::with_outputs(
+            A::default(),
+            B::default(),
+            1,
+            0,
+        ));
+        let c: [bool; 31] = code(CodeGenerator::with_outputs(
+            A::default(),
+            C::default(),
+            0,
+            1,
+        ));
+        assert!(max_cross_correlation(&a, &c) > gold_bound(5));
+    }
+
+    #[test]
+    fn kasami_sets() {
+        let codes: [[bool; 63]; 8] =
+            core::array::from_fn(|k| code(Kasami6::member(k as u32).unwrap()));
+        let codes: [&[bool]; 8] = core::array::from_fn(|i| &codes[i][..]);
+        assert!(within_bound(&codes, kasami_bound(6)));
+        assert!(Kasami6::member(8).is_none());
+
+        let codes: [[bool; 1023]; 3] = [0, 1, 31].map(|k| code(Kasami10::member(k).unwrap()));
+        let codes: [&[bool]; 3] = core::array::from_fn(|i| &codes[i][..]);
+        assert!(within_bound(&codes, kasami_bound(10)));
+        assert!(Kasami10::member(32).is_none());
+    }
+}
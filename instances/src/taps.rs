@@ -0,0 +1,190 @@
+//!
+//! Tables of primitive feedback polynomials with few taps, for widths 2 to 64.
+//!
+//! Tables are indexed by width and hold 1-based taps in decreasing order, in the same notation as
+//! `galois_lfsr!` and `fibonacci_lfsr!`, so `[7, 6]` is `x^7 + x^6 + 1`.
+//! Different polynomials of the same width give different maximum length sequences, which is what
+//! spreading codes and decorrelated test streams need. Few taps means little feedback logic in hardware.
+//!
+//! All primitive polynomials of a width, not just these, are enumerated by
+//! `lfsr_base::gf2::primitive_polynomials`, and `nth_primitive_galois_lfsr!` instantiates one of them.
+//!
+
+/// All primitive trinomials `x^n + x^k + 1`, as 2-tap sets `[n, k]`, indexed by width `n`
+///
+/// Some widths, like all multiples of 8, have none.
+/// The reciprocal `[n, n - k]` of every entry is also there.
+#[rustfmt::skip]
+pub const TWO_TAPS: [&[[u32; 2]]; 65] = [
+    &[],
+    &[],
+    &[[2, 1]],
+    &[[3, 2], [3, 1]],
+    &[[4, 3], [4, 1]],
+    &[[5, 3], [5, 2]],
+    &[[6, 5], [6, 1]],
+    &[[7, 6], [7, 4], [7, 3], [7, 1]],
+    &[],
+    &[[9, 5], [9, 4]],
+    &[[10, 7], [10, 3]],
+    &[[11, 9], [11, 2]],
+    &[],
+    &[],
+    &[],
+    &[[15, 14], [15, 11], [15, 8], [15, 7], [15, 4], [15, 1]],
+    &[],
+    &[[17, 14], [17, 12], [17, 11], [17, 6], [17, 5], [17, 3]],
+    &[[18, 11], [18, 7]],
+    &[],
+    &[[20, 17], [20, 3]],
+    &[[21, 19], [21, 2]],
+    &[[22, 21], [22, 1]],
+    &[[23, 18], [23, 14], [23, 9], [23, 5]],
+    &[],
+    &[[25, 22], [25, 18], [25, 7], [25, 3]],
+    &[],
+    &[],
+    &[[28, 25], [28, 19], [28, 15], [28, 13], [28, 9], [28, 3]],
+    &[[29, 27], [29, 2]],
+    &[],
+    &[[31, 28], [31, 25], [31, 24], [31, 18], [31, 13], [31, 7], [31, 6], [31, 3]],
+    &[],
+    &[[33, 20], [33, 13]],
+    &[],
+    &[[35, 33], [35, 2]],
+    &[[36, 25], [36, 11]],
+    &[],
+    &[],
+    &[[39, 35], [39, 31], [39, 25], [39, 14], [39, 8], [39, 4]],
+    &[],
+    &[[41, 38], [41, 21], [41, 20], [41, 3]],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[[47, 42], [47, 33], [47, 27], [47, 26], [47, 21], [47, 20], [47, 14], [47, 5]],
+    &[],
+    &[[49, 40], [49, 37], [49, 34], [49, 27], [49, 22], [49, 15], [49, 12], [49, 9]],
+    &[],
+    &[],
+    &[[52, 49], [52, 33], [52, 31], [52, 21], [52, 19], [52, 3]],
+    &[],
+    &[],
+    &[[55, 31], [55, 24]],
+    &[],
+    &[[57, 50], [57, 35], [57, 22], [57, 7]],
+    &[[58, 39], [58, 19]],
+    &[],
+    &[[60, 59], [60, 49], [60, 11], [60, 1]],
+    &[],
+    &[],
+    &[[63, 62], [63, 58], [63, 32], [63, 31], [63, 5], [63, 1]],
+    &[],
+];
+
+/// Up to 8 primitive pentanomials per width, as 4-tap sets `[n, a, b, c]`, indexed by width `n`
+///
+/// These are the first ones in decreasing lexicographic order of taps, so the taps are
+/// close to the end of the register, like in [XAPP052](https://docs.xilinx.com/v/u/en-US/xapp052).
+/// Widths below 5 have none.
+#[rustfmt::skip]
+pub const FOUR_TAPS: [&[[u32; 4]]; 65] = [
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[[5, 4, 3, 2], [5, 4, 3, 1], [5, 4, 2, 1], [5, 3, 2, 1]],
+    &[[6, 5, 4, 1], [6, 5, 3, 2], [6, 5, 2, 1], [6, 4, 3, 1]],
+    &[[7, 6, 5, 4], [7, 6, 5, 2], [7, 6, 4, 2], [7, 6, 4, 1], [7, 6, 3, 1], [7, 5, 4, 3], [7, 5, 3, 1], [7, 5, 2, 1]],
+    &[[8, 7, 6, 1], [8, 7, 5, 3], [8, 7, 3, 2], [8, 7, 2, 1], [8, 6, 5, 4], [8, 6, 5, 3], [8, 6, 5, 2], [8, 6, 5, 1]],
+    &[[9, 8, 7, 2], [9, 8, 6, 5], [9, 8, 5, 4], [9, 8, 5, 1], [9, 8, 4, 2], [9, 8, 4, 1], [9, 7, 6, 4], [9, 7, 5, 2]],
+    &[[10, 9, 8, 5], [10, 9, 7, 6], [10, 9, 7, 3], [10, 9, 6, 1], [10, 9, 5, 2], [10, 9, 4, 2], [10, 9, 4, 1], [10, 8, 7, 5]],
+    &[[11, 10, 9, 7], [11, 10, 9, 5], [11, 10, 9, 2], [11, 10, 8, 6], [11, 10, 8, 1], [11, 10, 7, 3], [11, 10, 7, 2], [11, 10, 6, 5]],
+    &[[12, 11, 10, 4], [12, 11, 10, 2], [12, 11, 8, 6], [12, 11, 7, 4], [12, 10, 9, 3], [12, 10, 5, 4], [12, 10, 2, 1], [12, 9, 8, 5]],
+    &[[13, 12, 11, 8], [13, 12, 11, 2], [13, 12, 11, 1], [13, 12, 10, 9], [13, 12, 10, 6], [13, 12, 10, 3], [13, 12, 9, 7], [13, 12, 9, 3]],
+    &[[14, 13, 12, 2], [14, 13, 11, 9], [14, 13, 11, 4], [14, 13, 10, 8], [14, 13, 10, 6], [14, 13, 10, 3], [14, 13, 8, 6], [14, 13, 8, 4]],
+    &[[15, 14, 13, 11], [15, 14, 13, 8], [15, 14, 13, 1], [15, 14, 12, 3], [15, 14, 12, 2], [15, 14, 11, 8], [15, 14, 11, 6], [15, 14, 11, 1]],
+    &[[16, 15, 13, 4], [16, 15, 12, 10], [16, 15, 12, 1], [16, 15, 10, 4], [16, 15, 9, 6], [16, 15, 9, 4], [16, 15, 7, 2], [16, 15, 4, 2]],
+    &[[17, 16, 15, 14], [17, 16, 15, 7], [17, 16, 14, 9], [17, 16, 14, 6], [17, 16, 14, 4], [17, 16, 14, 1], [17, 16, 13, 12], [17, 16, 13, 5]],
+    &[[18, 17, 16, 13], [18, 17, 16, 10], [18, 17, 16, 5], [18, 17, 15, 9], [18, 17, 14, 9], [18, 17, 13, 4], [18, 17, 13, 1], [18, 17, 10, 2]],
+    &[[19, 18, 17, 14], [19, 18, 17, 13], [19, 18, 17, 5], [19, 18, 16, 10], [19, 18, 16, 5], [19, 18, 15, 13], [19, 18, 15, 12], [19, 18, 15, 10]],
+    &[[20, 19, 16, 14], [20, 19, 16, 2], [20, 19, 15, 11], [20, 19, 15, 10], [20, 19, 15, 4], [20, 19, 14, 9], [20, 19, 14, 4], [20, 19, 12, 8]],
+    &[[21, 20, 19, 16], [21, 20, 19, 13], [21, 20, 19, 7], [21, 20, 19, 5], [21, 20, 19, 4], [21, 20, 19, 2], [21, 20, 18, 6], [21, 20, 17, 14]],
+    &[[22, 21, 20, 11], [22, 21, 20, 7], [22, 21, 19, 10], [22, 21, 19, 2], [22, 21, 18, 12], [22, 21, 17, 13], [22, 21, 17, 8], [22, 21, 16, 15]],
+    &[[23, 22, 21, 16], [23, 22, 21, 8], [23, 22, 21, 2], [23, 22, 21, 1], [23, 22, 20, 18], [23, 22, 20, 16], [23, 22, 20, 4], [23, 22, 20, 3]],
+    &[[24, 23, 22, 17], [24, 23, 22, 7], [24, 23, 21, 20], [24, 23, 21, 11], [24, 23, 21, 8], [24, 23, 19, 2], [24, 23, 18, 14], [24, 23, 16, 13]],
+    &[[25, 24, 23, 22], [25, 24, 23, 2], [25, 24, 22, 15], [25, 24, 22, 13], [25, 24, 22, 8], [25, 24, 22, 5], [25, 24, 22, 3], [25, 24, 21, 18]],
+    &[[26, 25, 24, 20], [26, 25, 24, 8], [26, 25, 24, 7], [26, 25, 22, 17], [26, 25, 22, 14], [26, 25, 22, 3], [26, 25, 22, 2], [26, 25, 21, 1]],
+    &[[27, 26, 25, 22], [27, 26, 25, 17], [27, 26, 25, 11], [27, 26, 24, 11], [27, 26, 23, 7], [27, 26, 22, 10], [27, 26, 22, 2], [27, 26, 22, 1]],
+    &[[28, 27, 26, 16], [28, 27, 26, 11], [28, 27, 25, 18], [28, 27, 25, 6], [28, 27, 24, 22], [28, 27, 24, 7], [28, 27, 23, 19], [28, 27, 23, 4]],
+    &[[29, 28, 27, 25], [29, 28, 27, 20], [29, 28, 27, 8], [29, 28, 27, 4], [29, 28, 26, 10], [29, 28, 25, 6], [29, 28, 24, 19], [29, 28, 24, 2]],
+    &[[30, 29, 28, 7], [30, 29, 26, 24], [30, 29, 26, 22], [30, 29, 26, 4], [30, 29, 24, 17], [30, 29, 24, 12], [30, 29, 23, 20], [30, 29, 23, 18]],
+    &[[31, 30, 29, 28], [31, 30, 29, 25], [31, 30, 29, 20], [31, 30, 29, 19], [31, 30, 29, 5], [31, 30, 28, 24], [31, 30, 28, 22], [31, 30, 28, 18]],
+    &[[32, 31, 30, 10], [32, 31, 29, 1], [32, 31, 26, 18], [32, 31, 26, 9], [32, 31, 26, 7], [32, 31, 23, 10], [32, 31, 22, 17], [32, 31, 21, 16]],
+    &[[33, 32, 31, 26], [33, 32, 31, 25], [33, 32, 31, 23], [33, 32, 31, 22], [33, 32, 31, 5], [33, 32, 30, 24], [33, 32, 30, 20], [33, 32, 30, 17]],
+    &[[34, 33, 32, 7], [34, 33, 32, 4], [34, 33, 31, 6], [34, 33, 30, 21], [34, 33, 30, 1], [34, 33, 29, 25], [34, 33, 29, 11], [34, 33, 28, 21]],
+    &[[35, 34, 33, 26], [35, 34, 32, 6], [35, 34, 31, 6], [35, 34, 30, 19], [35, 34, 30, 17], [35, 34, 30, 10], [35, 34, 30, 5], [35, 34, 28, 27]],
+    &[[36, 35, 32, 22], [36, 35, 30, 26], [36, 35, 29, 28], [36, 35, 29, 27], [36, 35, 29, 15], [36, 35, 29, 11], [36, 35, 29, 9], [36, 35, 28, 23]],
+    &[[37, 36, 35, 28], [37, 36, 35, 19], [37, 36, 35, 2], [37, 36, 34, 11], [37, 36, 33, 31], [37, 36, 33, 18], [37, 36, 33, 3], [37, 36, 32, 23]],
+    &[[38, 37, 35, 25], [38, 37, 35, 16], [38, 37, 35, 13], [38, 37, 33, 32], [38, 37, 33, 31], [38, 37, 33, 17], [38, 37, 33, 11], [38, 37, 32, 30]],
+    &[[39, 38, 37, 14], [39, 38, 37, 13], [39, 38, 36, 30], [39, 38, 36, 5], [39, 38, 36, 3], [39, 38, 35, 32], [39, 38, 35, 21], [39, 38, 34, 19]],
+    &[[40, 39, 38, 5], [40, 39, 37, 31], [40, 39, 37, 18], [40, 39, 35, 5], [40, 39, 34, 10], [40, 39, 34, 2], [40, 39, 33, 16], [40, 39, 33, 9]],
+    &[[41, 40, 39, 38], [41, 40, 39, 35], [41, 40, 39, 23], [41, 40, 38, 37], [41, 40, 38, 34], [41, 40, 38, 27], [41, 40, 38, 9], [41, 40, 38, 4]],
+    &[[42, 41, 40, 13], [42, 41, 40, 5], [42, 41, 38, 21], [42, 41, 38, 10], [42, 41, 37, 17], [42, 41, 36, 27], [42, 41, 36, 15], [42, 41, 35, 24]],
+    &[[43, 42, 41, 31], [43, 42, 41, 17], [43, 42, 40, 22], [43, 42, 40, 5], [43, 42, 40, 1], [43, 42, 38, 37], [43, 42, 38, 22], [43, 42, 38, 17]],
+    &[[44, 43, 41, 6], [44, 43, 40, 27], [44, 43, 40, 8], [44, 43, 40, 3], [44, 43, 38, 13], [44, 43, 38, 6], [44, 43, 38, 4], [44, 43, 37, 15]],
+    &[[45, 44, 42, 41], [45, 44, 42, 23], [45, 44, 41, 39], [45, 44, 41, 19], [45, 44, 39, 9], [45, 44, 38, 37], [45, 44, 38, 29], [45, 44, 38, 18]],
+    &[[46, 45, 43, 37], [46, 45, 43, 29], [46, 45, 43, 16], [46, 45, 43, 6], [46, 45, 42, 19], [46, 45, 42, 9], [46, 45, 41, 32], [46, 45, 41, 26]],
+    &[[47, 46, 45, 16], [47, 46, 45, 11], [47, 46, 45, 2], [47, 46, 44, 40], [47, 46, 44, 39], [47, 46, 44, 36], [47, 46, 44, 34], [47, 46, 44, 24]],
+    &[[48, 47, 45, 20], [48, 47, 45, 9], [48, 47, 43, 37], [48, 47, 43, 1], [48, 47, 40, 1], [48, 47, 39, 29], [48, 47, 33, 6], [48, 47, 29, 19]],
+    &[[49, 48, 47, 13], [49, 48, 47, 7], [49, 48, 46, 40], [49, 48, 46, 29], [49, 48, 46, 20], [49, 48, 45, 36], [49, 48, 45, 34], [49, 48, 45, 21]],
+    &[[50, 49, 48, 34], [50, 49, 48, 8], [50, 49, 46, 21], [50, 49, 46, 14], [50, 49, 45, 20], [50, 49, 45, 13], [50, 49, 44, 3], [50, 49, 43, 30]],
+    &[[51, 50, 49, 23], [51, 50, 49, 7], [51, 50, 49, 5], [51, 50, 48, 45], [51, 50, 48, 33], [51, 50, 48, 18], [51, 50, 47, 39], [51, 50, 47, 33]],
+    &[[52, 51, 50, 41], [52, 51, 50, 40], [52, 51, 50, 14], [52, 51, 49, 46], [52, 51, 48, 37], [52, 51, 48, 36], [52, 51, 48, 28], [52, 51, 48, 12]],
+    &[[53, 52, 51, 47], [53, 52, 51, 41], [53, 52, 51, 16], [53, 52, 51, 1], [53, 52, 49, 38], [53, 52, 49, 35], [53, 52, 48, 19], [53, 52, 47, 27]],
+    &[[54, 53, 52, 37], [54, 53, 51, 5], [54, 53, 50, 27], [54, 53, 49, 38], [54, 53, 49, 29], [54, 53, 48, 17], [54, 53, 47, 44], [54, 53, 47, 29]],
+    &[[55, 54, 53, 49], [55, 54, 53, 37], [55, 54, 53, 11], [55, 54, 53, 8], [55, 54, 53, 2], [55, 54, 53, 1], [55, 54, 52, 45], [55, 54, 52, 43]],
+    &[[56, 55, 54, 14], [56, 55, 53, 30], [56, 55, 53, 27], [56, 55, 53, 16], [56, 55, 53, 1], [56, 55, 52, 21], [56, 55, 50, 39], [56, 55, 50, 25]],
+    &[[57, 56, 55, 46], [57, 56, 55, 38], [57, 56, 54, 47], [57, 56, 54, 36], [57, 56, 54, 29], [57, 56, 53, 46], [57, 56, 53, 41], [57, 56, 53, 35]],
+    &[[58, 57, 56, 23], [58, 57, 56, 17], [58, 57, 55, 37], [58, 57, 55, 11], [58, 57, 55, 6], [58, 57, 55, 5], [58, 57, 54, 19], [58, 57, 53, 52]],
+    &[[59, 58, 57, 35], [59, 58, 57, 25], [59, 58, 56, 30], [59, 58, 55, 47], [59, 58, 55, 33], [59, 58, 55, 21], [59, 58, 55, 12], [59, 58, 55, 2]],
+    &[[60, 59, 58, 16], [60, 59, 56, 35], [60, 59, 56, 16], [60, 59, 56, 3], [60, 59, 54, 51], [60, 59, 54, 42], [60, 59, 54, 36], [60, 59, 54, 14]],
+    &[[61, 60, 59, 56], [61, 60, 59, 32], [61, 60, 59, 28], [61, 60, 59, 25], [61, 60, 59, 16], [61, 60, 59, 13], [61, 60, 59, 4], [61, 60, 58, 29]],
+    &[[62, 61, 59, 34], [62, 61, 59, 1], [62, 61, 57, 46], [62, 61, 57, 31], [62, 61, 57, 19], [62, 61, 56, 37], [62, 61, 56, 18], [62, 61, 56, 13]],
+    &[[63, 62, 61, 11], [63, 62, 61, 7], [63, 62, 61, 2], [63, 62, 60, 54], [63, 62, 60, 30], [63, 62, 59, 58], [63, 62, 59, 50], [63, 62, 59, 10]],
+    &[[64, 63, 62, 53], [64, 63, 61, 60], [64, 63, 60, 18], [64, 63, 58, 15], [64, 63, 58, 14], [64, 63, 57, 52], [64, 63, 57, 39], [64, 63, 56, 29]],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lfsr_base::gf2::Poly;
+
+    #[test]
+    fn two_taps_are_all_the_primitive_trinomials() {
+        for (width, taps) in TWO_TAPS.iter().enumerate().skip(2) {
+            let width = width as u32;
+            let mut expected = (1..width)
+                .rev()
+                .filter(|k| Poly::from_taps(&[width, *k]).is_primitive())
+                .map(|k| [width, k]);
+            assert!(taps.iter().copied().eq(&mut expected), "width {}", width);
+        }
+    }
+
+    #[test]
+    fn four_taps_are_the_first_primitive_pentanomials() {
+        for (width, taps) in FOUR_TAPS.iter().enumerate().skip(2) {
+            let width = width as u32;
+            let mut expected = (1..width)
+                .rev()
+                .flat_map(|a| (1..a).rev().map(move |b| (a, b)))
+                .flat_map(|(a, b)| (1..b).rev().map(move |c| [width, a, b, c]))
+                .filter(|taps| Poly::from_taps(taps).is_primitive())
+                .take(8);
+            assert!(taps.iter().copied().eq(&mut expected), "width {}", width);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Output bits of Galois LFSRs one `inc()` at a time against `up8` and `up64`
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use lfsr::galois::{Galois32, Galois64};
+use lfsr::LFSR;
+
+const BYTES: usize = 64 * 1024;
+
+macro_rules! leap_forward {
+    ($c:expr, $group:literal, $lfsr:ident) => {
+        let mut group = $c.benchmark_group($group);
+        group.throughput(Throughput::Bytes(BYTES as u64));
+        let mut buffer = vec![0u8; BYTES];
+
+        group.bench_function("inc", |b| {
+            let mut lfsr = $lfsr::default();
+            b.iter(|| {
+                for byte in buffer.iter_mut() {
+                    let mut bits = 0;
+                    for i in 0..8 {
+                        bits |= ((lfsr.get_state() & 1) as u8) << i;
+                        lfsr.inc();
+                    }
+                    *byte = bits;
+                }
+                black_box(&buffer);
+            })
+        });
+
+        group.bench_function("up8", |b| {
+            let mut state = $lfsr::default().state;
+            b.iter(|| {
+                for byte in buffer.iter_mut() {
+                    let (next, bits) = $lfsr::up8(state);
+                    state = next;
+                    *byte = bits;
+                }
+                black_box(&buffer);
+            })
+        });
+
+        group.bench_function("up64", |b| {
+            let mut state = $lfsr::default().state;
+            b.iter(|| {
+                for chunk in buffer.chunks_exact_mut(8) {
+                    let (next, bits) = $lfsr::up64(state);
+                    state = next;
+                    chunk.copy_from_slice(&bits.to_le_bytes());
+                }
+                black_box(&buffer);
+            })
+        });
+
+        group.finish();
+    };
+}
+
+fn galois32(c: &mut Criterion) {
+    leap_forward!(c, "galois32", Galois32);
+}
+
+fn galois64(c: &mut Criterion) {
+    leap_forward!(c, "galois64", Galois64);
+}
+
+criterion_group!(benches, galois32, galois64);
+criterion_main!(benches);
@@ -58,21 +58,197 @@
 //! // wrap around
 //! lfsr.inc();
 //! assert_eq!(galois16_direct_lookup(&lfsr), 0);
+//!
+//! // Jumping ahead takes a logarithmic number of steps
+//!
+//! let mut lfsr = galois::Galois32::default();
+//! lfsr.advance(100_000_000);
+//! assert_eq!(galois32_lookup_big(&lfsr), Some(100_000_000));
+//!
+//! lfsr.rewind(100_000_000);
+//! assert_eq!(lfsr.get_state(), 1);
+//! # }
+//! ```
+//!
+//! Lookups for LFSRs other than the ones in [`galois`] need the same width and taps
+//! that were passed to `galois_lfsr!`:
+//!
+//! ```rust
+//! use lfsr::{direct_lfsr_lookup, galois_lfsr, searching_lfsr_lookup};
+//! use lfsr::LFSR;
+//!
+//! galois_lfsr!(MyLFSR, 12, 12, 6, 4, 1);
+//! direct_lfsr_lookup!(my_direct_lookup, MyLFSR, width = 12, taps = [12, 6, 4, 1]);
+//! searching_lfsr_lookup!(my_lookup, MyLFSR, 1000, 2000, 10, width = 12, taps = [12, 6, 4, 1]);
+//!
+//! # fn main() {
+//! let mut lfsr = MyLFSR::default();
+//! for _ in 0..1234 {
+//!     lfsr.inc();
+//! }
+//! assert_eq!(my_direct_lookup(&lfsr), 1234);
+//! assert_eq!(my_lookup(&lfsr), Some(1234));
+//! # }
+//! ```
+//!
+//! Positions anywhere in the sequence of a maximum length Galois LFSR can be found with
+//! discrete logarithms, at most `256` states are stored for every prime factor of `2^32 - 1` here:
+//!
+//! ```rust
+//! use lfsr::discrete_log_lfsr_lookup;
+//! use lfsr::galois;
+//! use lfsr::LFSR;
+//!
+//! discrete_log_lfsr_lookup!(galois32_log_lookup, galois::Galois32, 256);
+//!
+//! # fn main() {
+//! let mut lfsr = galois::Galois32::default();
+//! lfsr.advance(3_000_000_000);
+//! assert_eq!(galois32_log_lookup(&lfsr), Some(3_000_000_000));
+//! assert_eq!(galois32_log_lookup(&galois::Galois32::new(0)), None);
+//! # }
+//! ```
+//!
+//! A lookup may take at most `2^32` steps for every prime factor, `2^127 - 1` is prime itself and no
+//! table is big enough for it:
+//!
+//! ```compile_fail
+//! use lfsr::discrete_log_lfsr_lookup;
+//! use lfsr::galois;
+//!
+//! discrete_log_lfsr_lookup!(galois127_log_lookup, galois::Galois127, 4096);
+//! # fn main() {}
+//! ```
+//!
+//! Direct lookups store every state and stop at width 22:
+//!
+//! ```compile_fail
+//! use lfsr::direct_lfsr_lookup;
+//! use lfsr::galois;
+//!
+//! direct_lfsr_lookup!(galois48_direct_lookup, galois::Galois48);
+//! # fn main() {}
+//! ```
+//!
+//! [`GaloisLfsr`] and [`FibonacciLfsr`] are configured at run time and step the same way
+//! as the generated LFSRs with the same width and taps:
+//!
+//! ```rust
+//! use lfsr::{galois, GaloisLfsr, LFSR};
+//!
+//! let mut runtime = GaloisLfsr::new(16, &[16, 14, 13, 11], 1).unwrap();
+//! let mut generated = galois::Galois16::default();
+//! runtime.advance(1000);
+//! generated.advance(1000);
+//! assert_eq!(runtime.state, generated.state as u128);
+//! ```
+//!
+//! Many maximum length LFSRs of the same width are there for distinct sequences, [`taps`] has
+//! tables of primitive polynomials with 2 and 4 taps and `nth_primitive_galois_lfsr!` takes any of them:
+//!
+//! ```rust
+//! use lfsr::gf2::{primitive_polynomials, Poly};
+//! use lfsr::{galois_lfsr, nth_primitive_galois_lfsr, taps, LFSR};
+//!
+//! galois_lfsr!(Trinomial31, 31, 31, 25);
+//! nth_primitive_galois_lfsr!(Third20, 20, 2);
+//!
+//! # fn main() {
+//! assert!(taps::TWO_TAPS[31].contains(&[31, 25]));
+//! assert_eq!(Poly::from_taps(Third20::TAPS), primitive_polynomials(20).nth(2).unwrap());
+//! assert_eq!(Third20::default().sequence_length(), (1 << 20) - 1);
 //! # }
 //! ```
+//!
+//! With the `rand_core` feature every LFSR implements `RngCore` and `SeedableRng` from
+//! [`rand_core`](https://docs.rs/rand_core/0.9) 0.9, see `lfsr::rng` for how the bits are used.
 
 pub use lfsr_base::*;
 pub use lfsr_instances::*;
-pub use lfsr_macro_generate::galois_lfsr;
-pub use lfsr_macro_lookup::{direct_lfsr_lookup, searching_lfsr_lookup};
+pub use lfsr_macro_generate::{
+    crc, fibonacci_lfsr, fibonacci_xnor_lfsr, galois_lfsr, galois_misr, galois_sisr, galois_xnor_lfsr,
+    nth_primitive_fibonacci_lfsr, nth_primitive_galois_lfsr,
+};
+pub use lfsr_macro_lookup::{
+    direct_lfsr_lookup, discrete_log_lfsr_lookup, searching_lfsr_lookup,
+};
 
 #[cfg(test)]
 mod tests {
+    extern crate std;
+
     use super::*;
+    use std::format;
 
     searching_lfsr_lookup!(galois32_lookup, galois::Galois32, 10, 20, 5);
     direct_lfsr_lookup!(galois16_direct_lookup, galois::Galois16);
 
+    galois_lfsr!(Custom12, 12, 4095, 12, 6, 4, 1);
+    // (x^2 + x + 1)^2, not primitive
+    galois_lfsr!(NonMaximal4, 4, 4, 2);
+    galois_lfsr!(Wide12, 12u64, 12, 6, 4, 1);
+    // taps in any order after an explicit sequence length
+    galois_lfsr!(Unordered8, 8, 255, 4, 5, 6, 8);
+    // x^4 + 1, its sequence length is its width
+    galois_lfsr!(Cyclic4, 4, 4, 4);
+    fibonacci_lfsr!(Fibonacci16, 16, 16, 14, 13, 11);
+    fibonacci_lfsr!(Fibonacci8, 8, 8, 6, 5, 4);
+    fibonacci_lfsr!(FibonacciNonMaximal4, 4, 4, 2);
+    galois_xnor_lfsr!(GaloisXnor16, 16, 16, 14, 13, 11);
+    galois_xnor_lfsr!(GaloisXnorNonMaximal4, 4, 4, 2);
+    fibonacci_xnor_lfsr!(FibonacciXnorNonMaximal4, 4, 4, 2);
+    nth_primitive_galois_lfsr!(FirstPrimitive10, 10, 0);
+    nth_primitive_galois_lfsr!(LastPrimitive10, 10, 59);
+    nth_primitive_fibonacci_lfsr!(SecondPrimitive12, 12, 1);
+    // Fibonacci8's x^8 + x^6 + x^5 + x^4 + 1 in every notation
+    galois_lfsr!(Positional8, 8, 8, 6, 5, 4);
+    galois_lfsr!(Koopman8, 8, koopman = 0xB8);
+    galois_lfsr!(Polynomial8, 8, 255, polynomial = x^8 + x^6 + x^5 + x^4 + 1);
+    galois_lfsr!(Normal8, 8, normal = 0x71);
+    galois_lfsr!(Reversed8, 8, reversed = 0x8E);
+    galois_lfsr!(Reciprocal8, 8, reciprocal = 0x1D);
+    galois_lfsr!(NamedTaps8, 8, taps = [8, 6, 5, 4]);
+    galois_lfsr!(Koopman32, 32, 4294967295, koopman = 0xA3000000);
+    fibonacci_lfsr!(FibonacciPolynomial16, 16, polynomial = 1 + x^11 + x^13 + x^14 + x^16);
+    galois_misr!(Misr8, 8, koopman = 0xB8);
+    galois_misr!(Misr16, 16, 16, 15, 13, 4);
+    galois_sisr!(Sisr8, 8, 8, 6, 5, 4);
+    galois_sisr!(Sisr32, 32, 32, 30, 26, 25);
+    // the taps of `galois::Galois32` as a reference Fibonacci register
+    fibonacci_lfsr!(Reference32, 32, 32, 30, 26, 25);
+    crc!(Crc32, "CRC-32");
+    crc!(Crc16Xmodem, "crc-16/xmodem");
+    crc!(Crc8Rohc, width = 8, poly = 0x07, init = 0xff, refin = true, check = 0xd0);
+    crc!(Crc5Usb, width = 5, poly = 0x05, init = 0x1f, refin = true, xorout = 0x1f, check = 0x19);
+    crc!(Crc12Umts, width = 12, poly = 0x80f, refout = true, check = 0xdaf);
+    crc!(Crc3Gsm, width = 3, poly = 0x3, xorout = 0x7, check = 0x4);
+    crc!(Crc64Xz, "CRC-64/XZ");
+    crc!(Crc82Darc, width = 82, poly = 0x0308c0111011401440411, refin = true, check = 0x09ea83f625023801fd612);
+
+    searching_lfsr_lookup!(galois64_lookup, galois::Galois64, 1000, 2000, 100);
+    discrete_log_lfsr_lookup!(galois16_log_lookup, galois::Galois16, 16);
+    discrete_log_lfsr_lookup!(galois32_log_lookup, galois::Galois32, 256);
+    // 2^48 - 1 is divisible by 3^2
+    discrete_log_lfsr_lookup!(galois48_log_lookup, galois::Galois48, 1024);
+    discrete_log_lfsr_lookup!(galois64_log_lookup, galois::Galois64, 4096);
+    discrete_log_lfsr_lookup!(custom12_log_lookup, Custom12, 1, width = 12, taps = [12, 6, 4, 1]);
+    direct_lfsr_lookup!(
+        wide12_direct_lookup,
+        Wide12,
+        width = 12u64,
+        taps = [12, 6, 4, 1]
+    );
+    searching_lfsr_lookup!(
+        custom12_lookup,
+        Custom12,
+        100,
+        200,
+        10,
+        width = 12,
+        taps = [12, 6, 4, 1]
+    );
+    direct_lfsr_lookup!(custom12_direct_lookup, Custom12, width = 12, taps = [12, 6, 4, 1]);
+
     #[test]
     fn lookup_out_of_range() {
         let mut lfsr = galois::Galois32::default();
@@ -149,6 +325,492 @@ mod tests {
         assert_eq!(galois16_direct_lookup(&lfsr), 0);
     }
 
+    #[test]
+    fn lookup_custom() {
+        let mut lfsr = Custom12::default();
+
+        for ix in 0..4095 {
+            assert_eq!(custom12_direct_lookup(&lfsr), ix);
+            if (100..200).contains(&ix) {
+                assert_eq!(custom12_lookup(&lfsr), Some(ix));
+            } else if ix < 100 {
+                assert_eq!(custom12_lookup(&lfsr), None);
+            }
+            lfsr.inc();
+        }
+        assert_eq!(custom12_direct_lookup(&lfsr), 0);
+    }
+
+    #[test]
+    fn sequence_length_non_maximal() {
+        assert_eq!(NonMaximal4::default().sequence_length(), 6);
+        assert_eq!(galois::Galois32::default().sequence_length(), 4294967295);
+    }
+
+    #[test]
+    fn state_types() {
+        let _: u8 = galois::Galois8::default().state;
+        let _: u16 = galois::Galois9::default().state;
+        let _: u64 = galois::Galois64::default().state;
+        let _: u128 = galois::Galois65::default().state;
+        let _: u64 = Wide12::default().state;
+
+        assert_eq!(galois::Galois64::default().sequence_length(), u64::MAX);
+        assert_eq!(galois::Galois128::default().sequence_length(), u128::MAX);
+        assert_eq!(format!("{}", galois::Galois12::default()), "000000000001");
+    }
+
+    #[test]
+    fn lookup_wide() {
+        let mut lfsr = galois::Galois64::default();
+        for _ in 0..1234 {
+            lfsr.inc();
+        }
+        assert_eq!(galois64_lookup(&lfsr), Some(1234));
+
+        let mut lfsr = Wide12::default();
+        for _ in 0..1234 {
+            lfsr.inc();
+        }
+        assert_eq!(wide12_direct_lookup(&lfsr), 1234u64);
+    }
+
+    #[test]
+    fn fibonacci16_matches_reference() {
+        // https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Fibonacci_LFSRs
+        let mut reference: u16 = 0xACE1;
+        let mut lfsr = Fibonacci16::new(0xACE1);
+        for _ in 0..1000 {
+            let bit = (reference ^ (reference >> 2) ^ (reference >> 3) ^ (reference >> 5)) & 1;
+            reference = (reference >> 1) | (bit << 15);
+            lfsr.inc();
+            assert_eq!(lfsr.state, reference);
+        }
+    }
+
+    #[test]
+    fn fibonacci_differs_from_galois() {
+        assert_eq!(Fibonacci16::default().sequence_length(), 65535);
+        assert_eq!(FibonacciNonMaximal4::default().sequence_length(), 6);
+        assert_ne!(Fibonacci16::up(1), galois::Galois16::up(1));
+    }
+
+    #[test]
+    fn xnor_is_inverted_xor() {
+        let mut xor = galois::Galois16::default();
+        let mut xnor = GaloisXnor16::new(!xor.state);
+        for _ in 0..1000 {
+            xor.inc();
+            xnor.inc();
+            assert_eq!(xnor.state, !xor.state);
+        }
+        assert_eq!(GaloisXnor16::default().state, 0);
+        assert_eq!(GaloisXnor16::up(0xffff), 0xffff);
+        assert_eq!(fibonacci_xnor::FibonacciXnor8::up(0xff), 0xff);
+        assert_eq!(fibonacci_xnor::FibonacciXnor8::down(0xff), 0xff);
+        assert_eq!(GaloisXnorNonMaximal4::default().sequence_length(), 6);
+        assert_eq!(FibonacciXnorNonMaximal4::default().sequence_length(), 6);
+    }
+
+    #[test]
+    fn fibonacci_xnor16_matches_xapp052() {
+        // Q1 is the most significant bit, taps at Q16, Q15, Q13 and Q4
+        let mut reference: u16 = 0;
+        let mut lfsr = fibonacci_xnor::FibonacciXnor16::default();
+        for _ in 0..1000 {
+            let bit = !(reference ^ (reference >> 1) ^ (reference >> 3) ^ (reference >> 12)) & 1;
+            reference = (reference >> 1) | (bit << 15);
+            lfsr.inc();
+            assert_eq!(lfsr.state, reference);
+        }
+    }
+
+    #[test]
+    fn advance_in_const() {
+        const JUMPED: u32 = galois::Galois32::advance(1, 4_000_000_000);
+        const BACK: u32 = galois::Galois32::rewind(JUMPED, 4_000_000_000);
+        let tenth = galois::Galois32::rewind(JUMPED, 3_999_999_990);
+        assert_eq!(galois32_lookup(&galois::Galois32::new(tenth)), Some(10));
+        assert_eq!(BACK, 1);
+
+        let mut lfsr = galois::Galois128::default();
+        lfsr.advance(u128::MAX - 1);
+        lfsr.inc();
+        assert_eq!(lfsr.state, 1);
+    }
+
+    #[test]
+    fn lookup_discrete_log() {
+        let mut lfsr = galois::Galois16::default();
+        for position in 0..65535 {
+            if position % 97 == 0 {
+                assert_eq!(galois16_log_lookup(&lfsr), Some(position));
+            }
+            lfsr.inc();
+        }
+        assert_eq!(galois16_log_lookup(&galois::Galois16::new(0)), None);
+
+        for position in [0, 1, 10, 100_000_000, 4_294_967_294] {
+            let lfsr = galois::Galois32::new(galois::Galois32::advance(1, position));
+            assert_eq!(galois32_log_lookup(&lfsr), Some(position));
+        }
+        for position in [0, 1, 1 << 40, (1 << 48) - 2] {
+            let lfsr = galois::Galois48::new(galois::Galois48::advance(1, position));
+            assert_eq!(galois48_log_lookup(&lfsr), Some(position));
+        }
+        for position in [0, 12_345_678_901_234_567_890, u64::MAX - 1] {
+            let lfsr = galois::Galois64::new(galois::Galois64::advance(1, position));
+            assert_eq!(galois64_log_lookup(&lfsr), Some(position));
+        }
+        for position in [0, 1234, 4094] {
+            let lfsr = Custom12::new(Custom12::advance(1, position));
+            assert_eq!(custom12_log_lookup(&lfsr), Some(position));
+        }
+    }
+
+    #[test]
+    fn iterators() {
+        let mut lfsr = Fibonacci16::new(0xACE1);
+        let states: std::vec::Vec<u16> = (&mut lfsr).states().take(100).collect();
+        assert_eq!(states[0], 0xACE1);
+        assert_eq!(states[1], Fibonacci16::up(0xACE1));
+        assert_eq!(lfsr.state, Fibonacci16::advance(0xACE1, 100));
+        assert!((&mut lfsr).states_rev().take(100).eq(states.iter().rev().copied()));
+        assert_eq!(lfsr.state, 0xACE1);
+
+        assert!(Fibonacci16::new(0xACE1)
+            .bits(OutputBit::Lsb)
+            .zip(states.iter())
+            .all(|(bit, state)| bit == (state & 1 == 1)));
+        assert!(Fibonacci16::new(0xACE1)
+            .bits(OutputBit::Msb)
+            .zip(states.iter())
+            .all(|(bit, state)| bit == (state >> 15 == 1)));
+        // the output bit of a Fibonacci LFSR reaches the MSB `width - 1` steps earlier
+        assert!(Fibonacci16::new(0xACE1)
+            .bits(OutputBit::Lsb)
+            .skip(15)
+            .take(80)
+            .eq(Fibonacci16::new(0xACE1).bits(OutputBit::Msb).take(80)));
+
+        let mut lfsr = Fibonacci16::new(Fibonacci16::advance(0xACE1, 80));
+        assert!((&mut lfsr)
+            .bits_rev(OutputBit::Lsb)
+            .take(80)
+            .eq(states[..80].iter().rev().map(|state| state & 1 == 1)));
+    }
+
+    #[test]
+    fn packed_bits() {
+        let bits: std::vec::Vec<bool> = galois::Galois8::default()
+            .bits(OutputBit::Lsb)
+            .take(64)
+            .collect();
+        let bytes: std::vec::Vec<u8> = galois::Galois8::default()
+            .packed(OutputBit::Lsb, BitOrder::LsbFirst)
+            .take(8)
+            .collect();
+        let words: std::vec::Vec<u64> = galois::Galois8::default()
+            .packed(OutputBit::Lsb, BitOrder::MsbFirst)
+            .take(1)
+            .collect();
+        for (i, bit) in bits.iter().enumerate() {
+            assert_eq!((bytes[i / 8] >> (i % 8)) & 1 == 1, *bit);
+            assert_eq!((words[0] >> (63 - i)) & 1 == 1, *bit);
+        }
+
+        let mut lfsr = galois::Galois8::new(galois::Galois8::advance(1, 64));
+        let bytes_rev: std::vec::Vec<u8> = (&mut lfsr)
+            .packed_rev(OutputBit::Lsb, BitOrder::MsbFirst)
+            .take(8)
+            .collect();
+        assert_eq!(lfsr.state, 1);
+        // reversed bits, packed from the other end, make the same bytes in reverse order
+        assert!(bytes.iter().rev().eq(bytes_rev.iter()));
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn rand_core() {
+        use rand_core::{RngCore, SeedableRng};
+
+        let mut rng = galois::Galois32::from_seed(0x1234_5678u32.to_le_bytes());
+        assert_eq!(rng.state, 0x1234_5678);
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        let expected: std::vec::Vec<u8> = galois::Galois32::new(0x1234_5678)
+            .packed(OutputBit::Lsb, BitOrder::LsbFirst)
+            .take(16)
+            .collect();
+        assert_eq!(&bytes[..], &expected[..]);
+
+        let mut rng = galois::Galois32::new(0x1234_5678);
+        assert_eq!(rng.next_u32(), u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+        let mut high = [0u8; 8];
+        high.copy_from_slice(&bytes[4..12]);
+        assert_eq!(rng.next_u64(), u64::from_le_bytes(high));
+
+        // lock-up states are replaced with the default one, bits beyond the width are dropped
+        assert_eq!(galois::Galois32::from_seed([0; 4]).state, 1);
+        assert_eq!(fibonacci_xnor::FibonacciXnor8::from_seed([0xff]).state, 0);
+        assert_eq!(Custom12::from_seed([0xff, 0xff]).state, 0x0fff);
+        assert_eq!(Custom12::from_seed([0x00, 0xf0]).state, 1);
+
+        assert_eq!(
+            galois::Galois64::seed_from_u64(42).state,
+            galois::Galois64::seed_from_u64(42).state
+        );
+    }
+
+    #[test]
+    fn berlekamp_massey_recovers_taps() {
+        use crate::berlekamp_massey::berlekamp_massey;
+
+        let bits = galois::Galois32::default().bits(OutputBit::Lsb).take(100);
+        let synthesis = berlekamp_massey(bits).unwrap();
+        assert_eq!(synthesis.linear_complexity, 32);
+        assert!(synthesis.taps().eq(galois::Galois32::TAPS.iter().copied()));
+
+        let bits = Fibonacci16::new(0xACE1).bits(OutputBit::Lsb).take(100);
+        let synthesis = berlekamp_massey(bits).unwrap();
+        assert!(synthesis.taps().eq(Fibonacci16::TAPS.iter().copied()));
+        let mut lfsr = synthesis.lfsr().unwrap();
+        assert_eq!(lfsr.state, 0xACE1);
+        assert!((&mut lfsr)
+            .states()
+            .take(1000)
+            .eq(Fibonacci16::new(0xACE1).states().take(1000).map(u128::from)));
+
+        let bits = galois::Galois128::default().bits(OutputBit::Lsb).take(256);
+        let synthesis = berlekamp_massey(bits).unwrap();
+        assert_eq!(synthesis.linear_complexity, 128);
+        assert_eq!(synthesis.connection_polynomial().order(), u128::MAX);
+
+        // non-primitive polynomials are recovered as long as the state reaches all of their factors
+        let bits = FibonacciNonMaximal4::new(1).bits(OutputBit::Lsb).take(8);
+        let synthesis = berlekamp_massey(bits).unwrap();
+        assert!(synthesis.taps().eq([4, 2].iter().copied()));
+    }
+
+    #[test]
+    fn runtime_fibonacci() {
+        let mut runtime = FibonacciLfsr::new(16, Fibonacci16::TAPS, 0xACE1).unwrap();
+        let mut generated = Fibonacci16::new(0xACE1);
+        for _ in 0..1000 {
+            runtime.inc();
+            generated.inc();
+            assert_eq!(runtime.state, u128::from(generated.state));
+        }
+        runtime.advance(123_456);
+        generated.advance((123_456 % 65535) as u16);
+        assert_eq!(runtime.state, u128::from(generated.state));
+        runtime.rewind(1_000_000);
+        generated.rewind((1_000_000 % 65535) as u16);
+        assert_eq!(runtime.state, u128::from(generated.state));
+        assert_eq!(runtime.sequence_length(), 65535);
+        assert_eq!(FibonacciLfsr::new(4, &[4, 2], 1).unwrap().sequence_length(), 6);
+        assert_eq!(FibonacciLfsr::new(8, &[8, 6, 5, 4], 0).unwrap().sequence_length(), 1);
+
+        assert_eq!(FibonacciLfsr::new(0, &[], 1), Err(TapsError::Width(0)));
+        assert_eq!(FibonacciLfsr::new(8, &[8, 9], 1), Err(TapsError::Tap(9)));
+        assert_eq!(FibonacciLfsr::new(8, &[8, 4, 4], 1), Err(TapsError::DuplicateTap(4)));
+        assert_eq!(FibonacciLfsr::new(8, &[7, 4], 1), Err(TapsError::NoTapAtWidth));
+    }
+
+    /// Steps a runtime LFSR alongside a generated one with the same width and taps
+    macro_rules! runtime_matches_generated {
+        ($runtime:ty, $($generated:ty),*) => {
+            $({
+                let mut runtime = <$runtime>::new(<$generated>::WIDTH, <$generated>::TAPS, 1).unwrap();
+                let mut generated = <$generated>::new(1);
+                for _ in 0..1000 {
+                    runtime.inc();
+                    generated.inc();
+                    assert_eq!(runtime.state, generated.state as u128);
+                }
+                for _ in 0..500 {
+                    runtime.dec();
+                    generated.dec();
+                    assert_eq!(runtime.state, generated.state as u128);
+                }
+                let n = 12_345 % generated.sequence_length() as u128;
+                runtime.advance(12_345);
+                generated.advance(n as _);
+                assert_eq!(runtime.state, generated.state as u128);
+                runtime.rewind(12_345);
+                generated.rewind(n as _);
+                assert_eq!(runtime.state, generated.state as u128);
+                assert_eq!(runtime.sequence_length(), generated.sequence_length() as u128);
+            })*
+        };
+    }
+
+    #[test]
+    fn runtime_galois() {
+        use galois::*;
+        runtime_matches_generated!(
+            GaloisLfsr, Galois3, Galois8, Galois16, Galois32, Galois64, Galois100, Galois128
+        );
+        runtime_matches_generated!(GaloisLfsr, Custom12, NonMaximal4);
+        runtime_matches_generated!(FibonacciLfsr, Fibonacci8, Fibonacci16, FibonacciNonMaximal4);
+
+        // x^2 + x + 1 divides the feedback polynomial (x^2 + x + 1)^2
+        let short = GaloisLfsr::new(4, &[4, 2], 0b0111).unwrap();
+        assert_eq!(short.sequence_length(), 3);
+        assert_eq!(short.states().skip(1).position(|state| state == 0b0111), Some(2));
+        assert_eq!(GaloisLfsr::new(4, &[3], 1), Err(TapsError::NoTapAtWidth));
+    }
+
+    #[test]
+    fn nth_primitive() {
+        use gf2::{primitive_polynomials, Poly};
+
+        assert_eq!(FirstPrimitive10::TAPS, &[10, 3]);
+        assert_eq!(LastPrimitive10::TAPS, &[10, 9, 8, 7, 6, 5, 4, 3]);
+        assert_eq!(
+            Poly::from_taps(SecondPrimitive12::TAPS),
+            primitive_polynomials(12).nth(1).unwrap()
+        );
+        assert_eq!(FirstPrimitive10::default().sequence_length(), 1023);
+        assert_eq!(SecondPrimitive12::default().sequence_length(), 4095);
+        assert!(taps::TWO_TAPS[10].contains(&[10, 3]));
+    }
+
+    #[test]
+    fn sequence_length_argument() {
+        assert_eq!(Unordered8::TAPS, &[4, 5, 6, 8]);
+        assert_eq!(Unordered8::default().sequence_length(), 255);
+        assert!(Unordered8::default()
+            .states()
+            .take(300)
+            .eq(Positional8::default().states().take(300)));
+        assert_eq!(Cyclic4::TAPS, &[4]);
+        assert_eq!(Cyclic4::default().sequence_length(), 4);
+    }
+
+    #[test]
+    fn set_state_drops_bits_beyond_width() {
+        let mut generated = Custom12::default();
+        generated.set_state(0xffff);
+        assert_eq!(generated.state, 0x0fff);
+        let mut wide = Wide12::default();
+        wide.set_state(u64::MAX);
+        assert_eq!(wide.state, 0x0fff);
+        let mut runtime = GaloisLfsr::new(12, Custom12::TAPS, 1).unwrap();
+        runtime.set_state(0xffff);
+        assert_eq!(runtime.state, 0x0fff);
+    }
+
+    #[test]
+    fn named_taps() {
+        assert_eq!(Koopman8::TAPS, &[8, 6, 5, 4]);
+        assert_eq!(Polynomial8::TAPS, Koopman8::TAPS);
+        assert_eq!(Normal8::TAPS, Koopman8::TAPS);
+        assert_eq!(Reversed8::TAPS, Koopman8::TAPS);
+        assert_eq!(Reciprocal8::TAPS, Koopman8::TAPS);
+        assert_eq!(NamedTaps8::TAPS, Koopman8::TAPS);
+        assert_eq!(Koopman32::TAPS, galois::Galois32::TAPS);
+        assert_eq!(FibonacciPolynomial16::TAPS, Fibonacci16::TAPS);
+
+        let mut positional = Positional8::default();
+        let mut koopman = Koopman8::default();
+        for _ in 0..255 {
+            positional.inc();
+            koopman.inc();
+            assert_eq!(koopman.get_state(), positional.get_state());
+        }
+        assert_eq!(Polynomial8::default().sequence_length(), 255);
+        assert_eq!(Koopman32::default().sequence_length(), u32::MAX);
+    }
+
+    #[test]
+    fn signature_analysers() {
+        // with nothing fed in they step like the LFSR with the same taps
+        let mut lfsr = Positional8::new(0x5A);
+        let mut misr = Misr8::new(0x5A);
+        for _ in 0..300 {
+            lfsr.inc();
+            misr.feed(0);
+            assert_eq!(misr.signature(), lfsr.get_state());
+        }
+
+        const GOLDEN: u16 = Misr16::signature_of(0, &[0x1234, 0x5678, 0x9abc]);
+        let mut misr = Misr16::default();
+        misr.feed_all([0x1234, 0x5678, 0x9abc]);
+        assert_eq!(misr.signature(), GOLDEN);
+        assert_eq!(format!("{}", misr).len(), 16);
+        misr.reset(0);
+        misr.feed_all([0x1234, 0x5679, 0x9abc]);
+        assert_ne!(misr.signature(), GOLDEN);
+
+        // a bit fed into a SISR is a word of the taps fed into a MISR
+        let bits = [true, false, true, true, false, false, true, false, true];
+        let mut misr = Misr8::default();
+        for bit in bits.iter() {
+            misr.feed(if *bit { 0xB8 } else { 0 });
+        }
+        assert_eq!(Sisr8::signature_of(0, &bits), misr.signature());
+
+        let mut msb_first = Sisr32::default();
+        msb_first.feed_bytes(b"\xA5\x01", BitOrder::MsbFirst);
+        let mut lsb_first = Sisr32::default();
+        lsb_first.feed_bytes(b"\xA5\x80", BitOrder::LsbFirst);
+        let mut bits = Sisr32::default();
+        bits.feed_all((0..16).map(|i| (0xA501u16 >> (15 - i)) & 1 == 1));
+        assert_eq!(msb_first.signature(), bits.signature());
+        assert_eq!(lsb_first.signature(), bits.signature());
+
+        assert_eq!(Misr16::aliasing_probability(1), 0.0);
+        assert_eq!(Misr16::aliasing_probability(2), 65535.0 / 4294967295.0);
+        assert_eq!(Sisr32::aliasing_probability(32), 0.0);
+        assert!((Sisr32::aliasing_probability(1 << 40) - 1.0 / 4294967296.0).abs() < 1e-20);
+    }
+
+    #[test]
+    fn galois_instances_match_fibonacci_references() {
+        let galois = GaloisLfsr::new(32, galois::Galois32::TAPS, 0xDEADBEEF).unwrap();
+        let reference = Reference32::new(galois.fibonacci_state(galois.state) as u32);
+        let galois_bits = galois::Galois32::new(0xDEADBEEF).bits(OutputBit::Lsb);
+        assert!(galois_bits.take(2000).eq(reference.bits(OutputBit::Lsb).take(2000)));
+
+        // from their default states `Fibonacci16` is this much behind `galois::Galois16`
+        let galois = GaloisLfsr::new(16, galois::Galois16::TAPS, 1).unwrap();
+        let fibonacci = FibonacciLfsr::new(16, Fibonacci16::TAPS, 1).unwrap();
+        let steps = convert::alignment(&galois, &fibonacci, 65535).unwrap();
+        let mut reference = Fibonacci16::default();
+        reference.advance(steps as u16);
+        let galois_bits = galois::Galois16::default().bits(OutputBit::Lsb);
+        assert!(galois_bits.take(2000).eq(reference.bits(OutputBit::Lsb).take(2000)));
+
+        // the same from the positions of both Galois states
+        let galois_position = galois16_log_lookup(&galois::Galois16::default()).unwrap();
+        let fibonacci_position =
+            galois16_log_lookup(&galois::Galois16::new(fibonacci.to_galois().state as u16)).unwrap();
+        assert_eq!(
+            (u128::from(galois_position) + 65535 - u128::from(fibonacci_position)) % 65535,
+            steps
+        );
+    }
+
+    #[test]
+    fn generated_crcs() {
+        const CHECK: u32 = Crc32::checksum(b"123456789");
+        assert_eq!(CHECK, 0xCBF43926);
+        let mut crc = Crc32::new();
+        crc.update(b"1234");
+        crc.update(b"56789");
+        assert_eq!(crc.finalize(), CHECK);
+
+        assert_eq!(Crc16Xmodem::checksum(b"123456789"), 0x31c3);
+        assert_eq!(Crc8Rohc::CHECK, crc::CRC_8_ROHC.check as u8);
+        assert_eq!(Crc5Usb::checksum(b"123456789"), 0x19);
+        assert_eq!(Crc12Umts::checksum(b"123456789"), 0xdaf);
+        assert_eq!(Crc3Gsm::checksum(b"123456789"), 0x4);
+        assert_eq!(Crc64Xz::checksum(b"123456789"), 0x995dc9bbdf1939fa);
+        assert_eq!(Crc82Darc::checksum(b"123456789"), 0x09ea83f625023801fd612);
+        assert_eq!(Crc32::TABLE[1], 0x77073096);
+    }
+
     #[test]
     fn galois32_first_states() {
         let mut lfsr = galois::Galois32::default();
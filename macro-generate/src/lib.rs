@@ -4,31 +4,150 @@ extern crate proc_macro;
 
 use itertools::Itertools;
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
+use proc_macro2::{Literal, Span};
+use quote::{quote, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::parse_macro_input;
-use syn::IntSuffix;
 use syn::Token;
 
-fn galois_mask(taps: &[u32]) -> u32 {
-    let mut ret: u32 = 0;
+use lfsr_base::arguments::Arguments;
+use lfsr_base::crc::{CrcEngine, CrcParams, CrcTable};
+use lfsr_base::gf2::{primitive_polynomials, Poly};
+
+/// Sets bits at the given 0-based positions
+fn bit_mask(taps: &[u32]) -> u128 {
+    let mut ret: u128 = 0;
     for tap in taps {
         ret |= 1 << *tap;
     }
     ret
 }
 
-fn galois_inverse_mask(forward_mask: u32, width: u32) -> u32 {
+fn galois_inverse_mask(forward_mask: u128, width: u32) -> u128 {
     (forward_mask << 1) | ((forward_mask >> (width - 1)) & 1)
 }
 
+/// The state type is the smallest unsigned integer that fits `width` bits,
+/// unless the width literal has an explicit suffix, as in `24u64`
+fn state_type(width_lit: &syn::LitInt, width: u32) -> syn::Result<syn::Ident> {
+    let bits = match width_lit.suffix() {
+        "" => [8, 16, 32, 64, 128]
+            .iter()
+            .copied()
+            .find(|bits| *bits >= width)
+            .unwrap_or(128),
+        "u8" => 8,
+        "u16" => 16,
+        "u32" => 32,
+        "u64" => 64,
+        "u128" => 128,
+        _ => {
+            return Err(syn::Error::new(
+                width_lit.span(),
+                "state type has to be one of u8, u16, u32, u64 or u128",
+            ))
+        }
+    };
+    if bits < width {
+        return Err(syn::Error::new(
+            width_lit.span(),
+            format!("{} bits don't fit into u{}", width, bits),
+        ));
+    }
+    Ok(syn::Ident::new(&format!("u{}", bits), Span::call_site()))
+}
+
 #[derive(Debug)]
 struct LFSRInput {
     name: String,
     width: u32,
-    sequence_length: u32,
+    state_type: syn::Ident,
     taps: Vec<u32>,
+    width_lit: syn::LitInt,
+    /// Explicit sequence length and its tokens
+    sequence_length: Option<(u128, proc_macro2::TokenStream)>,
+    /// Tokens every tap comes from, the whole notation for named taps
+    taps_tokens: Vec<proc_macro2::TokenStream>,
+}
+
+impl LFSRInput {
+    /// Feedback polynomial in the notation of the tap tables: `1 + sum(x^tap)`
+    fn polynomial(&self) -> Poly {
+        Poly::from_taps(&self.taps)
+    }
+
+    fn state_mask(&self) -> u128 {
+        u128::MAX >> (128 - self.width)
+    }
+
+    /// Rejects taps that don't describe a `width`-bit LFSR and explicit sequence lengths
+    /// different from the actual period, returns the period otherwise
+    fn sequence_length(&self, kind: Kind, feedback: Feedback) -> syn::Result<u128> {
+        if self.width < 2 || self.width > 128 {
+            return Err(syn::Error::new(
+                self.width_lit.span(),
+                "width has to be between 2 and 128 bits",
+            ));
+        }
+
+        for (ix, (tap, tokens)) in self.taps.iter().zip(&self.taps_tokens).enumerate() {
+            if *tap < 1 || *tap > self.width {
+                return Err(syn::Error::new_spanned(
+                    tokens,
+                    format!("tap has to be between 1 and {}", self.width),
+                ));
+            }
+            if self.taps[..ix].contains(tap) {
+                return Err(syn::Error::new_spanned(tokens, "duplicate tap"));
+            }
+        }
+
+        if !self.taps.contains(&self.width) {
+            return Err(self.taps_error(format!("one of the taps has to be {}", self.width)));
+        }
+
+        let max_sequence_length = self.state_mask();
+        let polynomial = self.polynomial();
+        let initial_state = feedback.xor_equivalent(feedback.default_state(), self.state_mask());
+        let state_polynomial = kind.state_polynomial(polynomial, initial_state, self.width);
+        let period = (polynomial / polynomial.gcd(state_polynomial)).order();
+        if let Some((sequence_length, tokens)) = &self.sequence_length {
+            let sequence_length = *sequence_length;
+            if sequence_length == max_sequence_length && period != max_sequence_length {
+                return Err(self.taps_error(format!(
+                    "`{}` is not primitive, sequence length would be {} instead of {}",
+                    self.polynomial(),
+                    period,
+                    max_sequence_length
+                )));
+            }
+            if sequence_length != period && sequence_length <= u128::from(self.width) {
+                return Err(syn::Error::new_spanned(
+                    tokens,
+                    format!(
+                        "sequence length of this LFSR is {}, taps without a sequence length \
+                         have to start with {}",
+                        period, self.width
+                    ),
+                ));
+            }
+            if sequence_length != period {
+                return Err(syn::Error::new_spanned(
+                    tokens,
+                    format!("sequence length of this LFSR is {}", period),
+                ));
+            }
+        }
+
+        Ok(period)
+    }
+
+    fn taps_error(&self, message: String) -> syn::Error {
+        match (self.taps_tokens.first(), self.taps_tokens.last()) {
+            (Some(first), Some(last)) => syn::Error::new_spanned(quote!(#first #last), message),
+            _ => syn::Error::new(Span::call_site(), message),
+        }
+    }
 }
 
 impl Parse for LFSRInput {
@@ -36,76 +155,352 @@ impl Parse for LFSRInput {
         let name = input.parse::<syn::Ident>()?.to_string();
         input.parse::<Token![,]>()?;
 
-        let width = input.parse::<syn::LitInt>()?.value() as u32;
-        input.parse::<Token![,]>()?;
+        let width_lit = input.parse::<syn::LitInt>()?;
+        let width = width_lit.base10_parse::<u32>()?;
+        let state_type = state_type(&width_lit, width)?;
 
-        let sequence_length = input.parse::<syn::LitInt>()?.value() as u32;
-        input.parse::<Token![,]>()?;
+        // the width and the arguments after it, split on the commas outside of groups
+        let mut arguments = vec![width_lit.to_token_stream()];
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let mut argument = proc_macro2::TokenStream::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                argument.extend(Some(input.parse::<proc_macro2::TokenTree>()?));
+            }
+            arguments.push(argument);
+        }
 
-        let taps = input
-            .parse_terminated::<syn::LitInt, Token![,]>(syn::LitInt::parse)?
-            .iter()
-            .map(|l| l.value() as u32)
-            .collect_vec();
+        let texts = arguments.iter().map(ToString::to_string).collect_vec();
+        let texts = texts.iter().map(String::as_str).collect_vec();
+        let parsed = Arguments::parse(&texts).map_err(|err| {
+            syn::Error::new_spanned(&arguments[err.argument], err.to_string())
+        })?;
+        let taps_tokens = (0..parsed.taps().len())
+            .map(|ix| arguments[parsed.tap_argument(ix)].clone())
+            .collect();
+        let sequence_length = parsed
+            .sequence_length
+            .zip(parsed.sequence_length_argument)
+            .map(|(sequence_length, ix)| (sequence_length, arguments[ix].clone()));
 
         Ok(Self {
             name,
             width,
+            state_type,
+            taps: parsed.taps().to_vec(),
+            width_lit,
             sequence_length,
-            taps,
+            taps_tokens,
         })
     }
 }
 
+/// Generates a Galois LFSR: `galois_lfsr!(Name, width, [sequence_length,] taps...)`
+///
+/// Taps are 1-based, as in tables of maximum length LFSRs, and one of them has to be `width`.
+/// Sequence length is the period of the feedback polynomial `1 + x^tap + ...`, it's computed
+/// at compile time. If it's given explicitly it's checked against the computed one, so a claimed
+/// `2^width - 1` fails to compile unless the polynomial is primitive.
+///
+/// Without a sequence length the taps have to start with `width`. The first number after the width
+/// is the sequence length if it isn't `width`, or if `width` comes again among the taps:
+///
+/// ```ignore
+/// galois_lfsr!(Name, 8, 8, 6, 5, 4); // taps 8, 6, 5, 4
+/// galois_lfsr!(Name, 8, 255, 4, 5, 6, 8); // sequence length 255, taps in any order
+/// galois_lfsr!(Name, 8, 4, 5, 6, 8); // sequence length 4, fails to compile
+/// galois_lfsr!(Name, 4, 4, 4); // sequence length 4, tap 4
+/// ```
+///
+/// Instead of the list the taps can be given by name in one of the usual notations, these are all
+/// the same as `galois_lfsr!(Name, 8, 8, 6, 5, 4)`:
+///
+/// ```ignore
+/// galois_lfsr!(Name, 8, taps = [8, 6, 5, 4]);
+/// galois_lfsr!(Name, 8, polynomial = x^8 + x^6 + x^5 + x^4 + 1);
+/// galois_lfsr!(Name, 8, koopman = 0xB8); // bit i is x^(i+1), + 1 implicit
+/// galois_lfsr!(Name, 8, normal = 0x71); // bit i is x^i, x^8 implicit
+/// galois_lfsr!(Name, 8, reversed = 0x8E); // normal with the 8 bits reversed
+/// galois_lfsr!(Name, 8, 255, reciprocal = 0x1D); // normal of x^8 + x^4 + x^3 + x^2 + 1
+/// ```
+///
+/// The polynomial has to be of degree `width` with a `+ 1`, the taps end up sorted from `width` down.
+///
+/// Width is up to 128 bits. The state is stored in the smallest of `u8`, `u16`, `u32`, `u64`
+/// and `u128` that fits it, a suffix on the width selects a wider type: `galois_lfsr!(Name, 24u64, ...)`.
+///
+/// Besides `up` and `down` there are `up8`, `up16`, `up32` and `up64` that make that many steps at once
+/// with a table lookup per 8 steps and return their output bits packed together, the first one in
+/// the least significant bit, for `lfsr/benches/leap_forward.rs` that's about 4 times faster than `inc()`.
+///
+/// With the `rand_core` feature all the generated LFSRs also implement `RngCore` and `SeedableRng`,
+/// the way they use the output bits is described in `lfsr_base::rng`.
 #[proc_macro]
 pub fn galois_lfsr(input: TokenStream) -> TokenStream {
     let input: LFSRInput = parse_macro_input!(input as LFSRInput);
-    let name = syn::Ident::new(&input.name, Span::call_site());
-    let tests_mod_name = syn::Ident::new(
-        format!("tests_{}", &input.name.to_lowercase()).as_str(),
-        Span::call_site(),
-    );
-    let width = syn::LitInt::new(input.width as u64, IntSuffix::U32, Span::call_site());
-    let sequence_length = syn::LitInt::new(
-        input.sequence_length as u64,
-        IntSuffix::U32,
-        Span::call_site(),
-    );
+    generate_lfsr(input, Kind::Galois, Feedback::Xor)
+}
 
-    let taps_str = &input.taps.iter().map(ToString::to_string).join(", ");
-    let fwd_mask_u32 = galois_mask(input.taps.iter().map(|t| t - 1).collect_vec().as_slice());
-    let fwd_mask = syn::LitInt::new(fwd_mask_u32 as u64, IntSuffix::U32, Span::call_site());
-    let inv_mask = syn::LitInt::new(
-        galois_inverse_mask(fwd_mask_u32, input.width) as u64,
-        IntSuffix::U32,
-        Span::call_site(),
-    );
+/// Generates a Galois LFSR with XNOR feedback: `galois_xnor_lfsr!(Name, width, [sequence_length,] taps...)`
+///
+/// Arguments are the same as in `galois_lfsr!`. The state is the inverse of
+/// the state of the same XOR LFSR, so all ones is the lock-up state and the default state is `0`.
+#[proc_macro]
+pub fn galois_xnor_lfsr(input: TokenStream) -> TokenStream {
+    let input: LFSRInput = parse_macro_input!(input as LFSRInput);
+    generate_lfsr(input, Kind::Galois, Feedback::Xnor)
+}
 
-    let struct_comment = format!(r#"
-        {}-bit [Galois LFSR](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Galois_LFSRs) with taps at {}.
-        Sequence length is {}.
-        `0` is a lock-up state.
-    "#,
-        input.width, taps_str, input.sequence_length);
+/// Generates a Fibonacci LFSR: `fibonacci_lfsr!(Name, width, [sequence_length,] taps...)`
+///
+/// Arguments are the same as in `galois_lfsr!`, and so is the sequence length.
+/// The state shifts towards the least significant bit, which is the output,
+/// and the feedback bit, XOR of the tapped bits, is shifted in as the most significant one.
+/// Tap `t` is bit `width - t`, as in tables of maximum length LFSRs.
+#[proc_macro]
+pub fn fibonacci_lfsr(input: TokenStream) -> TokenStream {
+    let input: LFSRInput = parse_macro_input!(input as LFSRInput);
+    generate_lfsr(input, Kind::Fibonacci, Feedback::Xor)
+}
 
-    let expanded = quote! {
-                #[doc=#struct_comment]
-                pub struct #name {
-                    pub state: u32,
-                }
+/// Generates a Fibonacci LFSR with XNOR feedback: `fibonacci_xnor_lfsr!(Name, width, [sequence_length,] taps...)`
+///
+/// Arguments are the same as in `fibonacci_lfsr!`. The feedback bit is XNOR of the tapped bits,
+/// like in [XAPP052](https://docs.xilinx.com/v/u/en-US/xapp052) counters, with tap 1 being
+/// the most significant bit. All ones is the lock-up state and the default state is `0`.
+#[proc_macro]
+pub fn fibonacci_xnor_lfsr(input: TokenStream) -> TokenStream {
+    let input: LFSRInput = parse_macro_input!(input as LFSRInput);
+    generate_lfsr(input, Kind::Fibonacci, Feedback::Xnor)
+}
 
-                impl #name {
-                    pub const fn new(initial_state: u32) -> Self {
-                        Self {
-                            state: initial_state,
-                        }
+/// `Name, width, n`, taps are those of the `n`-th primitive polynomial of degree `width`
+struct NthPrimitiveInput(LFSRInput);
+
+impl Parse for NthPrimitiveInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?.to_string();
+        input.parse::<Token![,]>()?;
+
+        let width_lit = input.parse::<syn::LitInt>()?;
+        let width = width_lit.base10_parse::<u32>()?;
+        let state_type = state_type(&width_lit, width)?;
+        input.parse::<Token![,]>()?;
+
+        let n_lit = input.parse::<syn::LitInt>()?;
+        let n = n_lit.base10_parse::<usize>()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        if !(2..=128).contains(&width) {
+            return Err(syn::Error::new(
+                width_lit.span(),
+                "width has to be between 2 and 128 bits",
+            ));
+        }
+        let taps = primitive_polynomials(width)
+            .nth(n)
+            .ok_or_else(|| {
+                syn::Error::new(
+                    n_lit.span(),
+                    format!("there are fewer than {} primitive polynomials of degree {}", n + 1, width),
+                )
+            })?
+            .taps()
+            .collect_vec();
+        let taps_tokens = vec![n_lit.to_token_stream(); taps.len()];
+
+        Ok(Self(LFSRInput {
+            name,
+            width,
+            state_type,
+            taps,
+            width_lit,
+            sequence_length: None,
+            taps_tokens,
+        }))
+    }
+}
+
+/// Generates a maximum length Galois LFSR from the `n`-th primitive polynomial of degree `width`:
+/// `nth_primitive_galois_lfsr!(Name, width, n)`
+///
+/// `n` is 0-based and counts in the order of `lfsr_base::gf2::primitive_polynomials`, so different `n`
+/// give different sequences of the same length. Polynomials are enumerated at compile time, which is fast
+/// for small `n` but gets slow in the thousands for wide LFSRs.
+/// The generated LFSR is the same as the one `galois_lfsr!` makes out of the taps in its `TAPS`.
+#[proc_macro]
+pub fn nth_primitive_galois_lfsr(input: TokenStream) -> TokenStream {
+    let NthPrimitiveInput(input) = parse_macro_input!(input as NthPrimitiveInput);
+    generate_lfsr(input, Kind::Galois, Feedback::Xor)
+}
+
+/// Generates a maximum length Fibonacci LFSR from the `n`-th primitive polynomial of degree `width`:
+/// `nth_primitive_fibonacci_lfsr!(Name, width, n)`
+///
+/// Arguments are the same as in `nth_primitive_galois_lfsr!`, the taps follow `fibonacci_lfsr!`.
+#[proc_macro]
+pub fn nth_primitive_fibonacci_lfsr(input: TokenStream) -> TokenStream {
+    let NthPrimitiveInput(input) = parse_macro_input!(input as NthPrimitiveInput);
+    generate_lfsr(input, Kind::Fibonacci, Feedback::Xor)
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Galois,
+    Fibonacci,
+}
+
+#[derive(Clone, Copy)]
+enum Feedback {
+    Xor,
+    Xnor,
+}
+
+impl Feedback {
+    fn default_state(self) -> u128 {
+        match self {
+            Feedback::Xor => 1,
+            Feedback::Xnor => 0,
+        }
+    }
+
+    /// State of the XOR LFSR that steps through the same states, inverted for XNOR
+    fn xor_equivalent(self, state: u128, state_mask: u128) -> u128 {
+        match self {
+            Feedback::Xor => state,
+            Feedback::Xnor => !state & state_mask,
+        }
+    }
+}
+
+impl Kind {
+    fn macro_name(self, feedback: Feedback) -> &'static str {
+        match (self, feedback) {
+            (Kind::Galois, Feedback::Xor) => "galois_lfsr!",
+            (Kind::Galois, Feedback::Xnor) => "galois_xnor_lfsr!",
+            (Kind::Fibonacci, Feedback::Xor) => "fibonacci_lfsr!",
+            (Kind::Fibonacci, Feedback::Xnor) => "fibonacci_xnor_lfsr!",
+        }
+    }
+
+    /// Polynomial `p` such that the sequence starting at XOR LFSR `state` has the period
+    /// of `polynomial / gcd(polynomial, p)`
+    fn state_polynomial(self, polynomial: Poly, state: u128, width: u32) -> Poly {
+        match self {
+            // the state is a polynomial modulo the feedback one, `up` divides it by `x`
+            Kind::Galois => Poly::from(state),
+            // the state holds the next `width` output bits, its generating function
+            // has this numerator
+            Kind::Fibonacci => polynomial * Poly::from(state) % Poly::monomial(width),
+        }
+    }
+
+    fn link(self) -> &'static str {
+        match self {
+            Kind::Galois => "[Galois LFSR](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Galois_LFSRs)",
+            Kind::Fibonacci => "[Fibonacci LFSR](https://en.wikipedia.org/wiki/Linear-feedback_shift_register#Fibonacci_LFSRs)",
+        }
+    }
+
+    /// `const fn up` and `const fn down`, `const fn advance` and `const fn rewind` to make many steps at once
+    fn step_functions(self, input: &LFSRInput, feedback: Feedback) -> proc_macro2::TokenStream {
+        let state_type = &input.state_type;
+        let (up, down) = self.xor_step_bodies(input);
+
+        let steps = match feedback {
+            Feedback::Xor => quote! {
+                    pub const fn up(prev_state: #state_type) -> #state_type #up
+
+                    pub const fn down(prev_state: #state_type) -> #state_type #down
+            },
+            Feedback::Xnor => {
+                let state_mask = Literal::u128_unsuffixed(input.state_mask());
+                quote! {
+                    pub const fn up(prev_state: #state_type) -> #state_type {
+                        // XNOR feedback steps like XOR feedback on the inverted state
+                        let prev_state = !prev_state & #state_mask;
+                        let state = #up;
+                        !state & #state_mask
                     }
 
-                    const fn sequence_length() -> u32 {
-                        #sequence_length
+                    pub const fn down(prev_state: #state_type) -> #state_type {
+                        let prev_state = !prev_state & #state_mask;
+                        let state = #down;
+                        !state & #state_mask
                     }
+                }
+            }
+        };
+
+        // `up` multiplies by `x^-1` modulo the feedback polynomial (Galois) or shifts out
+        // a sequence with that polynomial's reciprocal as the characteristic one (Fibonacci),
+        // either way the reciprocal polynomial annihilates `up` and the polynomial itself
+        // annihilates `down`
+        let width = input.width;
+        let up_polynomial = input
+            .taps
+            .iter()
+            .filter(|tap| **tap != width)
+            .fold(1u128, |low, tap| low | (1 << (width - tap)));
+        let down_polynomial = input
+            .taps
+            .iter()
+            .filter(|tap| **tap != width)
+            .fold(1u128, |low, tap| low | (1 << tap));
+        let advance = jump_function(
+            input,
+            feedback,
+            &syn::Ident::new("advance", Span::call_site()),
+            "State `n` steps after `state`, same as `n` calls to `up`",
+            up_polynomial,
+            &up,
+        );
+        let rewind = jump_function(
+            input,
+            feedback,
+            &syn::Ident::new("rewind", Span::call_site()),
+            "State `n` steps before `state`, same as `n` calls to `down`",
+            down_polynomial,
+            &down,
+        );
 
-                    pub const fn up(prev_state: u32) -> u32 {
+        let leap = match (self, feedback) {
+            (Kind::Galois, Feedback::Xor) => leap_functions(input),
+            _ => quote! {},
+        };
+
+        quote! {
+                    #steps
+
+                    #advance
+
+                    #rewind
+
+                    #leap
+        }
+    }
+
+    /// Bodies of `up` and `down` with XOR feedback, as blocks using `prev_state`
+    fn xor_step_bodies(
+        self,
+        input: &LFSRInput,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let width = input.width;
+        let state_type = &input.state_type;
+
+        match self {
+            Kind::Galois => {
+                let fwd_mask_u128 =
+                    bit_mask(input.taps.iter().map(|t| t - 1).collect_vec().as_slice());
+                let fwd_mask = Literal::u128_unsuffixed(fwd_mask_u128);
+                let inv_mask =
+                    Literal::u128_unsuffixed(galois_inverse_mask(fwd_mask_u128, input.width));
+
+                let up = quote! {
+                    {
                         let mut state = prev_state;
                         let lsb = state & 1;
                         state >>= 1;
@@ -116,8 +511,9 @@ pub fn galois_lfsr(input: TokenStream) -> TokenStream {
     //                    }
                         state
                     }
-
-                    pub const fn down(prev_state: u32) -> u32 {
+                };
+                let down = quote! {
+                    {
                         let mut state = prev_state;
                         let msb = state >> (#width - 1);
                         state <<= 1;
@@ -128,28 +524,338 @@ pub fn galois_lfsr(input: TokenStream) -> TokenStream {
     //                    }
                         state
                     }
+                };
+                (up, down)
+            }
+            Kind::Fibonacci => {
+                let feedback_mask = Literal::u128_unsuffixed(bit_mask(
+                    input.taps.iter().map(|t| input.width - t).collect_vec().as_slice(),
+                ));
+                let inv_mask = Literal::u128_unsuffixed(bit_mask(
+                    input
+                        .taps
+                        .iter()
+                        .filter(|t| **t != input.width)
+                        .map(|t| input.width - t - 1)
+                        .collect_vec()
+                        .as_slice(),
+                ));
+                let state_mask = Literal::u128_unsuffixed(input.state_mask());
+
+                let up = quote! {
+                    {
+                        let feedback = ((prev_state & #feedback_mask).count_ones() & 1) as #state_type;
+                        (prev_state >> 1) | (feedback << (#width - 1))
+                    }
+                };
+                let down = quote! {
+                    {
+                        // the bit that was shifted out is the only one in the feedback
+                        // that isn't in the current state anymore
+                        let msb = prev_state >> (#width - 1);
+                        let lsb = (msb ^ ((prev_state & #inv_mask).count_ones() & 1) as #state_type) & 1;
+                        ((prev_state << 1) | lsb) & #state_mask
+                    }
+                };
+                (up, down)
+            }
+        }
+    }
+}
+
+/// `const fn` that makes `n` steps at once: `n` steps are the same linear map as `x^n`
+/// modulo `x^width + low` evaluated at the step, which has only `width` terms
+fn jump_function(
+    input: &LFSRInput,
+    feedback: Feedback,
+    name: &syn::Ident,
+    comment: &str,
+    low: u128,
+    xor_step: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let width = input.width;
+    let state_type = &input.state_type;
+    let low = Literal::u128_unsuffixed(low);
+    let state_mask = Literal::u128_unsuffixed(input.state_mask());
+    let (to_xor, from_xor) = match feedback {
+        Feedback::Xor => (quote! {}, quote! { ret }),
+        Feedback::Xnor => (
+            quote! { let state = !state & #state_mask; },
+            quote! { !ret & #state_mask },
+        ),
+    };
+
+    quote! {
+                    #[doc=#comment]
+                    pub const fn #name(state: #state_type, n: #state_type) -> #state_type {
+                        let powers = lfsr_base::gf2::x_pow_mod(n as u128, #width, #low);
+                        #to_xor
+                        let mut ret: #state_type = 0;
+                        let mut power = #width;
+                        while power > 0 {
+                            power -= 1;
+                            ret = {
+                                let prev_state = ret;
+                                #xor_step
+                            };
+                            if (powers >> power) & 1 == 1 {
+                                ret ^= state;
+                            }
+                        }
+                        #from_xor
+                    }
+    }
+}
+
+/// `const fn up8`, `up16`, `up32` and `up64` that make as many steps of a Galois XOR LFSR at once
+/// and return the output bits of all of them, with a 256-entry table built at compile time
+///
+/// `up` is linear, so 8 steps from `state` are 8 steps from its low byte XORed with 8 steps
+/// from the rest, and the latter only shift since their output bits are all zeros.
+fn leap_functions(input: &LFSRInput) -> proc_macro2::TokenStream {
+    let state_type = &input.state_type;
+    let high = if state_type == "u8" {
+        quote! { 0 }
+    } else {
+        quote! { state >> 8 }
+    };
+    let leap = |bits: u32, half: u32| {
+        let name = syn::Ident::new(&format!("up{}", bits), Span::call_site());
+        let half_name = syn::Ident::new(&format!("up{}", half), Span::call_site());
+        let output_type = syn::Ident::new(&format!("u{}", bits), Span::call_site());
+        let comment = format!(
+            "Same as {} calls to `up`, returns the state after them and their output bits, see `up8`",
+            bits
+        );
+        quote! {
+                    #[doc=#comment]
+                    pub const fn #name(state: #state_type) -> (#state_type, #output_type) {
+                        let (state, low) = Self::#half_name(state);
+                        let (state, high) = Self::#half_name(state);
+                        (state, low as #output_type | (high as #output_type) << #half)
+                    }
+        }
+    };
+    let up16 = leap(16, 8);
+    let up32 = leap(32, 16);
+    let up64 = leap(64, 32);
+
+    quote! {
+                    /// States 8 steps after every low byte of the state and the output bits of those steps
+                    const LEAP8: [(#state_type, u8); 256] = {
+                        let mut table = [(0, 0); 256];
+                        let mut low = 0;
+                        while low < 256 {
+                            let mut state = low as #state_type;
+                            let mut output = 0;
+                            let mut i = 0;
+                            while i < 8 {
+                                output |= ((state & 1) as u8) << i;
+                                state = Self::up(state);
+                                i += 1;
+                            }
+                            table[low] = (state, output);
+                            low += 1;
+                        }
+                        table
+                    };
+
+                    /// Same as 8 calls to `up`, returns the state after them and their output bits,
+                    /// the least significant bits of the states they start from, the first one
+                    /// in the least significant bit
+                    pub const fn up8(state: #state_type) -> (#state_type, u8) {
+                        let (low, output) = Self::LEAP8[(state & 0xff) as usize];
+                        ((#high) ^ low, output)
+                    }
+
+                    #up16
+
+                    #up32
+
+                    #up64
+    }
+}
+
+/// `RngCore` and `SeedableRng` with the `rand_core` feature, see `lfsr_base::rng` for how bits are used
+fn rand_core_impls(input: &LFSRInput, name: &syn::Ident, feedback: Feedback) -> proc_macro2::TokenStream {
+    if !cfg!(feature = "rand_core") {
+        return quote! {};
+    }
+
+    let state_type = &input.state_type;
+    let state_mask = Literal::u128_unsuffixed(input.state_mask());
+    let lock_up_state =
+        Literal::u128_unsuffixed(feedback.xor_equivalent(0, input.state_mask()));
+
+    quote! {
+                impl lfsr_base::rand_core::RngCore for #name {
+                    fn next_u32(&mut self) -> u32 {
+                        lfsr_base::rng::next_u32(self)
+                    }
+
+                    fn next_u64(&mut self) -> u64 {
+                        lfsr_base::rng::next_u64(self)
+                    }
+
+                    fn fill_bytes(&mut self, dst: &mut [u8]) {
+                        lfsr_base::rng::fill_bytes(self, dst)
+                    }
+                }
+
+                impl lfsr_base::rand_core::SeedableRng for #name {
+                    type Seed = [u8; core::mem::size_of::<#state_type>()];
+
+                    fn from_seed(seed: Self::Seed) -> Self {
+                        let state = #state_type::from_le_bytes(seed) & #state_mask;
+                        if state == #lock_up_state {
+                            Self::default()
+                        } else {
+                            Self::new(state)
+                        }
+                    }
+                }
+    }
+}
+
+fn generate_lfsr(input: LFSRInput, kind: Kind, feedback: Feedback) -> TokenStream {
+    let sequence_length = match input.sequence_length(kind, feedback) {
+        Ok(sequence_length) => sequence_length,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let name = syn::Ident::new(&input.name, Span::call_site());
+    let tests_mod_name = syn::Ident::new(
+        format!("tests_{}", &input.name.to_lowercase()).as_str(),
+        Span::call_site(),
+    );
+    let width = input.width;
+    let width_usize = input.width as usize;
+    let state_type = &input.state_type;
+    let sequence_length_lit = Literal::u128_unsuffixed(sequence_length);
+
+    let taps_str = &input.taps.iter().map(ToString::to_string).join(", ");
+    let taps = &input.taps;
+    let taps_comment = format!("1-based taps as passed to `{}`", kind.macro_name(feedback));
+    let rand_core_impls = rand_core_impls(&input, &name, feedback);
+    let step_functions = kind.step_functions(&input, feedback);
+    let default_state = Literal::u128_unsuffixed(feedback.default_state());
+    let default_comment = format!("Default state is `{}`", feedback.default_state());
+    let state_mask = Literal::u128_unsuffixed(input.state_mask());
+    let fibonacci_xor_impl = match (kind, feedback) {
+        (Kind::Fibonacci, Feedback::Xor) => quote! {
+                impl lfsr_base::FibonacciXorLFSR for #name {}
+        },
+        _ => quote! {},
+    };
+
+    // walking through the whole sequence takes too long beyond that
+    let sequence_length_test = if sequence_length <= u128::from(u32::MAX) {
+        quote! {
+                    #[test]
+                    fn sequence_length() {
+                        let mut f = #name::default();
+                        let initial_state = f.state;
+                        let mut count = 1;
+                        f.inc();
+                        while (f.state != initial_state) {
+                            f.inc();
+                            count += 1;
+
+                            assert!(count <= #name::sequence_length());
+                        }
+
+                        assert_eq!(count, #name::sequence_length());
+                    }
+        }
+    } else {
+        quote! {}
+    };
+
+    let leap_test = match (kind, feedback) {
+        (Kind::Galois, Feedback::Xor) => quote! {
+                    #[test]
+                    fn leap_forward() {
+                        let mut state = #name::advance(#name::default().state, 100);
+                        for _ in 0..10 {
+                            let (next, output) = #name::up64(state);
+                            let mut expected = 0;
+                            for i in 0..64 {
+                                expected |= ((state & 1) as u64) << i;
+                                state = #name::up(state);
+                            }
+                            assert_eq!((next, output), (state, expected));
+                        }
+                    }
+        },
+        _ => quote! {},
+    };
+
+    let struct_comment = format!(r#"
+        {}-bit {}{} with taps at {}.
+        Sequence length is {}.
+        {} is a lock-up state.
+    "#,
+        input.width,
+        kind.link(),
+        match feedback {
+            Feedback::Xor => "",
+            Feedback::Xnor => " with XNOR feedback",
+        },
+        taps_str,
+        sequence_length,
+        match feedback {
+            Feedback::Xor => "`0`",
+            Feedback::Xnor => "All ones",
+        });
+
+    let expanded = quote! {
+                #[doc=#struct_comment]
+                pub struct #name {
+                    pub state: #state_type,
                 }
 
-                /**
-                  Default state is `1`
-                */
+                impl #name {
+                    /// Width of the state in bits
+                    pub const WIDTH: u32 = #width;
+                    #[doc=#taps_comment]
+                    pub const TAPS: &'static [u32] = &[#(#taps),*];
+
+                    pub const fn new(initial_state: #state_type) -> Self {
+                        Self {
+                            state: initial_state,
+                        }
+                    }
+
+                    const fn sequence_length() -> #state_type {
+                        #sequence_length_lit
+                    }
+
+                    #step_functions
+                }
+
+                #[doc=#default_comment]
                 impl Default for #name {
                     fn default() -> Self {
-                        Self::new(1)
+                        Self::new(#default_state)
                     }
                 }
 
                 impl core::fmt::Display for #name {
                     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-                        write!(f, "{:032b}", self.get_state())
+                        write!(f, "{:0width$b}", self.state, width = #width_usize)
                     }
                 }
 
                 impl lfsr_base::LFSR for #name {
-                    fn get_state(&self) -> u32 {
+                    type State = #state_type;
+
+                    fn get_state(&self) -> #state_type {
                         self.state
                     }
 
+                    fn set_state(&mut self, state: #state_type) {
+                        self.state = state & #state_mask;
+                    }
+
                     fn inc(&mut self) {
                         self.state = Self::up(self.state);
                     }
@@ -158,38 +864,55 @@ pub fn galois_lfsr(input: TokenStream) -> TokenStream {
                         self.state = Self::down(self.state);
                     }
 
-                    fn sequence_length(&self) -> u32 {
+                    fn advance(&mut self, n: #state_type) {
+                        self.state = Self::advance(self.state, n);
+                    }
+
+                    fn rewind(&mut self, n: #state_type) {
+                        self.state = Self::rewind(self.state, n);
+                    }
+
+                    fn sequence_length(&self) -> #state_type {
                         Self::sequence_length()
                     }
+
+                    fn width(&self) -> u32 {
+                        Self::WIDTH
+                    }
                 }
 
                 impl lfsr_base::LFSRStatic for #name {
-                    fn sequence_length() -> u32 {
+                    fn sequence_length() -> #state_type {
                         Self::sequence_length()
                     }
                 }
 
+                #fibonacci_xor_impl
+
+                #rand_core_impls
+
                 #[cfg(test)]
                 mod #tests_mod_name {
                     use super::#name;
                     use lfsr_base::LFSR;
 
+                    #sequence_length_test
+
                     #[test]
-                    fn sequence_length() {
+                    fn advance_and_rewind() {
                         let mut f = #name::default();
                         let initial_state = f.state;
-                        let mut count = 1;
-                        f.inc();
-                        while (f.state != initial_state) {
+                        for _ in 0..200 {
                             f.inc();
-                            count += 1;
-
-                            assert!(count <= #name::sequence_length());
                         }
-
-                        assert_eq!(count, #name::sequence_length());
+                        assert_eq!(#name::advance(initial_state, 200), f.state);
+                        assert_eq!(#name::rewind(f.state, 200), initial_state);
+                        assert_eq!(#name::advance(initial_state, #name::sequence_length()), initial_state);
+                        assert_eq!(#name::rewind(initial_state, #name::sequence_length()), initial_state);
                     }
 
+                    #leap_test
+
                     #[test]
                     fn back_and_forth() {
                         let mut f = #name::default();
@@ -207,3 +930,564 @@ pub fn galois_lfsr(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// What a signature analyser compresses at every step
+#[derive(Clone, Copy)]
+enum AnalyserInput {
+    /// A word as wide as the register, XORed into the state after a step
+    Words,
+    /// A bit, XORed into the least significant bit, and so the feedback, before a step
+    Bits,
+}
+
+/// Generates a multiple input signature register: `galois_misr!(Name, width, [sequence_length,] taps...)`
+///
+/// Arguments are the same as in `galois_lfsr!`. A MISR compresses a stream of `width`-bit words,
+/// typically the outputs of a circuit under test, into a signature. Feeding a word steps
+/// the Galois LFSR with the same taps and XORs the word into the state, `up(state) ^ word`.
+///
+/// The signature a known-good stream should produce is `signature_of(seed, &words)`, a `const fn`,
+/// so it can be a constant compared against at run time. An error confined to one word is always
+/// detected, the chance of missing errors spread over `n` words is `aliasing_probability(n)`, see
+/// `lfsr_base::signature`. The default state is `0`.
+///
+/// ```ignore
+/// galois_misr!(Misr16, 16, 16, 15, 13, 4);
+///
+/// const GOLDEN: u16 = Misr16::signature_of(0, &[0x1234, 0x5678, 0x9abc]);
+///
+/// let mut misr = Misr16::default();
+/// misr.feed_all([0x1234, 0x5678, 0x9abc]);
+/// assert_eq!(misr.signature(), GOLDEN);
+/// ```
+#[proc_macro]
+pub fn galois_misr(input: TokenStream) -> TokenStream {
+    let input: LFSRInput = parse_macro_input!(input as LFSRInput);
+    generate_signature_analyser(input, AnalyserInput::Words)
+}
+
+/// Generates a single input signature register: `galois_sisr!(Name, width, [sequence_length,] taps...)`
+///
+/// Arguments are the same as in `galois_lfsr!`. A SISR compresses a stream of bits into
+/// a signature like a serial signature analyser. Feeding a bit XORs it into the feedback,
+/// the least significant bit, and steps the Galois LFSR with the same taps, `up(state ^ bit)`,
+/// which divides the stream by the feedback polynomial.
+///
+/// The signature of a known-good stream is `signature_of(seed, &bits)`, a `const fn`. Any error
+/// burst at most `width` bits long is always detected, the chance of missing errors spread over
+/// `n` bits is `aliasing_probability(n)`, see `lfsr_base::signature`. The default state is `0`.
+#[proc_macro]
+pub fn galois_sisr(input: TokenStream) -> TokenStream {
+    let input: LFSRInput = parse_macro_input!(input as LFSRInput);
+    generate_signature_analyser(input, AnalyserInput::Bits)
+}
+
+fn generate_signature_analyser(input: LFSRInput, analyser_input: AnalyserInput) -> TokenStream {
+    // the same checks of the taps and the stated sequence length as for the LFSR
+    if let Err(err) = input.sequence_length(Kind::Galois, Feedback::Xor) {
+        return TokenStream::from(err.to_compile_error());
+    }
+    let name = syn::Ident::new(&input.name, Span::call_site());
+    let tests_mod_name = syn::Ident::new(
+        format!("tests_{}", &input.name.to_lowercase()).as_str(),
+        Span::call_site(),
+    );
+    let width = input.width;
+    let width_usize = input.width as usize;
+    let state_type = &input.state_type;
+    let state_mask = Literal::u128_unsuffixed(input.state_mask());
+    let (up, _) = Kind::Galois.xor_step_bodies(&input);
+
+    let taps_str = &input.taps.iter().map(ToString::to_string).join(", ");
+    let taps = &input.taps;
+
+    let (macro_name, kind_str, feed_functions, test) = match analyser_input {
+        AnalyserInput::Words => (
+            "galois_misr!",
+            "multiple input signature register (MISR)",
+            quote! {
+                    /// The state after feeding `word` in `state`, `up(state) ^ word`,
+                    /// bits of `word` beyond the width are ignored
+                    pub const fn step(state: #state_type, word: #state_type) -> #state_type {
+                        Self::up(state) ^ (word & #state_mask)
+                    }
+
+                    pub fn feed(&mut self, word: #state_type) {
+                        self.state = Self::step(self.state, word);
+                    }
+
+                    pub fn feed_all<I: IntoIterator<Item = #state_type>>(&mut self, words: I) {
+                        for word in words {
+                            self.feed(word);
+                        }
+                    }
+
+                    /// Signature of `words` fed one by one starting from `seed`
+                    pub const fn signature_of(seed: #state_type, words: &[#state_type]) -> #state_type {
+                        let mut state = seed;
+                        let mut i = 0;
+                        while i < words.len() {
+                            state = Self::step(state, words[i]);
+                            i += 1;
+                        }
+                        state
+                    }
+
+                    /// Probability that errors in a stream of `words` words aren't detected,
+                    /// every error stream being equally likely
+                    pub fn aliasing_probability(words: u128) -> f64 {
+                        lfsr_base::signature::aliasing_probability(
+                            Self::WIDTH,
+                            words.saturating_mul(u128::from(Self::WIDTH)),
+                        )
+                    }
+            },
+            quote! {
+                    #[test]
+                    fn single_word_errors() {
+                        let words: [#state_type; 4] = [1, 2, 3, 4];
+                        let golden = #name::signature_of(0, &words);
+                        let mut misr = #name::default();
+                        misr.feed_all(words.iter().copied());
+                        assert_eq!(misr.signature(), golden);
+
+                        for i in 0..words.len() {
+                            for bit in 0..#name::WIDTH {
+                                let mut bad = words;
+                                bad[i] ^= 1 << bit;
+                                assert_ne!(#name::signature_of(0, &bad), golden);
+                            }
+                        }
+                    }
+            },
+        ),
+        AnalyserInput::Bits => (
+            "galois_sisr!",
+            "single input signature register (SISR)",
+            quote! {
+                    /// The state after feeding `bit` in `state`, `up(state ^ bit)`
+                    pub const fn step(state: #state_type, bit: bool) -> #state_type {
+                        Self::up(state ^ bit as #state_type)
+                    }
+
+                    pub fn feed(&mut self, bit: bool) {
+                        self.state = Self::step(self.state, bit);
+                    }
+
+                    pub fn feed_all<I: IntoIterator<Item = bool>>(&mut self, bits: I) {
+                        for bit in bits {
+                            self.feed(bit);
+                        }
+                    }
+
+                    /// Feeds all the bits of `bytes` in the given order
+                    pub fn feed_bytes(&mut self, bytes: &[u8], order: lfsr_base::BitOrder) {
+                        for byte in bytes {
+                            for i in 0..8 {
+                                let shift = match order {
+                                    lfsr_base::BitOrder::LsbFirst => i,
+                                    lfsr_base::BitOrder::MsbFirst => 7 - i,
+                                };
+                                self.feed((byte >> shift) & 1 == 1);
+                            }
+                        }
+                    }
+
+                    /// Signature of `bits` fed one by one starting from `seed`
+                    pub const fn signature_of(seed: #state_type, bits: &[bool]) -> #state_type {
+                        let mut state = seed;
+                        let mut i = 0;
+                        while i < bits.len() {
+                            state = Self::step(state, bits[i]);
+                            i += 1;
+                        }
+                        state
+                    }
+
+                    /// Probability that errors in a stream of `bits` bits aren't detected,
+                    /// every error stream being equally likely
+                    pub fn aliasing_probability(bits: u128) -> f64 {
+                        lfsr_base::signature::aliasing_probability(Self::WIDTH, bits)
+                    }
+            },
+            quote! {
+                    #[test]
+                    fn burst_errors() {
+                        let mut bits = [false; 3 * #width_usize];
+                        for (i, bit) in bits.iter_mut().enumerate() {
+                            *bit = i % 3 == 0;
+                        }
+                        let golden = #name::signature_of(0, &bits);
+                        let mut sisr = #name::default();
+                        sisr.feed_all(bits.iter().copied());
+                        assert_eq!(sisr.signature(), golden);
+
+                        // bursts that start and end with an error, up to the width long
+                        for start in 0..bits.len() {
+                            for len in 1..=#width_usize.min(bits.len() - start) {
+                                let mut bad = bits;
+                                bad[start] = !bad[start];
+                                if len > 1 {
+                                    bad[start + len - 1] = !bad[start + len - 1];
+                                }
+                                assert_ne!(#name::signature_of(0, &bad), golden);
+                            }
+                        }
+                    }
+            },
+        ),
+    };
+
+    let taps_comment = format!("1-based taps as passed to `{}`", macro_name);
+    let struct_comment = format!(
+        r#"
+        {}-bit {} on a Galois LFSR with taps at {}.
+        Default state is `0`.
+    "#,
+        input.width, kind_str, taps_str
+    );
+
+    let expanded = quote! {
+                #[doc=#struct_comment]
+                pub struct #name {
+                    pub state: #state_type,
+                }
+
+                impl #name {
+                    /// Width of the state and of the signature in bits
+                    pub const WIDTH: u32 = #width;
+                    #[doc=#taps_comment]
+                    pub const TAPS: &'static [u32] = &[#(#taps),*];
+
+                    pub const fn new(seed: #state_type) -> Self {
+                        Self { state: seed }
+                    }
+
+                    /// `up` of the Galois LFSR with the same taps
+                    pub const fn up(prev_state: #state_type) -> #state_type #up
+
+                    #feed_functions
+
+                    pub const fn signature(&self) -> #state_type {
+                        self.state
+                    }
+
+                    /// Starts over from `seed`
+                    pub fn reset(&mut self, seed: #state_type) {
+                        self.state = seed;
+                    }
+                }
+
+                impl Default for #name {
+                    fn default() -> Self {
+                        Self::new(0)
+                    }
+                }
+
+                impl core::fmt::Display for #name {
+                    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        write!(f, "{:0width$b}", self.state, width = #width_usize)
+                    }
+                }
+
+                #[cfg(test)]
+                mod #tests_mod_name {
+                    use super::#name;
+
+                    #test
+
+                    #[test]
+                    fn zero_stream_keeps_zero_signature() {
+                        let mut analyser = #name::default();
+                        for _ in 0..1000 {
+                            analyser.feed(Default::default());
+                        }
+                        assert_eq!(analyser.signature(), 0);
+                    }
+                }
+            };
+
+    TokenStream::from(expanded)
+}
+
+/// `Name, "CRC-NAME"` or `Name, width = .., poly = .., ...`
+struct CrcInput {
+    name: syn::Ident,
+    params: CrcParams,
+}
+
+impl Parse for CrcInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?;
+        input.parse::<Token![,]>()?;
+
+        if input.peek(syn::LitStr) {
+            let preset = input.parse::<syn::LitStr>()?;
+            input.parse::<Option<Token![,]>>()?;
+            let params = CrcParams::find(&preset.value()).ok_or_else(|| {
+                syn::Error::new(
+                    preset.span(),
+                    format!("there's no {} in lfsr_base::crc::CATALOGUE", preset.value()),
+                )
+            })?;
+            return Ok(Self {
+                name,
+                params: *params,
+            });
+        }
+
+        let mut width = None;
+        let mut poly = None;
+        let mut check: Option<(u128, Span)> = None;
+        let mut values = [("init", 0u128), ("xorout", 0)];
+        let mut reflections: [(&str, Option<bool>); 2] = [("refin", None), ("refout", None)];
+        let mut seen = Vec::new();
+        while !input.is_empty() {
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            let key_str = key.to_string();
+            if seen.contains(&key_str) {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("{} is given more than once", key_str),
+                ));
+            }
+            match key_str.as_str() {
+                "width" => width = Some(input.parse::<syn::LitInt>()?.base10_parse::<u32>()?),
+                "poly" => poly = Some(input.parse::<syn::LitInt>()?.base10_parse::<u128>()?),
+                "check" => {
+                    let lit = input.parse::<syn::LitInt>()?;
+                    check = Some((lit.base10_parse::<u128>()?, lit.span()));
+                }
+                "init" | "xorout" => {
+                    let value = input.parse::<syn::LitInt>()?.base10_parse::<u128>()?;
+                    for entry in values.iter_mut().filter(|entry| entry.0 == key_str) {
+                        entry.1 = value;
+                    }
+                }
+                "refin" | "refout" => {
+                    let value = input.parse::<syn::LitBool>()?.value;
+                    for entry in reflections.iter_mut().filter(|entry| entry.0 == key_str) {
+                        entry.1 = Some(value);
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected one of width, poly, init, refin, refout, xorout or check",
+                    ))
+                }
+            }
+            seen.push(key_str);
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let (width, poly) = match (width, poly) {
+            (Some(width), Some(poly)) => (width, poly),
+            _ => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "width and poly have to be given",
+                ))
+            }
+        };
+        let refin = reflections[0].1.unwrap_or(false);
+        let refout = reflections[1].1.unwrap_or(refin);
+        let params = CrcParams::new(width, poly, values[0].1, refin, refout, values[1].1)
+            .map_err(|err| syn::Error::new(Span::call_site(), err))?;
+        if let Some((check, span)) = check {
+            if check != params.check {
+                return Err(syn::Error::new(
+                    span,
+                    format!("the check value of these parameters is {:#x}", params.check),
+                ));
+            }
+        }
+        Ok(Self { name, params })
+    }
+}
+
+/// Generates a table-driven CRC: `crc!(Name, "CRC-32/ISO-HDLC")` for a preset from
+/// `lfsr_base::crc::CATALOGUE`, or `crc!(Name, width = 16, poly = 0x1021, init = 0xffff,
+/// refin = false, refout = false, xorout = 0x0000, check = 0x29b1)` for any parameters
+/// of the Rocksoft^tm model
+///
+/// `width` and `poly` in the normal notation have to be given, `init` and `xorout` default to `0`,
+/// `refin` to `false` and `refout` to `refin`. The table is computed at compile time with
+/// `lfsr_base::crc::CrcTable`, and if `check` is given it's checked against the CRC of `"123456789"`,
+/// so wrong parameters fail to compile.
+///
+/// The CRC is `checksum(data)`, a `const fn`, or of data coming in pieces, `update(data)` of
+/// `new()` followed by `finalize()`. The register is in the smallest of `u8`, `u16`, `u32`, `u64`
+/// and `u128` that fits the width, and so is the CRC.
+///
+/// ```ignore
+/// crc!(Crc32, "CRC-32");
+///
+/// const CHECK: u32 = Crc32::checksum(b"123456789");
+/// assert_eq!(CHECK, 0xCBF43926);
+///
+/// let mut crc = Crc32::new();
+/// crc.update(b"1234");
+/// crc.update(b"56789");
+/// assert_eq!(crc.finalize(), CHECK);
+/// ```
+#[proc_macro]
+pub fn crc(input: TokenStream) -> TokenStream {
+    let CrcInput { name, params } = parse_macro_input!(input as CrcInput);
+    let table = match CrcTable::new(params) {
+        Ok(table) => table,
+        Err(err) => return TokenStream::from(syn::Error::new(name.span(), err).to_compile_error()),
+    };
+    let tests_mod_name = syn::Ident::new(
+        format!("tests_{}", name.to_string().to_lowercase()).as_str(),
+        Span::call_site(),
+    );
+
+    // the unreflected register is shifted up to be at least a byte wide, as in `CrcTable`
+    let width = params.width;
+    let register_width = width.max(8);
+    let align = register_width - width;
+    let bits = [8, 16, 32, 64, 128]
+        .iter()
+        .copied()
+        .find(|bits| *bits >= register_width)
+        .unwrap_or(128);
+    let state_type = syn::Ident::new(&format!("u{}", bits), Span::call_site());
+
+    let lit = Literal::u128_unsuffixed;
+    let entries = table.table().iter().copied().map(lit);
+    let initial_register = lit(table.initial_register());
+    let (poly, init, xorout, check) = (
+        lit(params.poly),
+        lit(params.init),
+        lit(params.xorout),
+        lit(params.check),
+    );
+    let (refin, refout) = (params.refin, params.refout);
+
+    let next_register = match (refin, bits) {
+        (true, 8) => quote! { Self::TABLE[(register ^ data[i]) as usize] },
+        (true, _) => quote! { (register >> 8) ^ Self::TABLE[(register as u8 ^ data[i]) as usize] },
+        (false, _) if register_width == 8 => quote! { Self::TABLE[(register ^ data[i]) as usize] },
+        (false, _) => {
+            let register_mask = lit(u128::MAX >> (128 - register_width));
+            let top_byte = register_width - 8;
+            quote! {
+                ((register << 8) & #register_mask)
+                    ^ Self::TABLE[((register >> #top_byte) as u8 ^ data[i]) as usize]
+            }
+        }
+    };
+    let crc = if refin { quote! { register } } else { quote! { register >> #align } };
+    let crc = if refin != refout {
+        let unused = bits - width;
+        quote! { (#crc).reverse_bits() >> #unused }
+    } else {
+        crc
+    };
+
+    let struct_comment = format!(
+        "{}-bit CRC{}: poly `{:#x}`, init `{:#x}`, refin `{}`, refout `{}`, xorout `{:#x}`, check `{:#x}`",
+        width,
+        if params.name.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", params.name)
+        },
+        params.poly,
+        params.init,
+        params.refin,
+        params.refout,
+        params.xorout,
+        params.check,
+    );
+
+    let expanded = quote! {
+                #[doc=#struct_comment]
+                pub struct #name {
+                    register: #state_type,
+                }
+
+                impl #name {
+                    pub const WIDTH: u32 = #width;
+                    /// Generator polynomial in the normal notation
+                    pub const POLY: #state_type = #poly;
+                    pub const INIT: #state_type = #init;
+                    pub const REFIN: bool = #refin;
+                    pub const REFOUT: bool = #refout;
+                    pub const XOROUT: #state_type = #xorout;
+                    /// CRC of `"123456789"`
+                    pub const CHECK: #state_type = #check;
+                    /// Entry `i` is the register after byte `i` fed into a zero one
+                    pub const TABLE: [#state_type; 256] = [#(#entries),*];
+
+                    pub const fn new() -> Self {
+                        Self {
+                            register: #initial_register,
+                        }
+                    }
+
+                    pub fn update(&mut self, data: &[u8]) {
+                        self.register = Self::update_register(self.register, data);
+                    }
+
+                    /// The CRC of everything fed so far, more data can be fed afterwards
+                    pub const fn finalize(&self) -> #state_type {
+                        Self::final_crc(self.register)
+                    }
+
+                    pub const fn checksum(data: &[u8]) -> #state_type {
+                        Self::final_crc(Self::update_register(#initial_register, data))
+                    }
+
+                    const fn update_register(mut register: #state_type, data: &[u8]) -> #state_type {
+                        let mut i = 0;
+                        while i < data.len() {
+                            register = #next_register;
+                            i += 1;
+                        }
+                        register
+                    }
+
+                    const fn final_crc(register: #state_type) -> #state_type {
+                        (#crc) ^ #xorout
+                    }
+                }
+
+                impl Default for #name {
+                    fn default() -> Self {
+                        Self::new()
+                    }
+                }
+
+                #[cfg(test)]
+                mod #tests_mod_name {
+                    use super::#name;
+                    use lfsr_base::crc::{Crc, CrcEngine, CrcParams};
+                    use lfsr_base::LFSRState;
+
+                    #[test]
+                    fn check_and_bitwise() {
+                        assert_eq!(#name::checksum(b"123456789"), #name::CHECK);
+
+                        let params = CrcParams::new(#width, #poly, #init, #refin, #refout, #xorout).unwrap();
+                        let bitwise = Crc::new(params).unwrap();
+                        let mut data = [0u8; 100];
+                        for (i, byte) in data.iter_mut().enumerate() {
+                            *byte = (i * 37 + 11) as u8;
+                        }
+                        let mut crc = #name::new();
+                        for chunk in data.chunks(7) {
+                            crc.update(chunk);
+                        }
+                        assert_eq!(LFSRState::to_u128(crc.finalize()), bitwise.checksum(&data));
+                    }
+                }
+            };
+
+    TokenStream::from(expanded)
+}
@@ -3,23 +3,411 @@
 extern crate proc_macro;
 
 use itertools::Itertools;
+use lfsr_base::gf2::{mul_mod, pow_mod, x_pow_mod, Poly};
 use lfsr_base::LFSR;
 use proc_macro::TokenStream;
-use proc_macro2::{Delimiter, Group, Span};
+use proc_macro2::{Delimiter, Group, Literal, Span};
 use quote::quote;
 use quote::TokenStreamExt;
 use syn::parse::{Parse, ParseStream};
 use syn::parse_macro_input;
-use syn::IntSuffix;
+use syn::spanned::Spanned;
 use syn::Token;
 
+/// Same as in `galois_lfsr!`: the smallest unsigned integer that fits `width` bits,
+/// unless the width literal has an explicit suffix, as in `24u64`
+fn state_type(width: u32, suffix: &str) -> Option<syn::Ident> {
+    let bits = match suffix {
+        "" => [8, 16, 32, 64, 128]
+            .iter()
+            .copied()
+            .find(|bits| *bits >= width)?,
+        "u8" => 8,
+        "u16" => 16,
+        "u32" => 32,
+        "u64" => 64,
+        "u128" => 128,
+        _ => return None,
+    };
+    if bits < width {
+        return None;
+    }
+    Some(syn::Ident::new(&format!("u{}", bits), Span::call_site()))
+}
+
+/// Width and taps of a Galois LFSR, in the same 1-based notation as in `galois_lfsr!`
+#[derive(Debug)]
+struct GaloisDescription {
+    width: u32,
+    state_type: syn::Ident,
+    taps: Vec<u32>,
+}
+
+impl GaloisDescription {
+    /// Parses the optional trailing `width = .., taps = [..]` arguments
+    fn parse_named(input: ParseStream<'_>) -> syn::Result<Option<Self>> {
+        let mut width = None;
+        let mut taps = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<syn::Ident>()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "width" => {
+                    let lit = input.parse::<syn::LitInt>()?;
+                    let value = lit.base10_parse::<u32>()?;
+                    if !(1..=128).contains(&value) {
+                        return Err(syn::Error::new(lit.span(), "width has to be in 1..=128"));
+                    }
+                    let state_type = state_type(value, lit.suffix()).ok_or_else(|| {
+                        syn::Error::new(lit.span(), "width doesn't fit into the state type")
+                    })?;
+                    width = Some((value, state_type));
+                }
+                "taps" => {
+                    let content;
+                    let brackets = syn::bracketed!(content in input);
+                    let taps_with_spans = content
+                        .parse_terminated(syn::LitInt::parse, Token![,])?
+                        .iter()
+                        .map(|l| Ok((l.base10_parse::<u32>()?, l.span())))
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    taps = Some((taps_with_spans, brackets.span.join()));
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown parameter `{}`, expected `width` or `taps`", key),
+                    ))
+                }
+            }
+        }
+
+        match (width, taps) {
+            (None, None) => Ok(None),
+            (Some((width, state_type)), Some((taps, taps_span))) => Ok(Some(Self {
+                width,
+                state_type,
+                taps: Self::check_taps(width, &taps, taps_span)?,
+            })),
+            _ => Err(syn::Error::new(
+                Span::call_site(),
+                "both `width` and `taps` have to be specified",
+            )),
+        }
+    }
+
+    /// Taps have to be in `1..=width`, given once, and one of them has to be `width`
+    fn check_taps(width: u32, taps: &[(u32, Span)], taps_span: Span) -> syn::Result<Vec<u32>> {
+        let mut checked = Vec::with_capacity(taps.len());
+        for (tap, span) in taps.iter().copied() {
+            if !(1..=width).contains(&tap) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("tap {} is not in 1..={}", tap, width),
+                ));
+            }
+            if checked.contains(&tap) {
+                return Err(syn::Error::new(span, format!("tap {} is given twice", tap)));
+            }
+            checked.push(tap);
+        }
+        if !checked.contains(&width) {
+            return Err(syn::Error::new(
+                taps_span,
+                format!("one of the taps has to be {}", width),
+            ));
+        }
+        Ok(checked)
+    }
+
+    /// Uses an explicit description if there is one, otherwise looks `lfsr_ty` up among `lfsr_instances::galois`
+    fn resolve(lfsr_ty: &syn::TypePath, explicit: Option<Self>) -> syn::Result<Self> {
+        if let Some(description) = explicit {
+            return Ok(description);
+        }
+
+        macro_rules! known_instances {
+            ($name:expr, $($instance:ident),*) => {
+                match $name {
+                    $(stringify!($instance) => Some(Self {
+                        width: lfsr_instances::galois::$instance::WIDTH,
+                        state_type: state_type(lfsr_instances::galois::$instance::WIDTH, "").unwrap(),
+                        taps: lfsr_instances::galois::$instance::TAPS.to_vec(),
+                    }),)*
+                    _ => None,
+                }
+            };
+        }
+
+        let name = lfsr_ty
+            .path
+            .segments
+            .last()
+            .unwrap()
+            .ident
+            .to_string();
+        let known = known_instances!(
+            name.as_str(),
+            Galois128,
+            Galois127,
+            Galois126,
+            Galois125,
+            Galois124,
+            Galois123,
+            Galois122,
+            Galois121,
+            Galois120,
+            Galois119,
+            Galois118,
+            Galois117,
+            Galois116,
+            Galois115,
+            Galois114,
+            Galois113,
+            Galois112,
+            Galois111,
+            Galois110,
+            Galois109,
+            Galois108,
+            Galois107,
+            Galois106,
+            Galois105,
+            Galois104,
+            Galois103,
+            Galois102,
+            Galois101,
+            Galois100,
+            Galois99,
+            Galois98,
+            Galois97,
+            Galois96,
+            Galois95,
+            Galois94,
+            Galois93,
+            Galois92,
+            Galois91,
+            Galois90,
+            Galois89,
+            Galois88,
+            Galois87,
+            Galois86,
+            Galois85,
+            Galois84,
+            Galois83,
+            Galois82,
+            Galois81,
+            Galois80,
+            Galois79,
+            Galois78,
+            Galois77,
+            Galois76,
+            Galois75,
+            Galois74,
+            Galois73,
+            Galois72,
+            Galois71,
+            Galois70,
+            Galois69,
+            Galois68,
+            Galois67,
+            Galois66,
+            Galois65,
+            Galois64,
+            Galois63,
+            Galois62,
+            Galois61,
+            Galois60,
+            Galois59,
+            Galois58,
+            Galois57,
+            Galois56,
+            Galois55,
+            Galois54,
+            Galois53,
+            Galois52,
+            Galois51,
+            Galois50,
+            Galois49,
+            Galois48,
+            Galois47,
+            Galois46,
+            Galois45,
+            Galois44,
+            Galois43,
+            Galois42,
+            Galois41,
+            Galois40,
+            Galois39,
+            Galois38,
+            Galois37,
+            Galois36,
+            Galois35,
+            Galois34,
+            Galois33,
+            Galois32,
+            Galois31,
+            Galois30,
+            Galois29,
+            Galois28,
+            Galois27,
+            Galois26,
+            Galois25,
+            Galois24,
+            Galois23,
+            Galois22,
+            Galois21,
+            Galois20,
+            Galois19,
+            Galois18,
+            Galois17,
+            Galois16,
+            Galois15,
+            Galois14,
+            Galois13,
+            Galois12,
+            Galois11,
+            Galois10,
+            Galois9,
+            Galois8,
+            Galois7,
+            Galois6,
+            Galois5,
+            Galois4,
+            Galois3,
+            Galois2
+        );
+
+        known.ok_or_else(|| {
+            syn::Error::new(
+                lfsr_ty.span(),
+                format!(
+                    "`{}` is not one of `lfsr_instances::galois`, specify its parameters as in \
+                     `galois_lfsr!`: `width = .., taps = [..]`",
+                    name
+                ),
+            )
+        })
+    }
+
+    fn lfsr(&self) -> DescribedLFSR {
+        let fwd_mask = self
+            .taps
+            .iter()
+            .fold(0u128, |mask, tap| mask | (1 << (tap - 1)));
+        DescribedLFSR {
+            width: self.width,
+            state_type: self.state_type.clone(),
+            fwd_mask,
+            inv_mask: (fwd_mask << 1) | ((fwd_mask >> (self.width - 1)) & 1),
+            state: 1,
+        }
+    }
+}
+
+/// A Galois LFSR stepped at macro expansion time, mirroring the code generated by `galois_lfsr!`
+#[derive(Clone)]
+struct DescribedLFSR {
+    width: u32,
+    state_type: syn::Ident,
+    fwd_mask: u128,
+    inv_mask: u128,
+    state: u128,
+}
+
+impl DescribedLFSR {
+    /// Makes the generated code fail to compile if `lfsr_ty` steps differently from this description
+    ///
+    /// Steps up and back down from a few states that together set every bit, so every tap
+    /// and the bits shifted in and out at both ends are compared.
+    fn consistency_check(&self, lfsr_ty: &syn::TypePath) -> proc_macro2::TokenStream {
+        let state_mask = u128::MAX >> (128 - self.width);
+        let states = [
+            self.state,
+            1 << (self.width - 1),
+            state_mask,
+            0x5555_5555_5555_5555_5555_5555_5555_5555 & state_mask,
+            0xAAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA_AAAA & state_mask,
+            0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210 & state_mask,
+        ];
+        let round_trips = states
+            .iter()
+            .copied()
+            .filter(|state| *state != 0)
+            .unique()
+            .map(|state| {
+                let mut lfsr = self.clone();
+                lfsr.state = state;
+                lfsr.inc();
+                let initial_state = Literal::u128_unsuffixed(state);
+                let next_state = Literal::u128_unsuffixed(lfsr.state);
+                quote! {
+                    #lfsr_ty::up(#initial_state) == #next_state && #lfsr_ty::down(#next_state) == #initial_state
+                }
+            });
+        let message = format!(
+            "`{}` doesn't match the width and taps used to build this lookup",
+            quote!(#lfsr_ty).to_string().replace(' ', "")
+        );
+        quote! {
+            const _: () = assert!(#(#round_trips)&&*, #message);
+        }
+    }
+}
+
+impl LFSR for DescribedLFSR {
+    type State = u128;
+
+    fn get_state(&self) -> u128 {
+        self.state
+    }
+
+    fn set_state(&mut self, state: u128) {
+        self.state = state;
+    }
+
+    fn inc(&mut self) {
+        let lsb = self.state & 1;
+        self.state >>= 1;
+        self.state ^= lsb.wrapping_neg() & self.fwd_mask;
+    }
+
+    fn dec(&mut self) {
+        let msb = self.state >> (self.width - 1);
+        self.state <<= 1;
+        self.state ^= msb.wrapping_neg() & self.inv_mask;
+        self.state &= u128::MAX >> (128 - self.width);
+    }
+
+    fn sequence_length(&self) -> u128 {
+        let mut lfsr = self.clone();
+        let mut count = 1;
+        lfsr.inc();
+        while lfsr.state != self.state {
+            lfsr.inc();
+            count += 1;
+        }
+        count
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+}
+
 #[derive(Debug)]
 struct SearchingLFSRLookupInput {
     name: String,
     lfsr_ty: syn::TypePath,
-    min_value: u32,
-    max_value: u32,
-    step: u32,
+    min_value: u128,
+    max_value: u128,
+    step: u128,
+    description: Option<GaloisDescription>,
 }
 
 impl Parse for SearchingLFSRLookupInput {
@@ -30,13 +418,15 @@ impl Parse for SearchingLFSRLookupInput {
         let lfsr_ty = input.parse::<syn::TypePath>()?;
         input.parse::<Token![,]>()?;
 
-        let min_value = input.parse::<syn::LitInt>()?.value() as u32;
+        let min_value = input.parse::<syn::LitInt>()?.base10_parse::<u128>()?;
         input.parse::<Token![,]>()?;
 
-        let max_value = input.parse::<syn::LitInt>()?.value() as u32;
+        let max_value = input.parse::<syn::LitInt>()?.base10_parse::<u128>()?;
         input.parse::<Token![,]>()?;
 
-        let step = input.parse::<syn::LitInt>()?.value() as u32;
+        let step = input.parse::<syn::LitInt>()?.base10_parse::<u128>()?;
+
+        let description = GaloisDescription::parse_named(input)?;
 
         Ok(Self {
             name,
@@ -44,6 +434,7 @@ impl Parse for SearchingLFSRLookupInput {
             min_value,
             max_value,
             step,
+            description,
         })
     }
 }
@@ -59,51 +450,11 @@ pub fn searching_lfsr_lookup(input: TokenStream) -> TokenStream {
         Span::call_site(),
     );
 
-    let mut lfsr: Box<dyn LFSR> = {
-        match lfsr_ident
-            .path
-            .segments
-            .last()
-            .unwrap()
-            .value()
-            .ident
-            .to_string()
-            .as_str()
-        {
-            "Galois32" => Box::new(lfsr_instances::galois::Galois32::default()),
-            "Galois31" => Box::new(lfsr_instances::galois::Galois31::default()),
-            "Galois30" => Box::new(lfsr_instances::galois::Galois30::default()),
-            "Galois29" => Box::new(lfsr_instances::galois::Galois29::default()),
-            "Galois28" => Box::new(lfsr_instances::galois::Galois28::default()),
-            "Galois27" => Box::new(lfsr_instances::galois::Galois27::default()),
-            "Galois26" => Box::new(lfsr_instances::galois::Galois26::default()),
-            "Galois25" => Box::new(lfsr_instances::galois::Galois25::default()),
-            "Galois24" => Box::new(lfsr_instances::galois::Galois24::default()),
-            "Galois23" => Box::new(lfsr_instances::galois::Galois23::default()),
-            "Galois22" => Box::new(lfsr_instances::galois::Galois22::default()),
-            "Galois21" => Box::new(lfsr_instances::galois::Galois21::default()),
-            "Galois20" => Box::new(lfsr_instances::galois::Galois20::default()),
-            "Galois19" => Box::new(lfsr_instances::galois::Galois19::default()),
-            "Galois18" => Box::new(lfsr_instances::galois::Galois18::default()),
-            "Galois17" => Box::new(lfsr_instances::galois::Galois17::default()),
-            "Galois16" => Box::new(lfsr_instances::galois::Galois16::default()),
-            "Galois15" => Box::new(lfsr_instances::galois::Galois15::default()),
-            "Galois14" => Box::new(lfsr_instances::galois::Galois14::default()),
-            "Galois13" => Box::new(lfsr_instances::galois::Galois13::default()),
-            "Galois12" => Box::new(lfsr_instances::galois::Galois12::default()),
-            "Galois11" => Box::new(lfsr_instances::galois::Galois11::default()),
-            "Galois10" => Box::new(lfsr_instances::galois::Galois10::default()),
-            "Galois9" => Box::new(lfsr_instances::galois::Galois9::default()),
-            "Galois8" => Box::new(lfsr_instances::galois::Galois8::default()),
-            "Galois7" => Box::new(lfsr_instances::galois::Galois7::default()),
-            "Galois6" => Box::new(lfsr_instances::galois::Galois6::default()),
-            "Galois5" => Box::new(lfsr_instances::galois::Galois5::default()),
-            "Galois4" => Box::new(lfsr_instances::galois::Galois4::default()),
-            "Galois3" => Box::new(lfsr_instances::galois::Galois3::default()),
-            "Galois2" => Box::new(lfsr_instances::galois::Galois2::default()),
-            _ => panic!(),
-        }
+    let mut lfsr = match GaloisDescription::resolve(lfsr_ident, input.description) {
+        Ok(description) => description.lfsr(),
+        Err(err) => return TokenStream::from(err.to_compile_error()),
     };
+    let consistency_check = lfsr.consistency_check(lfsr_ident);
 
     let starting_values = (input.min_value..input.max_value)
         .step_by(input.step as usize)
@@ -118,13 +469,14 @@ pub fn searching_lfsr_lookup(input: TokenStream) -> TokenStream {
         lfsr_states.push(lfsr.get_state());
     }
 
-    let step = syn::LitInt::new(input.step as u64, IntSuffix::U32, Span::call_site());
+    let state_type = &lfsr.state_type;
+    let step = Literal::u128_unsuffixed(input.step);
     let steps = lfsr_states.len();
 
     let lookup_table = {
         let mut s = quote! {};
         for lfsr_state in lfsr_states.iter().cloned() {
-            let lfsr_state = syn::LitInt::new(lfsr_state as u64, IntSuffix::U32, Span::call_site());
+            let lfsr_state = Literal::u128_unsuffixed(lfsr_state);
             s.append_all(quote! {
                 #lfsr_state,
             });
@@ -135,9 +487,8 @@ pub fn searching_lfsr_lookup(input: TokenStream) -> TokenStream {
     let lookups = {
         let mut s = quote! {};
         for (ix, starting_value) in starting_values.iter().cloned().enumerate() {
-            let ix = syn::LitInt::new(ix as u64, IntSuffix::Usize, Span::call_site());
-            let starting_value =
-                syn::LitInt::new(starting_value as u64, IntSuffix::U32, Span::call_site());
+            let ix = Literal::usize_suffixed(ix);
+            let starting_value = Literal::u128_unsuffixed(starting_value);
             s.append_all(quote! {
                 if lfsr == LOOKUP[#ix] {
                     return Some(#starting_value + offset);
@@ -148,12 +499,13 @@ pub fn searching_lfsr_lookup(input: TokenStream) -> TokenStream {
     };
 
     let expanded = quote! {
-        fn #name(lfsr: &#lfsr_ident) -> Option<u32> {
-            const LOOKUP: [u32; #steps as usize] = #lookup_table;
+        fn #name(lfsr: &#lfsr_ident) -> Option<#state_type> {
+            #consistency_check
+            const LOOKUP: [#state_type; #steps as usize] = #lookup_table;
 
             let mut lfsr = lfsr.state;
 
-            for offset in 0u32..#step {
+            for offset in 0..#step {
                 #lookups
                 lfsr = #lfsr_ident::down(lfsr);
             }
@@ -168,6 +520,7 @@ pub fn searching_lfsr_lookup(input: TokenStream) -> TokenStream {
 struct DirectLFSRLookupInput {
     name: String,
     lfsr_ty: syn::TypePath,
+    description: Option<GaloisDescription>,
 }
 
 impl Parse for DirectLFSRLookupInput {
@@ -177,13 +530,19 @@ impl Parse for DirectLFSRLookupInput {
 
         let lfsr_ty = input.parse::<syn::TypePath>()?;
 
+        let description = GaloisDescription::parse_named(input)?;
+
         Ok(Self {
             name,
             lfsr_ty,
+            description,
         })
     }
 }
 
+/// Widest LFSR `direct_lfsr_lookup!` walks through, its table has `2^width` entries
+const MAX_DIRECT_WIDTH: u32 = 22;
+
 #[proc_macro]
 pub fn direct_lfsr_lookup(input: TokenStream) -> TokenStream {
     let input: DirectLFSRLookupInput = parse_macro_input!(input as DirectLFSRLookupInput);
@@ -195,57 +554,30 @@ pub fn direct_lfsr_lookup(input: TokenStream) -> TokenStream {
         Span::call_site(),
     );
 
-    let mut lfsr: Box<dyn LFSR> = {
-        match lfsr_ident
-            .path
-            .segments
-            .last()
-            .unwrap()
-            .value()
-            .ident
-            .to_string()
-            .as_str()
-        {
-            "Galois32" => Box::new(lfsr_instances::galois::Galois32::default()),
-            "Galois31" => Box::new(lfsr_instances::galois::Galois31::default()),
-            "Galois30" => Box::new(lfsr_instances::galois::Galois30::default()),
-            "Galois29" => Box::new(lfsr_instances::galois::Galois29::default()),
-            "Galois28" => Box::new(lfsr_instances::galois::Galois28::default()),
-            "Galois27" => Box::new(lfsr_instances::galois::Galois27::default()),
-            "Galois26" => Box::new(lfsr_instances::galois::Galois26::default()),
-            "Galois25" => Box::new(lfsr_instances::galois::Galois25::default()),
-            "Galois24" => Box::new(lfsr_instances::galois::Galois24::default()),
-            "Galois23" => Box::new(lfsr_instances::galois::Galois23::default()),
-            "Galois22" => Box::new(lfsr_instances::galois::Galois22::default()),
-            "Galois21" => Box::new(lfsr_instances::galois::Galois21::default()),
-            "Galois20" => Box::new(lfsr_instances::galois::Galois20::default()),
-            "Galois19" => Box::new(lfsr_instances::galois::Galois19::default()),
-            "Galois18" => Box::new(lfsr_instances::galois::Galois18::default()),
-            "Galois17" => Box::new(lfsr_instances::galois::Galois17::default()),
-            "Galois16" => Box::new(lfsr_instances::galois::Galois16::default()),
-            "Galois15" => Box::new(lfsr_instances::galois::Galois15::default()),
-            "Galois14" => Box::new(lfsr_instances::galois::Galois14::default()),
-            "Galois13" => Box::new(lfsr_instances::galois::Galois13::default()),
-            "Galois12" => Box::new(lfsr_instances::galois::Galois12::default()),
-            "Galois11" => Box::new(lfsr_instances::galois::Galois11::default()),
-            "Galois10" => Box::new(lfsr_instances::galois::Galois10::default()),
-            "Galois9" => Box::new(lfsr_instances::galois::Galois9::default()),
-            "Galois8" => Box::new(lfsr_instances::galois::Galois8::default()),
-            "Galois7" => Box::new(lfsr_instances::galois::Galois7::default()),
-            "Galois6" => Box::new(lfsr_instances::galois::Galois6::default()),
-            "Galois5" => Box::new(lfsr_instances::galois::Galois5::default()),
-            "Galois4" => Box::new(lfsr_instances::galois::Galois4::default()),
-            "Galois3" => Box::new(lfsr_instances::galois::Galois3::default()),
-            "Galois2" => Box::new(lfsr_instances::galois::Galois2::default()),
-            _ => panic!(),
-        }
+    let mut lfsr = match GaloisDescription::resolve(lfsr_ident, input.description) {
+        Ok(description) => description.lfsr(),
+        Err(err) => return TokenStream::from(err.to_compile_error()),
     };
+    if lfsr.width > MAX_DIRECT_WIDTH {
+        return TokenStream::from(
+            syn::Error::new(
+                lfsr_ident.span(),
+                format!(
+                    "a direct lookup table for width {} has 2^{} entries, widths above {} need `discrete_log_lfsr_lookup!`",
+                    lfsr.width, lfsr.width, MAX_DIRECT_WIDTH
+                ),
+            )
+            .to_compile_error(),
+        );
+    }
+    let consistency_check = lfsr.consistency_check(lfsr_ident);
 
     let lfsr_initial_state = lfsr.get_state();
 
+    let sequence_length = lfsr.sequence_length();
     let mut counter = 0;
     let mut lfsr_states = Vec::new();
-    while counter < lfsr.sequence_length() {
+    while counter < sequence_length {
         if counter != 0 {
             assert_ne!(lfsr.get_state(), lfsr_initial_state);
         }
@@ -255,18 +587,19 @@ pub fn direct_lfsr_lookup(input: TokenStream) -> TokenStream {
     }
 
     let mut reverse_lfsr_states = Vec::with_capacity(lfsr_states.len());
-    reverse_lfsr_states.resize_with(lfsr_states.len() + 1, || 0);
+    reverse_lfsr_states.resize_with(1 << lfsr.width, || 0);
 
     for (ix, lfsr_state) in lfsr_states.iter().copied().enumerate() {
         reverse_lfsr_states[lfsr_state as usize] = ix;
     }
 
-    let steps = lfsr_states.len() + 1;
+    let state_type = &lfsr.state_type;
+    let steps = reverse_lfsr_states.len();
 
     let lookup_table = {
         let mut s = quote! {};
         for original_value in reverse_lfsr_states.iter().cloned() {
-            let original_value = syn::LitInt::new(original_value as u64, IntSuffix::U32, Span::call_site());
+            let original_value = Literal::usize_unsuffixed(original_value);
             s.append_all(quote! {
                 #original_value,
             });
@@ -275,8 +608,9 @@ pub fn direct_lfsr_lookup(input: TokenStream) -> TokenStream {
     };
 
     let expanded = quote! {
-        fn #name(lfsr: &#lfsr_ident) -> u32 {
-            const LOOKUP: [u32; #steps as usize] = #lookup_table;
+        fn #name(lfsr: &#lfsr_ident) -> #state_type {
+            #consistency_check
+            const LOOKUP: [#state_type; #steps as usize] = #lookup_table;
 
             LOOKUP[lfsr.state as usize]
         }
@@ -285,7 +619,166 @@ pub fn direct_lfsr_lookup(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[deprecated(since="0.3.0", note="use searching_lfsr_lookup!(..) instead; or use direct_lfsr_lookup!(..) for small LUTs")]
+#[derive(Debug)]
+struct DiscreteLogLFSRLookupInput {
+    name: String,
+    lfsr_ty: syn::TypePath,
+    table_size: syn::LitInt,
+    description: Option<GaloisDescription>,
+}
+
+impl Parse for DiscreteLogLFSRLookupInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?.to_string();
+        input.parse::<Token![,]>()?;
+
+        let lfsr_ty = input.parse::<syn::TypePath>()?;
+        input.parse::<Token![,]>()?;
+
+        let table_size = input.parse::<syn::LitInt>()?;
+
+        let description = GaloisDescription::parse_named(input)?;
+
+        Ok(Self {
+            name,
+            lfsr_ty,
+            table_size,
+            description,
+        })
+    }
+}
+
+/// Most giant steps a lookup may take for one prime factor, more don't finish in practice
+const MAX_GIANT_STEPS: u128 = 1 << 32;
+
+/// Generates `fn name(lfsr: &Type) -> Option<State>` that finds the position of any state
+/// of a maximum length Galois LFSR: `discrete_log_lfsr_lookup!(name, Type, table_size)`
+///
+/// Takes discrete logarithms in GF(2^width), see `lfsr_base::discrete_log`. Every prime factor `q`
+/// of the sequence length gets a table of `min(q, table_size)` states, a lookup takes about
+/// `q / table_size` steps for each of them and may take at most `2^32`. `0` is not in the sequence
+/// and gives `None`.
+#[proc_macro]
+pub fn discrete_log_lfsr_lookup(input: TokenStream) -> TokenStream {
+    let input: DiscreteLogLFSRLookupInput = parse_macro_input!(input as DiscreteLogLFSRLookupInput);
+    let name = syn::Ident::new(&input.name, Span::call_site());
+    let lfsr_ident = &input.lfsr_ty;
+
+    let table_size = match input.table_size.base10_parse::<u32>() {
+        Ok(0) => {
+            return TokenStream::from(
+                syn::Error::new(input.table_size.span(), "table size has to be at least 1")
+                    .to_compile_error(),
+            )
+        }
+        Ok(table_size) => table_size,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+
+    let description = match GaloisDescription::resolve(lfsr_ident, input.description) {
+        Ok(description) => description,
+        Err(err) => return TokenStream::from(err.to_compile_error()),
+    };
+    let lfsr = description.lfsr();
+    let consistency_check = lfsr.consistency_check(lfsr_ident);
+
+    // `down` multiplies the state by `x` modulo the feedback polynomial
+    let degree = lfsr.width;
+    let order = u128::MAX >> (128 - degree);
+    let low = lfsr.inv_mask & order;
+    let primes = lfsr_base::mersenne::MERSENNE_PRIME_FACTORS[degree as usize];
+    if !Poly::from_taps(&description.taps).is_primitive() {
+        return TokenStream::from(
+            syn::Error::new(
+                lfsr_ident.span(),
+                format!(
+                    "`{}` isn't a maximum length LFSR, some states can't be reached from `1`",
+                    quote!(#lfsr_ident).to_string().replace(' ', "")
+                ),
+            )
+            .to_compile_error(),
+        );
+    }
+
+    let table_size_needed = primes
+        .iter()
+        .map(|prime| prime / (MAX_GIANT_STEPS + 1) + 1)
+        .max()
+        .unwrap_or(1);
+    if let Some(prime) = primes
+        .iter()
+        .find(|prime| *prime / (table_size as u128).min(**prime) > MAX_GIANT_STEPS)
+    {
+        return TokenStream::from(
+            syn::Error::new(
+                input.table_size.span(),
+                format!(
+                    "lookups would take up to {} steps for the prime factor {}, the table size has to be at least {}",
+                    prime / table_size as u128,
+                    prime,
+                    table_size_needed
+                ),
+            )
+            .to_compile_error(),
+        );
+    }
+
+    let state_type = &lfsr.state_type;
+    let subgroups = primes.iter().map(|prime| {
+        let mut multiplicity = 0u32;
+        let mut rest = order;
+        while rest % prime == 0 {
+            rest /= prime;
+            multiplicity += 1;
+        }
+
+        let generator = x_pow_mod(order / prime, degree, low);
+        let steps = (table_size as u128).min(*prime);
+        let mut baby_steps = Vec::with_capacity(steps as usize);
+        let mut element = 1;
+        for i in 0..steps as u32 {
+            baby_steps.push((element, i));
+            element = mul_mod(element, generator, degree, low);
+        }
+        baby_steps.sort_unstable();
+        let giant_step = Literal::u128_unsuffixed(pow_mod(generator, prime - steps, degree, low));
+
+        let baby_steps = baby_steps.into_iter().map(|(element, i)| {
+            let element = Literal::u128_unsuffixed(element);
+            quote! { (#element, #i) }
+        });
+        let prime = Literal::u128_unsuffixed(*prime);
+        quote! {
+            lfsr_base::discrete_log::Subgroup {
+                prime: #prime,
+                multiplicity: #multiplicity,
+                giant_step: #giant_step,
+                baby_steps: &[#(#baby_steps),*],
+            }
+        }
+    });
+    let low = Literal::u128_unsuffixed(low);
+
+    let expanded = quote! {
+        fn #name(lfsr: &#lfsr_ident) -> Option<#state_type> {
+            #consistency_check
+            const FIELD: lfsr_base::discrete_log::Field<#state_type> = lfsr_base::discrete_log::Field {
+                degree: #degree,
+                low: #low,
+                subgroups: &[#(#subgroups),*],
+            };
+
+            FIELD.galois_position(lfsr.state)
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[deprecated(
+    since = "0.3.0",
+    note = "use searching_lfsr_lookup!(..) instead; or use direct_lfsr_lookup!(..) for small LUTs"
+)]
 #[proc_macro]
 pub fn lfsr_lookup(input: TokenStream) -> TokenStream {
     searching_lfsr_lookup(input)